@@ -79,6 +79,172 @@ pub mod vertex_decode {
     }
 }
 
+/// ## Tasks
+///
+/// 1. Parse the header.
+/// 2. Read the payload in big endian.
+/// 3. Ensure that user can access the data.
+pub mod vertex_decode_big_endian {
+    pub use super::*;
+
+    #[divan::bench(sample_count = 25, sample_size = 2)]
+    fn ply_rs(bencher: Bencher) {
+        use ply_rs::{
+            parser::Parser,
+            ply::{DefaultElement, Property::*},
+        };
+
+        bencher.with_inputs(data::get()).bench_local_refs(|v| {
+            let mut reader = Cursor::new(v);
+            let parser = Parser::<DefaultElement>::new();
+            let object = parser.read_ply(&mut reader).unwrap();
+            let element = &object.payload["vertex"][0];
+            let flags = &[&element["x"], &element["y"], &element["z"]].map(|v| match v {
+                Float(v) => *v,
+                other => panic!("{:?}", other),
+            });
+            assert_eq!(data::FLAGS, flags);
+            object
+        })
+    }
+
+    #[divan::bench(sample_count = 25, sample_size = 2)]
+    fn polygon(bencher: Bencher) {
+        use gausplat_loader::source::polygon::{Decoder, Object};
+
+        bencher.with_inputs(data::get()).bench_local_refs(|v| {
+            let mut reader = Cursor::new(v);
+            let object = Object::decode(&mut reader).unwrap();
+            let element = object.elem("vertex").unwrap();
+            let flags = &[element.prop("x"), element.prop("y"), element.prop("z")]
+                .map(|v| v.unwrap().cast::<f32>().unwrap()[0]);
+            assert_eq!(data::FLAGS, flags);
+            object
+        })
+    }
+
+    mod data {
+        pub use super::*;
+
+        pub const ELEMENT_COUNT: usize = 500500;
+        pub const ELEMENT_SIZE: usize = ELEMENT_COUNT * (3 * 4);
+        pub const FLAGS: &[f32; 3] = &[0.123456, -7.89012, 3.456789];
+        pub const HEADER: &[u8] = b"\
+            ply\n\
+            format binary_big_endian 1.0\n\
+            element vertex 500500\n\
+            property float x\n\
+            property float y\n\
+            property float z\n\
+            end_header\n\
+        ";
+
+        pub fn get() -> impl FnMut() -> Vec<u8> {
+            move || {
+                let mut object = HEADER.to_owned();
+                let mut data = random::get_vec_u8(ELEMENT_SIZE)();
+                let flags = FLAGS.map(f32::to_be_bytes).concat();
+                data[0..flags.len()].copy_from_slice(&flags);
+                object.extend(data);
+                object
+            }
+        }
+    }
+}
+
+/// ## Tasks
+///
+/// 1. Parse the header.
+/// 2. Read the payload of many properties.
+/// 3. Ensure that user can access the data.
+pub mod gaussian_splat_decode {
+    pub use super::*;
+
+    #[divan::bench(sample_count = 10, sample_size = 2)]
+    fn ply_rs(bencher: Bencher) {
+        use ply_rs::{
+            parser::Parser,
+            ply::{DefaultElement, Property::*},
+        };
+
+        bencher.with_inputs(data::get()).bench_local_refs(|v| {
+            let mut reader = Cursor::new(v);
+            let parser = Parser::<DefaultElement>::new();
+            let object = parser.read_ply(&mut reader).unwrap();
+            let element = &object.payload["vertex"][0];
+            let flags = &[&element["x"], &element["opacity"], &element["rot_3"]].map(
+                |v| match v {
+                    Float(v) => *v,
+                    other => panic!("{:?}", other),
+                },
+            );
+            assert_eq!(data::FLAGS, flags);
+            object
+        })
+    }
+
+    #[divan::bench(sample_count = 10, sample_size = 2)]
+    fn polygon(bencher: Bencher) {
+        use gausplat_loader::source::polygon::{Decoder, Object};
+
+        bencher.with_inputs(data::get()).bench_local_refs(|v| {
+            let mut reader = Cursor::new(v);
+            let object = Object::decode(&mut reader).unwrap();
+            let element = object.elem("vertex").unwrap();
+            let flags = &[
+                element.prop("x"),
+                element.prop("opacity"),
+                element.prop("rot_3"),
+            ]
+            .map(|v| v.unwrap().cast::<f32>().unwrap()[0]);
+            assert_eq!(data::FLAGS, flags);
+            object
+        })
+    }
+
+    mod data {
+        pub use super::*;
+
+        /// The properties of Gaussian splats with SH degree 3.
+        pub const PROPERTY_COUNT: usize = 62;
+        pub const ELEMENT_COUNT: usize = 100100;
+        pub const ELEMENT_SIZE: usize = ELEMENT_COUNT * (PROPERTY_COUNT * 4);
+        pub const FLAGS: &[f32; 3] = &[0.123456, -7.89012, 3.456789];
+        pub const FLAG_INDICES: &[usize; 3] = &[0, 54, 61];
+
+        pub fn get() -> impl FnMut() -> Vec<u8> {
+            move || {
+                let mut object = get_header().into_bytes();
+                let mut data = random::get_vec_u8(ELEMENT_SIZE)();
+                FLAG_INDICES.iter().zip(FLAGS).for_each(|(index, flag)| {
+                    data[index * 4..(index + 1) * 4].copy_from_slice(&flag.to_le_bytes())
+                });
+                object.extend(data);
+                object
+            }
+        }
+
+        pub fn get_header() -> String {
+            let properties = ["x", "y", "z", "nx", "ny", "nz"]
+                .map(String::from)
+                .into_iter()
+                .chain((0..3).map(|index| format!("f_dc_{index}")))
+                .chain((0..45).map(|index| format!("f_rest_{index}")))
+                .chain(["opacity", "scale_0", "scale_1", "scale_2"].map(String::from))
+                .chain(["rot_0", "rot_1", "rot_2", "rot_3"].map(String::from))
+                .map(|name| format!("property float {name}\n"))
+                .collect::<String>();
+            format!(
+                "ply\n\
+                format binary_little_endian 1.0\n\
+                element vertex {ELEMENT_COUNT}\n\
+                {properties}\
+                end_header\n"
+            )
+        }
+    }
+}
+
 /// ## Tasks
 ///
 /// 1. Parse the header.
@@ -35,10 +35,26 @@ pub enum Error {
     #[error("Invalid UTF-8 string: {0:?}.")]
     InvalidUtf8(String),
 
+    /// Error from the `serde_json` crate.
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    /// Error from the mismatched camera parameter count.
+    #[error("Mismatched camera parameter count: {0}. It should be {1}.")]
+    MismatchedCameraParameterCount(usize, usize),
+
+    /// Error from the mismatched element count.
+    #[error("Mismatched element count: {0}. It should be {1} at {2}.")]
+    MismatchedElementCount(usize, usize, String),
+
     /// Error from the mismatched [tensor shape](burn_tensor::Shape).
     #[error("Mismatched tensor shape: {0:?}. It should be {1:?}.")]
     MismatchedTensorShape(Vec<usize>, Vec<usize>),
 
+    /// Error from the missing file or directory.
+    #[error("Missing file: {0:?}.")]
+    MissingFile(std::path::PathBuf),
+
     /// Error from the missing symbol.
     ///
     /// It generally comes from the decoding process.
@@ -49,7 +65,23 @@ pub enum Error {
     #[error("Out of bounds: {0} is out of {1} at {2}.")]
     OutOfBounds(usize, usize, String),
 
+    /// Error from the [`std::num::ParseFloatError`].
+    #[error("Parse float error: {0}")]
+    ParseFloatError(#[from] std::num::ParseFloatError),
+
     /// Error from the [`std::num::ParseIntError`].
     #[error("Parse int error: {0}")]
     ParseIntError(#[from] std::num::ParseIntError),
+
+    /// Error from the [`std::num::TryFromIntError`].
+    #[error("Try from int error: {0}")]
+    TryFromIntError(#[from] std::num::TryFromIntError),
+
+    /// Error from the unsupported kind.
+    #[error("Unsupported kind: {0:?}.")]
+    UnsupportedKind(String),
+
+    /// Error from the [`roxmltree::Error`].
+    #[error("XML error: {0}")]
+    Xml(#[from] roxmltree::Error),
 }
@@ -70,6 +70,23 @@ pub fn read_bytes(
     Ok(bytes)
 }
 
+/// Reading `n` bytes without preallocating them.
+///
+/// It is used when `n` comes from untrusted input,
+/// so that a truncated input fails before allocating `n` bytes.
+#[inline]
+pub fn read_bytes_untrusted(
+    reader: &mut impl Read,
+    n: usize,
+) -> Result<Vec<u8>, Error> {
+    let mut bytes = Vec::new();
+    reader.take(n as u64).read_to_end(&mut bytes)?;
+    if bytes.len() != n {
+        return Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof).into());
+    }
+    Ok(bytes)
+}
+
 /// Reading a byte after all delimiter bytes.
 #[inline]
 pub fn read_byte_after(
@@ -256,6 +273,27 @@ mod tests {
         read_bytes(reader, 1).unwrap_err();
     }
 
+    #[test]
+    fn read_bytes_untrusted() {
+        use super::*;
+        use std::io::Cursor;
+
+        let source =
+            &include_bytes!("../../examples/data/hello-world/ascii+binary.dat")[..];
+        let reader = &mut Cursor::new(source);
+
+        let target = &source[0..24];
+        let output = read_bytes_untrusted(reader, 24).unwrap();
+        assert_eq!(output, target);
+
+        let target = true;
+        let output = matches!(
+            read_bytes_untrusted(reader, usize::MAX).unwrap_err(),
+            Error::Io(err) if err.kind() == std::io::ErrorKind::UnexpectedEof
+        );
+        assert_eq!(output, target);
+    }
+
     #[test]
     fn read_byte_after() {
         use super::*;
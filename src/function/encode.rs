@@ -27,6 +27,19 @@ pub trait Encoder: Sized {
     ) -> Result<(), Self::Err>;
 }
 
+/// Encoding function with an initial state.
+pub trait EncoderWith<T>: Sized {
+    /// Error type.
+    type Err;
+
+    /// Encode bytes to the writer with the `init` state.
+    fn encode_with(
+        &self,
+        writer: &mut impl Write,
+        init: T,
+    ) -> Result<(), Self::Err>;
+}
+
 /// Convert bytes to an ASCII string.
 ///
 /// # Errors
@@ -0,0 +1,244 @@
+//! Bundler bundle module.
+
+pub use super::*;
+
+use super::super::colmap::format::{is_data_line, parse_token};
+use std::io::{BufReader, Read};
+
+/// The Bundler reconstruction in `bundle.out`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Bundle {
+    /// Cameras in the order of `list.txt`.
+    pub cameras: Vec<BundleCamera>,
+    /// Points.
+    pub points: Vec<BundlePoint>,
+}
+
+/// A camera in [`Bundle`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct BundleCamera {
+    /// Focal length.
+    ///
+    /// It is zero if the camera is not registered.
+    pub focal_length: f64,
+    /// Radial distortion coefficient 1.
+    pub k1: f64,
+    /// Radial distortion coefficient 2.
+    pub k2: f64,
+    /// The 3D rotation from world space to view space.
+    ///
+    /// It is in **row-major order**, i.e., `M[row][col]`.
+    pub rotation: [[f64; 3]; 3],
+    /// Translation in view space.
+    pub translation: [f64; 3],
+}
+
+/// A point in [`Bundle`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BundlePoint {
+    /// Position in world space.
+    pub position: [f64; 3],
+    /// Color in RGB.
+    pub color_rgb: [u8; 3],
+    /// Views of the point.
+    pub views: Vec<BundleView>,
+}
+
+/// A view of [`BundlePoint`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct BundleView {
+    /// The index of [`Bundle::cameras`].
+    pub camera_index: u32,
+    /// The index of the feature (key) in the image.
+    pub key: u32,
+    /// Position in image space.
+    ///
+    /// The origin is at the image center, and the y axis points up.
+    pub position: [f64; 2],
+}
+
+impl BundleCamera {
+    /// Check if the camera is registered.
+    #[inline]
+    pub fn is_registered(&self) -> bool {
+        self.focal_length != 0.0
+    }
+
+    /// Return the COLMAP camera with the image dimensions `(width, height)`.
+    ///
+    /// It is [`CameraVariant::Radial`] with the principal point at the image center.
+    pub fn camera(
+        &self,
+        camera_id: u32,
+        dimensions: (u64, u64),
+    ) -> Camera {
+        let (width, height) = dimensions;
+        Camera {
+            camera_id,
+            width,
+            height,
+            principal_point_x: width as f64 / 2.0,
+            principal_point_y: height as f64 / 2.0,
+            variant: CameraVariant::Radial {
+                focal_length: self.focal_length,
+                k1: self.k1,
+                k2: self.k2,
+            },
+        }
+    }
+
+    /// Return the COLMAP image with the pose of the camera.
+    ///
+    /// Bundler looks at -Z with Y up in view space, so the y and z axes are flipped.
+    pub fn image(
+        &self,
+        image_id: u32,
+        camera_id: u32,
+        file_name: CString,
+    ) -> Image {
+        let r = &self.rotation;
+        let t = &self.translation;
+        // NOTE: The rotation is transposed to column-major order.
+        let rotation = [0, 1, 2].map(|col| [r[0][col], -r[1][col], -r[2][col]]);
+
+        let mut image = Image {
+            image_id,
+            camera_id,
+            file_name,
+            translation: [t[0], -t[1], -t[2]],
+            ..Default::default()
+        };
+        image.set_rotation(&rotation);
+        image
+    }
+}
+
+impl Decoder for Bundle {
+    type Err = Error;
+
+    fn decode(reader: &mut impl Read) -> Result<Self, Self::Err> {
+        let mut text = String::new();
+        BufReader::new(reader).read_to_string(&mut text)?;
+        let tokens = &mut text
+            .lines()
+            .filter(|line| is_data_line(line))
+            .flat_map(str::split_whitespace);
+
+        let camera_count = parse_token::<usize>(tokens, "CAMERA_COUNT")?;
+        let point_count = parse_token::<usize>(tokens, "POINT_COUNT")?;
+
+        let cameras = (0..camera_count)
+            .map(|_| {
+                let focal_length = parse_token(tokens, "FOCAL_LENGTH")?;
+                let k1 = parse_token(tokens, "K1")?;
+                let k2 = parse_token(tokens, "K2")?;
+                let mut rotation = [[0.0; 3]; 3];
+                for row in rotation.iter_mut() {
+                    for value in row.iter_mut() {
+                        *value = parse_token(tokens, "ROTATION")?;
+                    }
+                }
+                let mut translation = [0.0; 3];
+                for value in translation.iter_mut() {
+                    *value = parse_token(tokens, "TRANSLATION")?;
+                }
+                Ok(BundleCamera {
+                    focal_length,
+                    k1,
+                    k2,
+                    rotation,
+                    translation,
+                })
+            })
+            .collect::<Result<_, Error>>()?;
+
+        let points = (0..point_count)
+            .map(|_| {
+                let mut position = [0.0; 3];
+                for value in position.iter_mut() {
+                    *value = parse_token(tokens, "POSITION")?;
+                }
+                let mut color_rgb = [0; 3];
+                for value in color_rgb.iter_mut() {
+                    *value = parse_token(tokens, "COLOR")?;
+                }
+                let view_count = parse_token::<usize>(tokens, "VIEW_COUNT")?;
+                let views = (0..view_count)
+                    .map(|_| {
+                        Ok(BundleView {
+                            camera_index: parse_token(tokens, "CAMERA_INDEX")?,
+                            key: parse_token(tokens, "KEY")?,
+                            position: [
+                                parse_token(tokens, "X")?,
+                                parse_token(tokens, "Y")?,
+                            ],
+                        })
+                    })
+                    .collect::<Result<_, Error>>()?;
+                Ok(BundlePoint {
+                    position,
+                    color_rgb,
+                    views,
+                })
+            })
+            .collect::<Result<_, Error>>()?;
+
+        #[cfg(all(debug_assertions, not(test)))]
+        log::debug!(target: "gausplat-loader::bundler::bundle", "Bundle::decode");
+
+        Ok(Self { cameras, points })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn decode() {
+        use super::*;
+
+        let source =
+            &mut std::fs::File::open("examples/data/bundler/0/bundle.out").unwrap();
+        let bundle = Bundle::decode(source).unwrap();
+
+        let target = (3, 2);
+        let output = (bundle.cameras.len(), bundle.points.len());
+        assert_eq!(output, target);
+
+        let target = (true, false);
+        let output = (
+            bundle.cameras[0].is_registered(),
+            bundle.cameras[1].is_registered(),
+        );
+        assert_eq!(output, target);
+
+        let target = vec![
+            BundleView {
+                camera_index: 0,
+                key: 3,
+                position: [0.5, -1.0],
+            },
+            BundleView {
+                camera_index: 2,
+                key: 7,
+                position: [-1.0, 0.25],
+            },
+        ];
+        let output = bundle.points[0].views.to_owned();
+        assert_eq!(output, target);
+    }
+
+    #[test]
+    fn decode_on_truncated_bundle() {
+        use super::*;
+        use std::io::Cursor;
+
+        let source = &mut Cursor::new(b"# Bundle file v0.3\n1 0\n10.0 0.1\n");
+
+        let target = true;
+        let output = matches!(
+            Bundle::decode(source).unwrap_err(),
+            Error::MissingSymbol(name) if name == "K2"
+        );
+        assert_eq!(output, target);
+    }
+}
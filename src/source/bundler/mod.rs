@@ -0,0 +1,315 @@
+//! Bundler source.
+//!
+//! It supports the `bundle.out` and `list.txt` files of Bundler,
+//! which are also exported by VisualSFM and RealityCapture.
+//!
+//! For more information, see the Bundler
+//! [documentation](https://www.cs.cornell.edu/~snavely/bundler/bundler-v0.4-manual.html).
+
+pub mod bundle;
+
+pub use super::colmap::{
+    Camera, CameraVariant, Cameras, ColmapSource, Image, ImagePoint, Images, Point,
+    Points, TrackElement,
+};
+pub use super::file::*;
+pub use crate::function::Decoder;
+pub use bundle::*;
+
+use std::{
+    ffi::CString,
+    fmt, fs,
+    io::{BufReader, Read},
+    path::{Path, PathBuf},
+};
+
+/// Bundler source.
+///
+/// It is converted to the cameras, images and points of [`ColmapSource`].
+#[derive(Clone, PartialEq)]
+pub struct BundlerSource<S> {
+    /// Cameras.
+    pub cameras: Cameras,
+    /// Images.
+    pub images: Images,
+    /// Images' file.
+    pub images_file: Files<S>,
+    /// Points.
+    pub points: Points,
+}
+
+impl Opener for BundlerSource<fs::File> {
+    /// Opening the Bundler scene in the directory.
+    ///
+    /// It reads `bundle.out` or `bundle/bundle.out`, and `list.txt`.
+    /// The image file paths in `list.txt` are relative to the directory.
+    ///
+    /// The unregistered cameras are skipped.
+    /// The [image ID](Image::image_id) and [camera ID](Camera::camera_id)
+    /// are the one-based line number in `list.txt`.
+    ///
+    /// The files are opened in read-only mode.
+    ///
+    /// # Errors
+    ///
+    /// It returns [`Error::MissingFile`] with the path of any missing file.
+    fn open(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let bundle_path = ["bundle.out", "bundle/bundle.out"]
+            .into_iter()
+            .map(|bundle_path| path.join(bundle_path))
+            .find(|bundle_path| bundle_path.is_file())
+            .ok_or_else(|| Error::MissingFile(path.join("bundle.out")))?;
+        let list_path = path.join("list.txt");
+        if !list_path.is_file() {
+            return Err(Error::MissingFile(list_path));
+        }
+
+        let bundle = Bundle::decode(&mut fs::File::open(bundle_path)?)?;
+        let mut list = String::new();
+        BufReader::new(fs::File::open(list_path)?).read_to_string(&mut list)?;
+        // NOTE: The line may be followed by the focal length.
+        let file_paths = list
+            .lines()
+            .filter_map(|line| line.split_whitespace().next())
+            .map(PathBuf::from)
+            .collect::<Vec<_>>();
+        if file_paths.len() != bundle.cameras.len() {
+            return Err(Error::MismatchedTensorShape(
+                vec![file_paths.len()],
+                vec![bundle.cameras.len()],
+            ));
+        }
+
+        let mut cameras = Cameras::default();
+        let mut images = Images::default();
+        let mut images_file = Files::default();
+
+        for (index, (camera_bundle, file_path)) in
+            bundle.cameras.iter().zip(file_paths).enumerate()
+        {
+            if !camera_bundle.is_registered() {
+                continue;
+            }
+            let id = index as u32 + 1;
+            let path = path.join(&file_path);
+            if !path.is_file() {
+                return Err(Error::MissingFile(path));
+            }
+
+            let (width, height) = ::image::ImageReader::open(&path)?.into_dimensions()?;
+            cameras.insert(id, camera_bundle.camera(id, (width as u64, height as u64)));
+
+            let file_name = file_path
+                .file_name()
+                .and_then(|file_name| file_name.to_str())
+                .ok_or_else(|| Error::InvalidUtf8(file_path.to_string_lossy().into()))?;
+            let file_name = CString::new(file_name)
+                .map_err(|err| Error::InvalidUtf8(format!("{:?}", err.into_vec())))?;
+            let mut image = camera_bundle.image(id, id, file_name);
+            image.points = Some(vec![]);
+            images.insert(id, image);
+
+            let inner = fs::File::open(&path)?;
+            images_file.insert(path.to_owned(), File { inner, path });
+        }
+
+        let points = bundle
+            .points
+            .into_iter()
+            .enumerate()
+            .map(|(index, point_bundle)| {
+                let point_id = index as u64 + 1;
+                let track = point_bundle
+                    .views
+                    .iter()
+                    .filter_map(|view| {
+                        let image_id = view.camera_index + 1;
+                        let image = images.get_mut(&image_id)?;
+                        let camera = &cameras[&image.camera_id];
+                        let points = image.points.as_mut()?;
+                        // NOTE: The y axis of Bundler image space points up.
+                        points.push(ImagePoint {
+                            position: [
+                                camera.principal_point_x + view.position[0],
+                                camera.principal_point_y - view.position[1],
+                            ],
+                            point_id,
+                        });
+                        Some(TrackElement {
+                            image_id,
+                            point_index: points.len() as u32 - 1,
+                        })
+                    })
+                    .collect();
+                let point = Point {
+                    point_id,
+                    position: point_bundle.position,
+                    color_rgb: point_bundle.color_rgb,
+                    // NOTE: Bundler exports no re-projection error.
+                    error: -1.0,
+                    track,
+                };
+                (point_id, point)
+            })
+            .collect();
+
+        #[cfg(all(debug_assertions, not(test)))]
+        log::debug!(target: "gausplat-loader::bundler", "BundlerSource::open");
+
+        Ok(Self {
+            cameras,
+            images,
+            images_file,
+            points,
+        })
+    }
+}
+
+impl<S> From<BundlerSource<S>> for ColmapSource<S> {
+    #[inline]
+    fn from(source: BundlerSource<S>) -> Self {
+        Self {
+            cameras: source.cameras,
+            images: source.images,
+            images_file: source.images_file,
+            points: source.points,
+        }
+    }
+}
+
+impl<S> fmt::Debug for BundlerSource<S> {
+    #[inline]
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        f.debug_struct("BundlerSource")
+            .field("cameras.len()", &self.cameras.len())
+            .field("images.len()", &self.images.len())
+            .field("images_file.len()", &self.images_file.len())
+            .field("points.len()", &self.points.len())
+            .finish()
+    }
+}
+
+impl<S: Default> Default for BundlerSource<S> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            cameras: Default::default(),
+            images: Default::default(),
+            images_file: Default::default(),
+            points: Default::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn open() {
+        use super::*;
+
+        let source = BundlerSource::open("examples/data/bundler/0").unwrap();
+
+        let target = vec![
+            Camera {
+                camera_id: 1,
+                width: 8,
+                height: 8,
+                principal_point_x: 4.0,
+                principal_point_y: 4.0,
+                variant: CameraVariant::Radial {
+                    focal_length: 10.0,
+                    k1: 0.1,
+                    k2: 0.01,
+                },
+            },
+            Camera {
+                camera_id: 3,
+                width: 8,
+                height: 8,
+                principal_point_x: 4.0,
+                principal_point_y: 4.0,
+                variant: CameraVariant::Radial {
+                    focal_length: 12.0,
+                    k1: 0.0,
+                    k2: 0.0,
+                },
+            },
+        ];
+        let output = source.cameras.values().copied().collect::<Vec<_>>();
+        assert_eq!(output, target);
+
+        let target = Image {
+            image_id: 1,
+            quaternion: [0.0, 1.0, 0.0, 0.0],
+            translation: [0.0, 0.0, 5.0],
+            camera_id: 1,
+            file_name: c"001.png".into(),
+            points: Some(vec![
+                ImagePoint {
+                    position: [4.5, 5.0],
+                    point_id: 1,
+                },
+                ImagePoint {
+                    position: [5.0, 3.0],
+                    point_id: 2,
+                },
+            ]),
+        };
+        let output = source.images[&1].to_owned();
+        assert_eq!(output, target);
+
+        let target = [0.0, 0.0, 5.0];
+        let image = &source.images[&1];
+        let output = image.position(&image.rotation());
+        assert_eq!(output, target);
+
+        let target = Point {
+            point_id: 1,
+            position: [0.0, 0.0, 0.0],
+            color_rgb: [255, 0, 0],
+            error: -1.0,
+            track: vec![
+                TrackElement {
+                    image_id: 1,
+                    point_index: 0,
+                },
+                TrackElement {
+                    image_id: 3,
+                    point_index: 0,
+                },
+            ],
+        };
+        let output = source.points[&1].to_owned();
+        assert_eq!(output, target);
+
+        let target = [3.0, 3.75];
+        let output = source.images[&3].points.as_ref().unwrap()[0].position;
+        assert_eq!(output, target);
+
+        let target = (2, 2, 2, 2);
+        let output = ColmapSource::from(source);
+        let output = (
+            output.cameras.len(),
+            output.images.len(),
+            output.images_file.len(),
+            output.points.len(),
+        );
+        assert_eq!(output, target);
+    }
+
+    #[test]
+    fn open_on_missing_files() {
+        use super::*;
+
+        let target = true;
+        let output = matches!(
+            BundlerSource::open("examples/data/bundler").unwrap_err(),
+            Error::MissingFile(path) if path.ends_with("bundle.out")
+        );
+        assert_eq!(output, target);
+    }
+}
@@ -47,6 +47,90 @@ impl Encoder for Cameras {
     }
 }
 
+impl DecoderWith<Format> for Cameras {
+    type Err = Error;
+
+    fn decode_with(
+        reader: &mut impl Read,
+        init: Format,
+    ) -> Result<Self, Self::Err> {
+        if init == Format::Binary {
+            return Self::decode(reader);
+        }
+
+        let mut text = String::new();
+        BufReader::new(reader).read_to_string(&mut text)?;
+
+        let cameras = text
+            .lines()
+            .filter(|line| is_data_line(line))
+            .map(|line| {
+                let tokens = &mut line.split_whitespace();
+                let camera_id = parse_token(tokens, "CAMERA_ID")?;
+                let model_name = next_token(tokens, "MODEL")?;
+                let model_id = Camera::model_id_from_name(model_name)
+                    .ok_or_else(|| Error::InvalidKind(model_name.into()))?;
+                let width = parse_token(tokens, "WIDTH")?;
+                let height = parse_token(tokens, "HEIGHT")?;
+                let parameters = tokens.map(str::parse).collect::<Result<Vec<_>, _>>()?;
+                let camera = Camera::from_parameters(
+                    camera_id,
+                    model_id,
+                    width,
+                    height,
+                    &parameters,
+                )?;
+                Ok((camera.camera_id, camera))
+            })
+            .collect();
+
+        #[cfg(all(debug_assertions, not(test)))]
+        log::debug!(target: "gausplat-loader::colmap::camera", "Cameras::decode_with");
+
+        cameras
+    }
+}
+
+impl EncoderWith<Format> for Cameras {
+    type Err = Error;
+
+    fn encode_with(
+        &self,
+        writer: &mut impl Write,
+        init: Format,
+    ) -> Result<(), Self::Err> {
+        if init == Format::Binary {
+            return self.encode(writer);
+        }
+
+        let writer = &mut BufWriter::new(writer);
+
+        writeln!(writer, "# Camera list with one line of data per camera:")?;
+        writeln!(writer, "#   CAMERA_ID, MODEL, WIDTH, HEIGHT, PARAMS[]")?;
+        writeln!(writer, "# Number of cameras: {}", self.len())?;
+        self.values().try_for_each(|camera| {
+            write!(
+                writer,
+                "{} {} {} {}",
+                camera.camera_id,
+                camera.model_name(),
+                camera.width,
+                camera.height
+            )?;
+            camera
+                .parameters()
+                .into_iter()
+                .try_for_each(|parameter| write!(writer, " {parameter}"))?;
+            writeln!(writer)
+        })?;
+
+        #[cfg(all(debug_assertions, not(test)))]
+        log::debug!(target: "gausplat-loader::colmap::camera", "Cameras::encode_with");
+
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -108,6 +192,170 @@ mod tests {
         assert_eq!(output, target);
     }
 
+    #[test]
+    fn decode_and_encode_on_all_models() {
+        use super::super::*;
+        use std::io::Cursor;
+
+        let source =
+            &include_bytes!("../../../../examples/data/colmap/3/cameras.bin")[..];
+
+        let camera = |camera_id, variant| Camera {
+            camera_id,
+            width: 640,
+            height: 480,
+            principal_point_x: 320.0,
+            principal_point_y: 240.0,
+            variant,
+        };
+        let targets = [
+            camera(
+                1,
+                CameraVariant::SimplePinhole {
+                    focal_length: 1000.0,
+                },
+            ),
+            camera(
+                2,
+                CameraVariant::Pinhole {
+                    focal_length_x: 1000.0,
+                    focal_length_y: 1010.0,
+                },
+            ),
+            camera(
+                3,
+                CameraVariant::SimpleRadial {
+                    focal_length: 1000.0,
+                    k: 0.01,
+                },
+            ),
+            camera(
+                4,
+                CameraVariant::Radial {
+                    focal_length: 1000.0,
+                    k1: 0.01,
+                    k2: -0.002,
+                },
+            ),
+            camera(
+                5,
+                CameraVariant::OpenCv {
+                    focal_length_x: 1000.0,
+                    focal_length_y: 1010.0,
+                    k1: 0.01,
+                    k2: -0.002,
+                    p1: 0.0003,
+                    p2: -0.0004,
+                },
+            ),
+            camera(
+                6,
+                CameraVariant::OpenCvFisheye {
+                    focal_length_x: 1000.0,
+                    focal_length_y: 1010.0,
+                    k1: 0.01,
+                    k2: -0.002,
+                    k3: 0.0003,
+                    k4: -0.0004,
+                },
+            ),
+            camera(
+                7,
+                CameraVariant::FullOpenCv {
+                    focal_length_x: 1000.0,
+                    focal_length_y: 1010.0,
+                    k1: 0.01,
+                    k2: -0.002,
+                    p1: 0.0003,
+                    p2: -0.0004,
+                    k3: 0.00005,
+                    k4: 0.006,
+                    k5: -0.0007,
+                    k6: 0.00008,
+                },
+            ),
+            camera(
+                8,
+                CameraVariant::Fov {
+                    focal_length_x: 1000.0,
+                    focal_length_y: 1010.0,
+                    omega: 0.9,
+                },
+            ),
+            camera(
+                9,
+                CameraVariant::SimpleRadialFisheye {
+                    focal_length: 1000.0,
+                    k: 0.01,
+                },
+            ),
+            camera(
+                10,
+                CameraVariant::RadialFisheye {
+                    focal_length: 1000.0,
+                    k1: 0.01,
+                    k2: -0.002,
+                },
+            ),
+            camera(
+                11,
+                CameraVariant::ThinPrismFisheye {
+                    focal_length_x: 1000.0,
+                    focal_length_y: 1010.0,
+                    k1: 0.01,
+                    k2: -0.002,
+                    p1: 0.0003,
+                    p2: -0.0004,
+                    k3: 0.00005,
+                    k4: 0.006,
+                    sx1: -0.0007,
+                    sy1: 0.00008,
+                },
+            ),
+        ]
+        .into_iter()
+        .map(|camera| (camera.camera_id, camera))
+        .collect::<Cameras>();
+
+        (0..source.len()).for_each(|i| {
+            let mut reader = Cursor::new(&source[..i]);
+            Cameras::decode(&mut reader).unwrap_err();
+        });
+        let mut reader = Cursor::new(source);
+        let output = Cameras::decode(&mut reader).unwrap();
+        assert_eq!(output, targets);
+
+        let target = (0..=10).collect::<Vec<_>>();
+        let output = output.values().map(Camera::model_id).collect::<Vec<_>>();
+        assert_eq!(output, target);
+
+        let target = [
+            1000.0, 1000.0, 1000.0, 1000.0, 1000.0, 1000.0, 1000.0, 1000.0, 1000.0,
+            1000.0, 1000.0,
+        ];
+        let output = targets
+            .values()
+            .map(Camera::focal_length_x)
+            .collect::<Vec<_>>();
+        assert_eq!(output, target);
+
+        let target = [
+            1000.0, 1010.0, 1000.0, 1000.0, 1010.0, 1010.0, 1010.0, 1010.0, 1000.0,
+            1000.0, 1010.0,
+        ];
+        let output = targets
+            .values()
+            .map(Camera::focal_length_y)
+            .collect::<Vec<_>>();
+        assert_eq!(output, target);
+
+        let target = source;
+        let mut writer = Cursor::new(vec![]);
+        targets.encode(&mut writer).unwrap();
+        let output = writer.into_inner();
+        assert_eq!(output, target);
+    }
+
     #[test]
     fn decode_on_unknown_camera_model_id() {
         use super::*;
@@ -207,4 +455,60 @@ mod tests {
         let output = writer.into_inner();
         assert_eq!(output, target);
     }
+
+    #[test]
+    fn decode_with_and_encode_with_on_text() {
+        use super::super::*;
+        use std::io::Cursor;
+
+        let source_binary =
+            &include_bytes!("../../../../examples/data/colmap/0/cameras.bin")[..];
+        let source_text =
+            &include_bytes!("../../../../examples/data/colmap/4/cameras.txt")[..];
+
+        let target = Cameras::decode(&mut Cursor::new(source_binary)).unwrap();
+        let output =
+            Cameras::decode_with(&mut Cursor::new(source_binary), Format::Binary)
+                .unwrap();
+        assert_eq!(output, target);
+        let output =
+            Cameras::decode_with(&mut Cursor::new(source_text), Format::Text).unwrap();
+        assert_eq!(output, target);
+
+        let target = source_text;
+        let mut writer = Cursor::new(vec![]);
+        output.encode_with(&mut writer, Format::Text).unwrap();
+        let output = writer.into_inner();
+        assert_eq!(output, target);
+    }
+
+    #[test]
+    fn decode_with_on_invalid_text() {
+        use super::super::*;
+        use std::io::Cursor;
+
+        let source = &b"1 PINHOLE 640 480 500 500 320\n"[..];
+        let target = true;
+        let output = matches!(
+            Cameras::decode_with(&mut Cursor::new(source), Format::Text).unwrap_err(),
+            Error::MismatchedCameraParameterCount(3, 4)
+        );
+        assert_eq!(output, target);
+
+        let source = &b"1 PINHOLES 640 480 500 500 320 240\n"[..];
+        let target = true;
+        let output = matches!(
+            Cameras::decode_with(&mut Cursor::new(source), Format::Text).unwrap_err(),
+            Error::InvalidKind(name) if name == "PINHOLES"
+        );
+        assert_eq!(output, target);
+
+        let source = &b"1 PINHOLE 640\n"[..];
+        let target = true;
+        let output = matches!(
+            Cameras::decode_with(&mut Cursor::new(source), Format::Text).unwrap_err(),
+            Error::MissingSymbol(name) if name == "HEIGHT"
+        );
+        assert_eq!(output, target);
+    }
 }
@@ -1,16 +1,34 @@
 //! COLMAP camera module.
 
 pub mod cameras;
+pub mod projection;
 
+pub use super::format::Format;
 pub use crate::{
     error::Error,
-    function::{Decoder, Encoder},
+    function::{Decoder, DecoderWith, Encoder, EncoderWith},
 };
 pub use cameras::*;
 
+use super::format::{is_data_line, next_token, parse_token};
 use byteorder::{ReadBytesExt, WriteBytesExt, LE};
 use std::io::{BufReader, BufWriter, Read, Write};
 
+/// The model names indexed by [`Camera::model_id`].
+pub const MODEL_NAMES: [&str; 11] = [
+    "SIMPLE_PINHOLE",
+    "PINHOLE",
+    "SIMPLE_RADIAL",
+    "RADIAL",
+    "OPENCV",
+    "OPENCV_FISHEYE",
+    "FULL_OPENCV",
+    "FOV",
+    "SIMPLE_RADIAL_FISHEYE",
+    "RADIAL_FISHEYE",
+    "THIN_PRISM_FISHEYE",
+];
+
 /// A COLMAP camera.
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub struct Camera {
@@ -29,6 +47,8 @@ pub struct Camera {
 }
 
 /// A COLMAP camera variant.
+///
+/// The distortion coefficients follow the COLMAP camera models.
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum CameraVariant {
     /// Simple pinhole camera.
@@ -45,8 +65,131 @@ pub enum CameraVariant {
         /// Focal length y value.
         focal_length_y: f64,
     },
-    // TODO: Support more camera models from COLMAP.
-    // See https://github.com/colmap/colmap/blob/c238aec0e669610850badf3a3279dc2858f37f0f/src/colmap/sensor/models.h#L82
+    /// Simple radial camera.
+    SimpleRadial {
+        /// Focal length.
+        ///
+        /// It is the same for x and y.
+        focal_length: f64,
+        /// Radial distortion coefficient.
+        k: f64,
+    },
+    /// Radial camera.
+    Radial {
+        /// Focal length.
+        ///
+        /// It is the same for x and y.
+        focal_length: f64,
+        /// Radial distortion coefficient 1.
+        k1: f64,
+        /// Radial distortion coefficient 2.
+        k2: f64,
+    },
+    /// OpenCV camera.
+    OpenCv {
+        /// Focal length x value.
+        focal_length_x: f64,
+        /// Focal length y value.
+        focal_length_y: f64,
+        /// Radial distortion coefficient 1.
+        k1: f64,
+        /// Radial distortion coefficient 2.
+        k2: f64,
+        /// Tangential distortion coefficient 1.
+        p1: f64,
+        /// Tangential distortion coefficient 2.
+        p2: f64,
+    },
+    /// OpenCV fisheye camera.
+    OpenCvFisheye {
+        /// Focal length x value.
+        focal_length_x: f64,
+        /// Focal length y value.
+        focal_length_y: f64,
+        /// Radial distortion coefficient 1.
+        k1: f64,
+        /// Radial distortion coefficient 2.
+        k2: f64,
+        /// Radial distortion coefficient 3.
+        k3: f64,
+        /// Radial distortion coefficient 4.
+        k4: f64,
+    },
+    /// Full OpenCV camera.
+    FullOpenCv {
+        /// Focal length x value.
+        focal_length_x: f64,
+        /// Focal length y value.
+        focal_length_y: f64,
+        /// Radial distortion coefficient 1.
+        k1: f64,
+        /// Radial distortion coefficient 2.
+        k2: f64,
+        /// Tangential distortion coefficient 1.
+        p1: f64,
+        /// Tangential distortion coefficient 2.
+        p2: f64,
+        /// Radial distortion coefficient 3.
+        k3: f64,
+        /// Radial distortion coefficient 4.
+        k4: f64,
+        /// Radial distortion coefficient 5.
+        k5: f64,
+        /// Radial distortion coefficient 6.
+        k6: f64,
+    },
+    /// Field-of-view camera.
+    Fov {
+        /// Focal length x value.
+        focal_length_x: f64,
+        /// Focal length y value.
+        focal_length_y: f64,
+        /// Field of view distortion coefficient.
+        omega: f64,
+    },
+    /// Simple radial fisheye camera.
+    SimpleRadialFisheye {
+        /// Focal length.
+        ///
+        /// It is the same for x and y.
+        focal_length: f64,
+        /// Radial distortion coefficient.
+        k: f64,
+    },
+    /// Radial fisheye camera.
+    RadialFisheye {
+        /// Focal length.
+        ///
+        /// It is the same for x and y.
+        focal_length: f64,
+        /// Radial distortion coefficient 1.
+        k1: f64,
+        /// Radial distortion coefficient 2.
+        k2: f64,
+    },
+    /// Thin prism fisheye camera.
+    ThinPrismFisheye {
+        /// Focal length x value.
+        focal_length_x: f64,
+        /// Focal length y value.
+        focal_length_y: f64,
+        /// Radial distortion coefficient 1.
+        k1: f64,
+        /// Radial distortion coefficient 2.
+        k2: f64,
+        /// Tangential distortion coefficient 1.
+        p1: f64,
+        /// Tangential distortion coefficient 2.
+        p2: f64,
+        /// Radial distortion coefficient 3.
+        k3: f64,
+        /// Radial distortion coefficient 4.
+        k4: f64,
+        /// Thin prism distortion coefficient x.
+        sx1: f64,
+        /// Thin prism distortion coefficient y.
+        sy1: f64,
+    },
 }
 
 impl Camera {
@@ -58,17 +201,75 @@ impl Camera {
         match self.variant {
             SimplePinhole { .. } => 0,
             Pinhole { .. } => 1,
+            SimpleRadial { .. } => 2,
+            Radial { .. } => 3,
+            OpenCv { .. } => 4,
+            OpenCvFisheye { .. } => 5,
+            FullOpenCv { .. } => 6,
+            Fov { .. } => 7,
+            SimpleRadialFisheye { .. } => 8,
+            RadialFisheye { .. } => 9,
+            ThinPrismFisheye { .. } => 10,
         }
     }
 
+    /// Return the model name.
+    ///
+    /// It is the same as the name in the COLMAP text format.
+    #[inline]
+    pub const fn model_name(&self) -> &'static str {
+        MODEL_NAMES[self.model_id() as usize]
+    }
+
+    /// Return the model ID from the model name.
+    ///
+    /// It returns `None` if the model name is unknown.
+    #[inline]
+    pub fn model_id_from_name(model_name: &str) -> Option<u32> {
+        MODEL_NAMES
+            .iter()
+            .position(|name| *name == model_name)
+            .map(|model_id| model_id as u32)
+    }
+
+    /// Return the number of parameters of the model.
+    ///
+    /// It returns `None` if the model ID is unknown.
+    #[inline]
+    pub const fn parameter_count(model_id: u32) -> Option<usize> {
+        Some(match model_id {
+            0 => 3,
+            1 => 4,
+            2 => 4,
+            3 => 5,
+            4 => 8,
+            5 => 8,
+            6 => 12,
+            7 => 5,
+            8 => 4,
+            9 => 5,
+            10 => 12,
+            _ => return None,
+        })
+    }
+
     /// Return the focal length x value.
     #[inline]
     pub const fn focal_length_x(&self) -> f64 {
         use CameraVariant::*;
 
         match self.variant {
-            SimplePinhole { focal_length } => focal_length,
-            Pinhole { focal_length_x, .. } => focal_length_x,
+            SimplePinhole { focal_length }
+            | SimpleRadial { focal_length, .. }
+            | Radial { focal_length, .. }
+            | SimpleRadialFisheye { focal_length, .. }
+            | RadialFisheye { focal_length, .. } => focal_length,
+            Pinhole { focal_length_x, .. }
+            | OpenCv { focal_length_x, .. }
+            | OpenCvFisheye { focal_length_x, .. }
+            | FullOpenCv { focal_length_x, .. }
+            | Fov { focal_length_x, .. }
+            | ThinPrismFisheye { focal_length_x, .. } => focal_length_x,
         }
     }
 
@@ -78,51 +279,275 @@ impl Camera {
         use CameraVariant::*;
 
         match self.variant {
-            SimplePinhole { focal_length } => focal_length,
-            Pinhole { focal_length_y, .. } => focal_length_y,
+            SimplePinhole { focal_length }
+            | SimpleRadial { focal_length, .. }
+            | Radial { focal_length, .. }
+            | SimpleRadialFisheye { focal_length, .. }
+            | RadialFisheye { focal_length, .. } => focal_length,
+            Pinhole { focal_length_y, .. }
+            | OpenCv { focal_length_y, .. }
+            | OpenCvFisheye { focal_length_y, .. }
+            | FullOpenCv { focal_length_y, .. }
+            | Fov { focal_length_y, .. }
+            | ThinPrismFisheye { focal_length_y, .. } => focal_length_y,
         }
     }
-}
 
-impl Decoder for Camera {
-    type Err = Error;
+    /// Return the parameters in COLMAP order.
+    ///
+    /// The order is the focal length(s), the principal point,
+    /// and then the distortion coefficients.
+    pub fn parameters(&self) -> Vec<f64> {
+        use CameraVariant::*;
 
-    fn decode(reader: &mut impl Read) -> Result<Self, Self::Err> {
+        let x = self.principal_point_x;
+        let y = self.principal_point_y;
+        match self.variant {
+            SimplePinhole { focal_length } => vec![focal_length, x, y],
+            Pinhole {
+                focal_length_x,
+                focal_length_y,
+            } => vec![focal_length_x, focal_length_y, x, y],
+            SimpleRadial { focal_length, k } => vec![focal_length, x, y, k],
+            Radial {
+                focal_length,
+                k1,
+                k2,
+            } => vec![focal_length, x, y, k1, k2],
+            OpenCv {
+                focal_length_x,
+                focal_length_y,
+                k1,
+                k2,
+                p1,
+                p2,
+            } => vec![focal_length_x, focal_length_y, x, y, k1, k2, p1, p2],
+            OpenCvFisheye {
+                focal_length_x,
+                focal_length_y,
+                k1,
+                k2,
+                k3,
+                k4,
+            } => vec![focal_length_x, focal_length_y, x, y, k1, k2, k3, k4],
+            FullOpenCv {
+                focal_length_x,
+                focal_length_y,
+                k1,
+                k2,
+                p1,
+                p2,
+                k3,
+                k4,
+                k5,
+                k6,
+            } => vec![
+                focal_length_x,
+                focal_length_y,
+                x,
+                y,
+                k1,
+                k2,
+                p1,
+                p2,
+                k3,
+                k4,
+                k5,
+                k6,
+            ],
+            Fov {
+                focal_length_x,
+                focal_length_y,
+                omega,
+            } => vec![focal_length_x, focal_length_y, x, y, omega],
+            SimpleRadialFisheye { focal_length, k } => vec![focal_length, x, y, k],
+            RadialFisheye {
+                focal_length,
+                k1,
+                k2,
+            } => vec![focal_length, x, y, k1, k2],
+            ThinPrismFisheye {
+                focal_length_x,
+                focal_length_y,
+                k1,
+                k2,
+                p1,
+                p2,
+                k3,
+                k4,
+                sx1,
+                sy1,
+            } => vec![
+                focal_length_x,
+                focal_length_y,
+                x,
+                y,
+                k1,
+                k2,
+                p1,
+                p2,
+                k3,
+                k4,
+                sx1,
+                sy1,
+            ],
+        }
+    }
+
+    /// Create a camera from the parameters in COLMAP order.
+    ///
+    /// See [`Camera::parameters`] for the order.
+    pub fn from_parameters(
+        camera_id: u32,
+        model_id: u32,
+        width: u64,
+        height: u64,
+        parameters: &[f64],
+    ) -> Result<Self, Error> {
         use CameraVariant::*;
 
-        let camera_id = reader.read_u32::<LE>()?;
-        let model_id = reader.read_u32::<LE>()?;
-        let width = reader.read_u64::<LE>()?;
-        let height = reader.read_u64::<LE>()?;
-        let variant = match model_id {
-            0 => {
-                let focal_length = reader.read_f64::<LE>()?;
-                SimplePinhole { focal_length }
-            },
-            1 => {
-                let focal_length_x = reader.read_f64::<LE>()?;
-                let focal_length_y = reader.read_f64::<LE>()?;
+        let (variant, point_x, point_y) = match (model_id, parameters) {
+            (0, &[focal_length, x, y]) => (SimplePinhole { focal_length }, x, y),
+            (1, &[focal_length_x, focal_length_y, x, y]) => (
                 Pinhole {
                     focal_length_x,
                     focal_length_y,
-                }
+                },
+                x,
+                y,
+            ),
+            (2, &[focal_length, x, y, k]) => (SimpleRadial { focal_length, k }, x, y),
+            (3, &[focal_length, x, y, k1, k2]) => (
+                Radial {
+                    focal_length,
+                    k1,
+                    k2,
+                },
+                x,
+                y,
+            ),
+            (4, &[focal_length_x, focal_length_y, x, y, k1, k2, p1, p2]) => (
+                OpenCv {
+                    focal_length_x,
+                    focal_length_y,
+                    k1,
+                    k2,
+                    p1,
+                    p2,
+                },
+                x,
+                y,
+            ),
+            (5, &[focal_length_x, focal_length_y, x, y, k1, k2, k3, k4]) => (
+                OpenCvFisheye {
+                    focal_length_x,
+                    focal_length_y,
+                    k1,
+                    k2,
+                    k3,
+                    k4,
+                },
+                x,
+                y,
+            ),
+            (
+                6,
+                &[focal_length_x, focal_length_y, x, y, k1, k2, p1, p2, k3, k4, k5, k6],
+            ) => (
+                FullOpenCv {
+                    focal_length_x,
+                    focal_length_y,
+                    k1,
+                    k2,
+                    p1,
+                    p2,
+                    k3,
+                    k4,
+                    k5,
+                    k6,
+                },
+                x,
+                y,
+            ),
+            (7, &[focal_length_x, focal_length_y, x, y, omega]) => (
+                Fov {
+                    focal_length_x,
+                    focal_length_y,
+                    omega,
+                },
+                x,
+                y,
+            ),
+            (8, &[focal_length, x, y, k]) => {
+                (SimpleRadialFisheye { focal_length, k }, x, y)
+            },
+            (9, &[focal_length, x, y, k1, k2]) => (
+                RadialFisheye {
+                    focal_length,
+                    k1,
+                    k2,
+                },
+                x,
+                y,
+            ),
+            (
+                10,
+                &[focal_length_x, focal_length_y, x, y, k1, k2, p1, p2, k3, k4, sx1, sy1],
+            ) => (
+                ThinPrismFisheye {
+                    focal_length_x,
+                    focal_length_y,
+                    k1,
+                    k2,
+                    p1,
+                    p2,
+                    k3,
+                    k4,
+                    sx1,
+                    sy1,
+                },
+                x,
+                y,
+            ),
+            _ => {
+                return Err(match Self::parameter_count(model_id) {
+                    Some(count) => {
+                        Error::MismatchedCameraParameterCount(parameters.len(), count)
+                    },
+                    None => Error::InvalidCameraModelId(model_id),
+                })
             },
-            _ => return Err(Error::InvalidCameraModelId(model_id)),
         };
-        let principal_point_x = reader.read_f64::<LE>()?;
-        let principal_point_y = reader.read_f64::<LE>()?;
 
         Ok(Self {
             camera_id,
             width,
             height,
-            principal_point_x,
-            principal_point_y,
+            principal_point_x: point_x,
+            principal_point_y: point_y,
             variant,
         })
     }
 }
 
+impl Decoder for Camera {
+    type Err = Error;
+
+    fn decode(reader: &mut impl Read) -> Result<Self, Self::Err> {
+        let camera_id = reader.read_u32::<LE>()?;
+        let model_id = reader.read_u32::<LE>()?;
+        let width = reader.read_u64::<LE>()?;
+        let height = reader.read_u64::<LE>()?;
+        let parameter_count = Self::parameter_count(model_id)
+            .ok_or(Error::InvalidCameraModelId(model_id))?;
+        let parameters = (0..parameter_count)
+            .map(|_| reader.read_f64::<LE>())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Self::from_parameters(camera_id, model_id, width, height, &parameters)
+    }
+}
+
 impl Encoder for Camera {
     type Err = Error;
 
@@ -130,24 +555,13 @@ impl Encoder for Camera {
         &self,
         writer: &mut impl Write,
     ) -> Result<(), Self::Err> {
-        use CameraVariant::*;
-
         writer.write_u32::<LE>(self.camera_id)?;
         writer.write_u32::<LE>(self.model_id())?;
         writer.write_u64::<LE>(self.width)?;
         writer.write_u64::<LE>(self.height)?;
-        match self.variant {
-            SimplePinhole { focal_length } => writer.write_f64::<LE>(focal_length),
-            Pinhole {
-                focal_length_x,
-                focal_length_y,
-            } => {
-                writer.write_f64::<LE>(focal_length_x)?;
-                writer.write_f64::<LE>(focal_length_y)
-            },
-        }?;
-        writer.write_f64::<LE>(self.principal_point_x)?;
-        writer.write_f64::<LE>(self.principal_point_y)?;
+        self.parameters()
+            .into_iter()
+            .try_for_each(|parameter| writer.write_f64::<LE>(parameter))?;
 
         Ok(())
     }
@@ -172,4 +586,61 @@ mod tests {
         let output = Camera::default().variant;
         assert_eq!(output, target);
     }
+
+    #[test]
+    fn from_parameters() {
+        use super::*;
+
+        let target = Camera {
+            camera_id: 3,
+            width: 640,
+            height: 480,
+            principal_point_x: 320.0,
+            principal_point_y: 240.0,
+            variant: CameraVariant::OpenCv {
+                focal_length_x: 1000.0,
+                focal_length_y: 1010.0,
+                k1: 0.01,
+                k2: -0.002,
+                p1: 0.0003,
+                p2: -0.0004,
+            },
+        };
+        let output = Camera::from_parameters(
+            3,
+            4,
+            640,
+            480,
+            &[1000.0, 1010.0, 320.0, 240.0, 0.01, -0.002, 0.0003, -0.0004],
+        )
+        .unwrap();
+        assert_eq!(output, target);
+
+        let target = target.parameters();
+        let output = output.parameters();
+        assert_eq!(output, target);
+
+        let target = Camera::parameter_count(4).unwrap();
+        let output = output.len();
+        assert_eq!(output, target);
+    }
+
+    #[test]
+    fn from_parameters_on_invalid_input() {
+        use super::*;
+
+        let target = true;
+        let output = matches!(
+            Camera::from_parameters(1, 4, 640, 480, &[1000.0, 320.0, 240.0]).unwrap_err(),
+            Error::MismatchedCameraParameterCount(3, 8),
+        );
+        assert_eq!(output, target);
+
+        let target = true;
+        let output = matches!(
+            Camera::from_parameters(1, 11, 640, 480, &[]).unwrap_err(),
+            Error::InvalidCameraModelId(11),
+        );
+        assert_eq!(output, target);
+    }
 }
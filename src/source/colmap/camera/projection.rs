@@ -0,0 +1,486 @@
+//! Camera projection and unprojection.
+//!
+//! A point in view space is projected to the normalized image plane (`z = 1`),
+//! distorted by [`CameraVariant`], and then mapped to pixel coordinates
+//! with the focal lengths and the principal point.
+//!
+//! The center of the top-left pixel is at `(0.5, 0.5)`, following COLMAP.
+
+pub use super::*;
+pub use burn_tensor::{backend::Backend, Tensor};
+
+use burn_tensor::TensorData;
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+/// Projection operations.
+impl Camera {
+    /// Project a point in view space to a pixel.
+    ///
+    /// The point should be in front of the camera, i.e., `z > 0`.
+    pub fn project(
+        &self,
+        point: [f64; 3],
+    ) -> [f64; 2] {
+        let [x, y, z] = point;
+        let [u, v] = self.variant.distort([x / z, y / z]);
+        [
+            self.focal_length_x() * u + self.principal_point_x,
+            self.focal_length_y() * v + self.principal_point_y,
+        ]
+    }
+
+    /// Unproject a pixel at the `depth` to a point in view space.
+    ///
+    /// The `depth` is the z value in view space.
+    pub fn unproject(
+        &self,
+        pixel: [f64; 2],
+        depth: f64,
+    ) -> [f64; 3] {
+        let [u, v] = self.unproject_to_direction(pixel);
+        [u * depth, v * depth, depth]
+    }
+
+    /// Return the ray directions in view space of all pixel centers.
+    ///
+    /// The directions are in **row-major order** and have `z = 1`.
+    pub fn rays(&self) -> Vec<[f64; 3]> {
+        let width = self.width as usize;
+        let height = self.height as usize;
+        (0..height * width)
+            .into_par_iter()
+            .map(|index| {
+                let pixel = [(index % width) as f64 + 0.5, (index / width) as f64 + 0.5];
+                let [u, v] = self.unproject_to_direction(pixel);
+                [u, v, 1.0]
+            })
+            .collect()
+    }
+
+    /// Unproject a pixel to the normalized image plane.
+    #[inline]
+    fn unproject_to_direction(
+        &self,
+        pixel: [f64; 2],
+    ) -> [f64; 2] {
+        self.variant.undistort([
+            (pixel[0] - self.principal_point_x) / self.focal_length_x(),
+            (pixel[1] - self.principal_point_y) / self.focal_length_y(),
+        ])
+    }
+}
+
+/// Interoperability with [`Tensor`].
+impl Camera {
+    /// Projecting the points in view space with shape of `[N, 3]`
+    /// to the pixels with shape of `[N, 2]`.
+    pub fn project_tensor<B: Backend>(
+        &self,
+        points: Tensor<B, 2>,
+    ) -> Result<Tensor<B, 2>, Error> {
+        let [count, channel_count] = points.dims();
+        if channel_count != 3 {
+            return Err(Error::MismatchedTensorShape(
+                vec![count, channel_count],
+                vec![count, 3],
+            ));
+        }
+
+        let device = points.device();
+        let points = Self::get_values_from_tensor(points);
+        let pixels = (0..count)
+            .into_par_iter()
+            .flat_map_iter(|index| {
+                let point = &points[index * 3..index * 3 + 3];
+                self.project([point[0], point[1], point[2]])
+            })
+            .collect::<Vec<_>>();
+
+        Ok(Tensor::from_data(
+            TensorData::new(pixels, [count, 2]),
+            &device,
+        ))
+    }
+
+    /// Unprojecting the pixels with shape of `[N, 2]` at the depths with shape of `[N]`
+    /// to the points in view space with shape of `[N, 3]`.
+    pub fn unproject_tensor<B: Backend>(
+        &self,
+        pixels: Tensor<B, 2>,
+        depths: Tensor<B, 1>,
+    ) -> Result<Tensor<B, 2>, Error> {
+        let [count, channel_count] = pixels.dims();
+        if channel_count != 2 {
+            return Err(Error::MismatchedTensorShape(
+                vec![count, channel_count],
+                vec![count, 2],
+            ));
+        }
+        let depths_dims = depths.dims();
+        if depths_dims != [count] {
+            return Err(Error::MismatchedTensorShape(
+                depths_dims.into(),
+                vec![count],
+            ));
+        }
+
+        let device = pixels.device();
+        let pixels = Self::get_values_from_tensor(pixels);
+        let depths = Self::get_values_from_tensor(depths);
+        let points = (0..count)
+            .into_par_iter()
+            .flat_map_iter(|index| {
+                let pixel = [pixels[index * 2], pixels[index * 2 + 1]];
+                self.unproject(pixel, depths[index])
+            })
+            .collect::<Vec<_>>();
+
+        Ok(Tensor::from_data(
+            TensorData::new(points, [count, 3]),
+            &device,
+        ))
+    }
+
+    /// Obtaining a [`Tensor`] with shape of `[H, W, 3]` from [`Camera::rays`].
+    #[inline]
+    pub fn rays_tensor<B: Backend>(
+        &self,
+        device: &B::Device,
+    ) -> Tensor<B, 3> {
+        let rays = self.rays().into_iter().flatten().collect::<Vec<_>>();
+        let shape = [self.height as usize, self.width as usize, 3];
+        Tensor::from_data(TensorData::new(rays, shape), device)
+    }
+
+    /// Obtaining the values of a [`Tensor`] in double precision.
+    #[inline]
+    fn get_values_from_tensor<B: Backend, const D: usize>(
+        tensor: Tensor<B, D>
+    ) -> Vec<f64> {
+        // NOTE: The data type is converted.
+        tensor.into_data().convert::<f64>().into_vec().unwrap()
+    }
+}
+
+/// Distortion operations.
+impl CameraVariant {
+    /// Distort a point in the normalized image plane.
+    pub fn distort(
+        &self,
+        point: [f64; 2],
+    ) -> [f64; 2] {
+        use CameraVariant::*;
+
+        let [u, v] = point;
+        let u_u = u * u;
+        let u_v = u * v;
+        let v_v = v * v;
+        let r_2 = u_u + v_v;
+
+        match *self {
+            SimplePinhole { .. } | Pinhole { .. } => [u, v],
+            SimpleRadial { k, .. } => {
+                let radial = 1.0 + k * r_2;
+                [u * radial, v * radial]
+            },
+            Radial { k1, k2, .. } => {
+                let radial = 1.0 + k1 * r_2 + k2 * r_2 * r_2;
+                [u * radial, v * radial]
+            },
+            OpenCv { k1, k2, p1, p2, .. } => {
+                let radial = 1.0 + k1 * r_2 + k2 * r_2 * r_2;
+                [
+                    u * radial + 2.0 * p1 * u_v + p2 * (r_2 + 2.0 * u_u),
+                    v * radial + 2.0 * p2 * u_v + p1 * (r_2 + 2.0 * v_v),
+                ]
+            },
+            OpenCvFisheye { k1, k2, k3, k4, .. } => Self::distort_fisheye(point, |t_2| {
+                1.0 + t_2 * (k1 + t_2 * (k2 + t_2 * (k3 + t_2 * k4)))
+            }),
+            FullOpenCv {
+                k1,
+                k2,
+                p1,
+                p2,
+                k3,
+                k4,
+                k5,
+                k6,
+                ..
+            } => {
+                let radial = (1.0 + r_2 * (k1 + r_2 * (k2 + r_2 * k3)))
+                    / (1.0 + r_2 * (k4 + r_2 * (k5 + r_2 * k6)));
+                [
+                    u * radial + 2.0 * p1 * u_v + p2 * (r_2 + 2.0 * u_u),
+                    v * radial + 2.0 * p2 * u_v + p1 * (r_2 + 2.0 * v_v),
+                ]
+            },
+            Fov { omega, .. } => {
+                const EPSILON: f64 = 1e-4;
+
+                let omega_2 = omega * omega;
+                let factor = if omega_2 < EPSILON {
+                    omega_2 * r_2 / 3.0 - omega_2 / 12.0 + 1.0
+                } else if r_2 < EPSILON {
+                    let tan_half_omega = (omega / 2.0).tan();
+                    -2.0 * tan_half_omega
+                        * (4.0 * r_2 * tan_half_omega * tan_half_omega - 3.0)
+                        / (3.0 * omega)
+                } else {
+                    let r = r_2.sqrt();
+                    (r * 2.0 * (omega / 2.0).tan()).atan() / (r * omega)
+                };
+                [u * factor, v * factor]
+            },
+            SimpleRadialFisheye { k, .. } => {
+                Self::distort_fisheye(point, |t_2| 1.0 + k * t_2)
+            },
+            RadialFisheye { k1, k2, .. } => {
+                Self::distort_fisheye(point, |t_2| 1.0 + t_2 * (k1 + t_2 * k2))
+            },
+            ThinPrismFisheye {
+                k1,
+                k2,
+                p1,
+                p2,
+                k3,
+                k4,
+                sx1,
+                sy1,
+                ..
+            } => {
+                let [u, v] = Self::distort_fisheye(point, |_| 1.0);
+                let u_u = u * u;
+                let u_v = u * v;
+                let v_v = v * v;
+                let r_2 = u_u + v_v;
+                let radial = 1.0 + r_2 * (k1 + r_2 * (k2 + r_2 * (k3 + r_2 * k4)));
+                [
+                    u * radial + 2.0 * p1 * u_v + p2 * (r_2 + 2.0 * u_u) + sx1 * r_2,
+                    v * radial + 2.0 * p2 * u_v + p1 * (r_2 + 2.0 * v_v) + sy1 * r_2,
+                ]
+            },
+        }
+    }
+
+    /// Undistort a point in the normalized image plane.
+    ///
+    /// It inverts [`CameraVariant::distort`] by Newton's method
+    /// with a numerical Jacobian.
+    pub fn undistort(
+        &self,
+        point: [f64; 2],
+    ) -> [f64; 2] {
+        const ITERATION_COUNT: usize = 100;
+        const STEP_NORM_MAX: f64 = 1e-10;
+        const STEP_SIZE_RELATIVE: f64 = 1e-6;
+
+        if matches!(self, Self::SimplePinhole { .. } | Self::Pinhole { .. }) {
+            return point;
+        }
+
+        let mut output = point;
+        for _ in 0..ITERATION_COUNT {
+            let step = output.map(|x| (x.abs() * STEP_SIZE_RELATIVE).max(f64::EPSILON));
+            let d_u = [
+                self.distort([output[0] + step[0], output[1]]),
+                self.distort([output[0] - step[0], output[1]]),
+            ];
+            let d_v = [
+                self.distort([output[0], output[1] + step[1]]),
+                self.distort([output[0], output[1] - step[1]]),
+            ];
+            let jacobian = [
+                [
+                    (d_u[0][0] - d_u[1][0]) / (2.0 * step[0]),
+                    (d_v[0][0] - d_v[1][0]) / (2.0 * step[1]),
+                ],
+                [
+                    (d_u[0][1] - d_u[1][1]) / (2.0 * step[0]),
+                    (d_v[0][1] - d_v[1][1]) / (2.0 * step[1]),
+                ],
+            ];
+            let determinant =
+                jacobian[0][0] * jacobian[1][1] - jacobian[0][1] * jacobian[1][0];
+            if determinant == 0.0 || !determinant.is_finite() {
+                break;
+            }
+
+            let distorted = self.distort(output);
+            let residual = [distorted[0] - point[0], distorted[1] - point[1]];
+            let delta = [
+                (jacobian[1][1] * residual[0] - jacobian[0][1] * residual[1])
+                    / determinant,
+                (jacobian[0][0] * residual[1] - jacobian[1][0] * residual[0])
+                    / determinant,
+            ];
+            output = [output[0] - delta[0], output[1] - delta[1]];
+
+            if delta[0] * delta[0] + delta[1] * delta[1] < STEP_NORM_MAX {
+                break;
+            }
+        }
+
+        output
+    }
+
+    /// Distort a point with the equidistant fisheye projection.
+    ///
+    /// The `radial` function takes the squared incident angle.
+    #[inline]
+    fn distort_fisheye(
+        point: [f64; 2],
+        radial: impl Fn(f64) -> f64,
+    ) -> [f64; 2] {
+        let [u, v] = point;
+        let r = (u * u + v * v).sqrt();
+        if r <= f64::EPSILON {
+            return point;
+        }
+        let theta = r.atan();
+        let factor = theta * radial(theta * theta) / r;
+        [u * factor, v * factor]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn project_and_unproject() {
+        use super::*;
+
+        let camera = Camera {
+            camera_id: 1,
+            width: 640,
+            height: 480,
+            principal_point_x: 320.0,
+            principal_point_y: 240.0,
+            variant: CameraVariant::Pinhole {
+                focal_length_x: 1000.0,
+                focal_length_y: 500.0,
+            },
+        };
+
+        let target = [820.0, 140.0];
+        let output = camera.project([1.0, -0.4, 2.0]);
+        assert_eq!(output, target);
+
+        let target = [1.0, -0.4, 2.0];
+        let output = camera.unproject([820.0, 140.0], 2.0);
+        assert_eq!(output, target);
+    }
+
+    #[test]
+    fn project_and_unproject_on_all_models() {
+        use super::*;
+        use std::io::Cursor;
+
+        let source =
+            &include_bytes!("../../../../examples/data/colmap/3/cameras.bin")[..];
+        let cameras = Cameras::decode(&mut Cursor::new(source)).unwrap();
+
+        let points = [
+            [0.0, 0.0, 1.0],
+            [0.1, -0.05, 2.0],
+            [-0.3, 0.2, 1.5],
+            [0.4, 0.3, 0.8],
+        ];
+        cameras.values().for_each(|camera| {
+            points.iter().for_each(|&target| {
+                let pixel = camera.project(target);
+                let output = camera.unproject(pixel, target[2]);
+                let error = output
+                    .iter()
+                    .zip(target)
+                    .map(|(a, b)| (a - b).abs())
+                    .fold(0.0, f64::max);
+                assert!(error < 1e-9, "{camera:?} {target:?} {output:?}");
+            });
+        });
+    }
+
+    #[test]
+    fn rays_tensor() {
+        use super::*;
+        use burn_ndarray::NdArray;
+
+        let camera = Camera {
+            camera_id: 1,
+            width: 4,
+            height: 2,
+            principal_point_x: 2.0,
+            principal_point_y: 1.0,
+            variant: CameraVariant::SimplePinhole { focal_length: 2.0 },
+        };
+
+        let target = Tensor::<NdArray, 3>::from([
+            [
+                [-0.75, -0.25, 1.0],
+                [-0.25, -0.25, 1.0],
+                [0.25, -0.25, 1.0],
+                [0.75, -0.25, 1.0],
+            ],
+            [
+                [-0.75, 0.25, 1.0],
+                [-0.25, 0.25, 1.0],
+                [0.25, 0.25, 1.0],
+                [0.75, 0.25, 1.0],
+            ],
+        ]);
+        let output = camera.rays_tensor::<NdArray>(&Default::default());
+        output.into_data().assert_eq(&target.into_data(), true);
+    }
+
+    #[test]
+    fn project_and_unproject_tensor() {
+        use super::*;
+        use burn_ndarray::NdArray;
+
+        let camera = Camera {
+            camera_id: 1,
+            width: 640,
+            height: 480,
+            principal_point_x: 320.0,
+            principal_point_y: 240.0,
+            variant: CameraVariant::Pinhole {
+                focal_length_x: 1000.0,
+                focal_length_y: 500.0,
+            },
+        };
+        let device = Default::default();
+
+        let source = Tensor::<NdArray, 2>::from([[1.0, -0.4, 2.0], [0.0, 0.0, 4.0]]);
+        let target = Tensor::<NdArray, 2>::from([[820.0, 140.0], [320.0, 240.0]]);
+        let output = camera.project_tensor(source.to_owned()).unwrap();
+        output
+            .to_owned()
+            .into_data()
+            .assert_eq(&target.into_data(), true);
+
+        let depths = Tensor::<NdArray, 1>::from([2.0, 4.0]);
+        let target = source;
+        let output = camera.unproject_tensor(output, depths).unwrap();
+        output.into_data().assert_eq(&target.into_data(), true);
+
+        let source = Tensor::<NdArray, 2>::zeros([3, 4], &device);
+        let target = (vec![3, 4], vec![3, 3]);
+        let output = matches!(
+            camera.project_tensor(source.to_owned()).unwrap_err(),
+            Error::MismatchedTensorShape(output_0, output_1)
+            if output_0 == target.0 && output_1 == target.1,
+        );
+        let target = true;
+        assert_eq!(output, target);
+
+        let depths = Tensor::<NdArray, 1>::zeros([4], &device);
+        let source = Tensor::<NdArray, 2>::zeros([3, 2], &device);
+        let target = (vec![4], vec![3]);
+        let output = matches!(
+            camera.unproject_tensor(source, depths).unwrap_err(),
+            Error::MismatchedTensorShape(output_0, output_1)
+            if output_0 == target.0 && output_1 == target.1,
+        );
+        let target = true;
+        assert_eq!(output, target);
+    }
+}
@@ -0,0 +1,155 @@
+//! COLMAP model format.
+
+pub use crate::error::Error;
+
+use std::{fmt, path::Path, str::FromStr};
+
+/// COLMAP model format.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum Format {
+    /// Binary format, e.g., `cameras.bin`.
+    #[default]
+    Binary,
+    /// Text format, e.g., `cameras.txt`.
+    Text,
+}
+
+impl Format {
+    /// Detect the format of the sparse model in the `directory`.
+    ///
+    /// It checks `cameras.bin` first, and then `cameras.txt`.
+    ///
+    /// # Errors
+    ///
+    /// It returns [`Error::MissingFile`] with the path of `cameras.bin`
+    /// if neither file exists.
+    pub fn detect(directory: impl AsRef<Path>) -> Result<Self, Error> {
+        let directory = directory.as_ref();
+
+        [Self::Binary, Self::Text]
+            .into_iter()
+            .find(|format| directory.join(format.file_name("cameras")).is_file())
+            .ok_or_else(|| {
+                Error::MissingFile(directory.join(Self::Binary.file_name("cameras")))
+            })
+    }
+
+    /// Return the file extension.
+    #[inline]
+    pub const fn extension(&self) -> &'static str {
+        match self {
+            Self::Binary => "bin",
+            Self::Text => "txt",
+        }
+    }
+
+    /// Return the file name of the `stem`, e.g., `cameras.bin`.
+    #[inline]
+    pub fn file_name(
+        &self,
+        stem: &str,
+    ) -> String {
+        format!("{stem}.{}", self.extension())
+    }
+}
+
+impl fmt::Display for Format {
+    #[inline]
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        f.write_str(match self {
+            Self::Binary => "binary",
+            Self::Text => "text",
+        })
+    }
+}
+
+/// Checking if the line in the text format contains data.
+///
+/// It returns `false` for empty lines and comments.
+#[inline]
+pub fn is_data_line(line: &str) -> bool {
+    let line = line.trim();
+    !line.is_empty() && !line.starts_with('#')
+}
+
+/// Taking the next token in the text format.
+///
+/// It returns [`Error::MissingSymbol`] with the `name` if there is no token.
+#[inline]
+pub fn next_token<'s>(
+    tokens: &mut impl Iterator<Item = &'s str>,
+    name: &str,
+) -> Result<&'s str, Error> {
+    tokens
+        .next()
+        .ok_or_else(|| Error::MissingSymbol(name.into()))
+}
+
+/// Parsing the next token in the text format.
+///
+/// See [`next_token`] for more information.
+#[inline]
+pub fn parse_token<'s, T: FromStr>(
+    tokens: &mut impl Iterator<Item = &'s str>,
+    name: &str,
+) -> Result<T, Error>
+where
+    Error: From<T::Err>,
+{
+    Ok(next_token(tokens, name)?.parse()?)
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn detect() {
+        use super::*;
+
+        let target = Format::Binary;
+        let output = Format::detect("examples/data/colmap/0").unwrap();
+        assert_eq!(output, target);
+
+        let target = Format::Text;
+        let output = Format::detect("examples/data/colmap/4").unwrap();
+        assert_eq!(output, target);
+
+        let target = true;
+        let output = matches!(
+            Format::detect("examples/data/image").unwrap_err(),
+            Error::MissingFile(path) if path.ends_with("image/cameras.bin")
+        );
+        assert_eq!(output, target);
+    }
+
+    #[test]
+    fn parse_token() {
+        use super::*;
+
+        let tokens = &mut "1 1.5 PINHOLE".split_whitespace();
+
+        let target = 1;
+        let output = parse_token::<u32>(tokens, "CAMERA_ID").unwrap();
+        assert_eq!(output, target);
+
+        let target = 1.5;
+        let output = parse_token::<f64>(tokens, "WIDTH").unwrap();
+        assert_eq!(output, target);
+
+        let target = true;
+        let output = matches!(
+            parse_token::<f64>(tokens, "HEIGHT").unwrap_err(),
+            Error::ParseFloatError(_)
+        );
+        assert_eq!(output, target);
+
+        let target = true;
+        let output = matches!(
+            next_token(tokens, "PARAMS").unwrap_err(),
+            Error::MissingSymbol(name) if name == "PARAMS"
+        );
+        assert_eq!(output, target);
+    }
+}
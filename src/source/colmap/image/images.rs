@@ -4,9 +4,58 @@
 
 pub use super::*;
 
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
 /// A map of [`Image::image_id`] to [`Image`].
 pub type Images = crate::collection::IndexMap<u32, Image>;
 
+/// An index of [`Images`] by [`Image::file_name`].
+///
+/// It finds the image that [matches the file path](Image::matches_file_path)
+/// without scanning all images.
+#[derive(Clone, Debug, Default)]
+pub struct ImageFileIndex<'i> {
+    images: HashMap<&'i Path, (usize, &'i Image)>,
+}
+
+impl<'i> ImageFileIndex<'i> {
+    /// Indexing the `images` by their file names.
+    pub fn new(images: &'i Images) -> Self {
+        let mut index = HashMap::with_capacity(images.len());
+        images
+            .values()
+            .enumerate()
+            .for_each(|(image_index, image)| {
+                if let Ok(file_name) = image.file_name.to_str() {
+                    index
+                        .entry(Path::new(file_name))
+                        .or_insert((image_index, image));
+                }
+            });
+        Self { images: index }
+    }
+
+    /// Get the first image that [matches the file path](Image::matches_file_path).
+    ///
+    /// It looks up each trailing part of the `path`.
+    pub fn get(
+        &self,
+        path: impl AsRef<Path>,
+    ) -> Option<&'i Image> {
+        let components = path.as_ref().components().collect::<Vec<_>>();
+        (0..=components.len())
+            .filter_map(|start| {
+                let suffix = components[start..].iter().collect::<PathBuf>();
+                self.images.get(suffix.as_path())
+            })
+            .min_by_key(|(image_index, _)| *image_index)
+            .map(|(_, image)| *image)
+    }
+}
+
 impl Decoder for Images {
     type Err = Error;
 
@@ -47,8 +96,230 @@ impl Encoder for Images {
     }
 }
 
+impl DecoderWith<Format> for Images {
+    type Err = Error;
+
+    #[inline]
+    fn decode_with(
+        reader: &mut impl Read,
+        init: Format,
+    ) -> Result<Self, Self::Err> {
+        Self::decode_with(
+            reader,
+            ImageDecodeOptions {
+                format: init,
+                points: false,
+            },
+        )
+    }
+}
+
+impl DecoderWith<ImageDecodeOptions> for Images {
+    type Err = Error;
+
+    fn decode_with(
+        reader: &mut impl Read,
+        init: ImageDecodeOptions,
+    ) -> Result<Self, Self::Err> {
+        if init.format == Format::Binary {
+            let reader = &mut BufReader::new(reader);
+
+            let image_count = reader.read_u64::<LE>()?;
+            return (0..image_count)
+                .map(|_| {
+                    let image = Image::decode_with(reader, init.points)?;
+                    Ok((image.image_id, image))
+                })
+                .collect();
+        }
+
+        let mut text = String::new();
+        BufReader::new(reader).read_to_string(&mut text)?;
+
+        let mut lines = text.lines();
+        let mut images = Self::default();
+        while let Some(line) = lines.by_ref().find(|line| is_data_line(line)) {
+            let tokens = &mut line.split_whitespace();
+            let image_id = parse_token(tokens, "IMAGE_ID")?;
+            let quaternion = [
+                parse_token(tokens, "QW")?,
+                parse_token(tokens, "QX")?,
+                parse_token(tokens, "QY")?,
+                parse_token(tokens, "QZ")?,
+            ];
+            let translation = [
+                parse_token(tokens, "TX")?,
+                parse_token(tokens, "TY")?,
+                parse_token(tokens, "TZ")?,
+            ];
+            let camera_id = parse_token(tokens, "CAMERA_ID")?;
+            let file_name = next_token(tokens, "NAME")?;
+            let file_name = CString::new(file_name)
+                .map_err(|err| Error::InvalidUtf8(format!("{:?}", err.into_vec())))?;
+
+            let line = lines
+                .next()
+                .ok_or_else(|| Error::MissingSymbol("POINTS2D[]".into()))?;
+            let points = if init.points {
+                let tokens = &mut line.split_whitespace().peekable();
+                let mut points = vec![];
+                while tokens.peek().is_some() {
+                    points.push(ImagePoint {
+                        position: [parse_token(tokens, "X")?, parse_token(tokens, "Y")?],
+                        // NOTE: Any negative ID is untriangulated.
+                        point_id: parse_token::<i64>(tokens, "POINT3D_ID")?
+                            .try_into()
+                            .unwrap_or(ImagePoint::POINT_ID_INVALID),
+                    });
+                }
+                Some(points)
+            } else {
+                // Skip points
+                None
+            };
+
+            images.insert(
+                image_id,
+                Image {
+                    image_id,
+                    quaternion,
+                    translation,
+                    camera_id,
+                    file_name,
+                    points,
+                },
+            );
+        }
+
+        #[cfg(all(debug_assertions, not(test)))]
+        log::debug!(target: "gausplat-loader::colmap::image", "Images::decode_with");
+
+        Ok(images)
+    }
+}
+
+/// Writing the point in the text format.
+///
+/// The invalid point ID is written as `-1`.
+fn write_point(
+    writer: &mut impl Write,
+    point: &ImagePoint,
+) -> std::io::Result<()> {
+    let [x, y] = point.position;
+    if point.is_triangulated() {
+        write!(writer, "{x} {y} {}", point.point_id)
+    } else {
+        write!(writer, "{x} {y} -1")
+    }
+}
+
+impl EncoderWith<Format> for Images {
+    type Err = Error;
+
+    fn encode_with(
+        &self,
+        writer: &mut impl Write,
+        init: Format,
+    ) -> Result<(), Self::Err> {
+        if init == Format::Binary {
+            return self.encode(writer);
+        }
+
+        let writer = &mut BufWriter::new(writer);
+
+        writeln!(writer, "# Image list with two lines of data per image:")?;
+        writeln!(
+            writer,
+            "#   IMAGE_ID, QW, QX, QY, QZ, TX, TY, TZ, CAMERA_ID, NAME"
+        )?;
+        writeln!(writer, "#   POINTS2D[] as (X, Y, POINT3D_ID)")?;
+        let observation_count = self
+            .values()
+            .flat_map(|image| image.points.iter().flatten())
+            .filter(|point| point.is_triangulated())
+            .count();
+        writeln!(
+            writer,
+            "# Number of images: {}, mean observations per image: {}",
+            self.len(),
+            if self.is_empty() {
+                0.0
+            } else {
+                observation_count as f64 / self.len() as f64
+            }
+        )?;
+        self.values().try_for_each(|image| {
+            let [q_w, q_x, q_y, q_z] = image.quaternion;
+            let [t_x, t_y, t_z] = image.translation;
+            writeln!(
+                writer,
+                "{} {q_w} {q_x} {q_y} {q_z} {t_x} {t_y} {t_z} {} {}",
+                image.image_id,
+                image.camera_id,
+                image.file_name.to_string_lossy()
+            )?;
+
+            // Write no points if the points are not decoded
+            let mut points = image.points.as_deref().unwrap_or_default().iter();
+            if let Some(point) = points.next() {
+                write_point(writer, point)?;
+            }
+            points.try_for_each(|point| {
+                write!(writer, " ")?;
+                write_point(writer, point)
+            })?;
+            writeln!(writer)
+        })?;
+
+        #[cfg(all(debug_assertions, not(test)))]
+        log::debug!(target: "gausplat-loader::colmap::image", "Images::encode_with");
+
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    #[test]
+    fn image_file_index_get() {
+        use super::*;
+
+        let images = [
+            (1, c"cam-1/001.png"),
+            (2, c"001.png"),
+            (3, c"cam-1/001.png"),
+            (4, c"002.png"),
+        ]
+        .into_iter()
+        .map(|(image_id, file_name)| {
+            let image = Image {
+                image_id,
+                file_name: file_name.into(),
+                ..Default::default()
+            };
+            (image_id, image)
+        })
+        .collect::<Images>();
+        let index = ImageFileIndex::new(&images);
+
+        [
+            "scene/images/cam-1/001.png",
+            "scene/images/cam-2/001.png",
+            "scene/images/002.png",
+            "scene/images/003.png",
+            "001.png",
+        ]
+        .into_iter()
+        .for_each(|path| {
+            let target = images
+                .values()
+                .find(|image| image.matches_file_path(path))
+                .map(|image| image.image_id);
+            let output = index.get(path).map(|image| image.image_id);
+            assert_eq!(output, target, "{path}");
+        });
+    }
+
     #[test]
     fn decode() {
         use super::*;
@@ -74,6 +345,7 @@ mod tests {
                     ],
                     camera_id: 1,
                     file_name: c"001.png".into(),
+                    points: None,
                 },
             ),
             (
@@ -93,6 +365,7 @@ mod tests {
                     ],
                     camera_id: 1,
                     file_name: c"002.png".into(),
+                    points: None,
                 },
             ),
             (
@@ -112,6 +385,7 @@ mod tests {
                     ],
                     camera_id: 1,
                     file_name: c"003.png".into(),
+                    points: None,
                 },
             ),
             (
@@ -131,6 +405,7 @@ mod tests {
                     ],
                     camera_id: 1,
                     file_name: c"004.png".into(),
+                    points: None,
                 },
             ),
             (
@@ -150,6 +425,7 @@ mod tests {
                     ],
                     camera_id: 1,
                     file_name: c"005.png".into(),
+                    points: None,
                 },
             ),
         ]
@@ -207,6 +483,7 @@ mod tests {
                     ],
                     camera_id: 1,
                     file_name: c"00001.png".into(),
+                    points: None,
                 },
             ),
             (
@@ -226,6 +503,7 @@ mod tests {
                     ],
                     camera_id: 1,
                     file_name: c"00002.png".into(),
+                    points: None,
                 },
             ),
             (
@@ -245,6 +523,7 @@ mod tests {
                     ],
                     camera_id: 1,
                     file_name: c"00003.png".into(),
+                    points: None,
                 },
             ),
             (
@@ -264,6 +543,7 @@ mod tests {
                     ],
                     camera_id: 1,
                     file_name: c"00004.png".into(),
+                    points: None,
                 },
             ),
             (
@@ -283,6 +563,7 @@ mod tests {
                     ],
                     camera_id: 1,
                     file_name: c"00005.png".into(),
+                    points: None,
                 },
             ),
         ]
@@ -308,4 +589,130 @@ mod tests {
         let output = writer.into_inner();
         assert_eq!(output, target);
     }
+
+    #[test]
+    fn decode_with_and_encode_with_on_text() {
+        use super::*;
+        use std::io::Cursor;
+
+        let source_binary =
+            &include_bytes!("../../../../examples/data/colmap/0/images.bin")[..];
+        let source_text =
+            &include_bytes!("../../../../examples/data/colmap/4/images.txt")[..];
+
+        let target = Images::decode(&mut Cursor::new(source_binary)).unwrap();
+        let output =
+            Images::decode_with(&mut Cursor::new(source_binary), Format::Binary).unwrap();
+        assert_eq!(output, target);
+        let output =
+            Images::decode_with(&mut Cursor::new(source_text), Format::Text).unwrap();
+        assert_eq!(output, target);
+
+        let target = source_text;
+        let mut writer = Cursor::new(vec![]);
+        output.encode_with(&mut writer, Format::Text).unwrap();
+        let output = writer.into_inner();
+        assert_eq!(output, target);
+    }
+
+    #[test]
+    fn decode_with_and_encode_on_points() {
+        use super::*;
+        use std::io::Cursor;
+
+        let source = &include_bytes!("../../../../examples/data/colmap/0/images.bin")[..];
+        let options = ImageDecodeOptions {
+            points: true,
+            ..Default::default()
+        };
+
+        let images = Images::decode_with(&mut Cursor::new(source), options).unwrap();
+
+        let target = true;
+        let output = images.values().all(|image| {
+            image
+                .points
+                .as_ref()
+                .is_some_and(|points| points.len() > 1000)
+        });
+        assert_eq!(output, target);
+
+        let target = true;
+        let output = images.values().all(|image| {
+            image.points.as_ref().is_some_and(|points| {
+                points.iter().any(|point| point.is_triangulated())
+                    && points.iter().any(|point| !point.is_triangulated())
+            })
+        });
+        assert_eq!(output, target);
+
+        let target = source;
+        let mut writer = Cursor::new(vec![]);
+        images.encode(&mut writer).unwrap();
+        let output = writer.into_inner();
+        assert_eq!(output, target);
+
+        let target = images;
+        let mut writer = Cursor::new(vec![]);
+        target.encode_with(&mut writer, Format::Text).unwrap();
+        let source = writer.into_inner();
+        let output = Images::decode_with(
+            &mut Cursor::new(source),
+            ImageDecodeOptions {
+                format: Format::Text,
+                points: true,
+            },
+        )
+        .unwrap();
+        assert_eq!(output, target);
+    }
+
+    #[test]
+    fn decode_with_on_text_points() {
+        use super::*;
+        use std::io::Cursor;
+
+        let source = &b"1 1 0 0 0 0 0 0 1 001.png\n1.5 2.5 7 3 4 -1 5 6 -2\n"[..];
+        let options = ImageDecodeOptions {
+            format: Format::Text,
+            points: true,
+        };
+
+        let target = Some(vec![
+            ImagePoint {
+                position: [1.5, 2.5],
+                point_id: 7,
+            },
+            ImagePoint {
+                position: [3.0, 4.0],
+                point_id: ImagePoint::POINT_ID_INVALID,
+            },
+            ImagePoint {
+                position: [5.0, 6.0],
+                point_id: ImagePoint::POINT_ID_INVALID,
+            },
+        ]);
+        let output = Images::decode_with(&mut Cursor::new(source), options)
+            .unwrap()
+            .swap_remove(&1)
+            .unwrap()
+            .points;
+        assert_eq!(output, target);
+
+        let source = &b"1 1 0 0 0 0 0 0 1 001.png\n1.5 2.5\n"[..];
+        let target = true;
+        let output = matches!(
+            Images::decode_with(&mut Cursor::new(source), options).unwrap_err(),
+            Error::MissingSymbol(name) if name == "POINT3D_ID"
+        );
+        assert_eq!(output, target);
+
+        let source = &b"1 1 0 0 0 0 0 0 1 001.png\n"[..];
+        let target = true;
+        let output = matches!(
+            Images::decode_with(&mut Cursor::new(source), options).unwrap_err(),
+            Error::MissingSymbol(name) if name == "POINTS2D[]"
+        );
+        assert_eq!(output, target);
+    }
 }
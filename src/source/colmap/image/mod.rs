@@ -2,17 +2,20 @@
 
 pub mod images;
 
+pub use super::format::Format;
 pub use crate::{
     error::Error,
-    function::{Decoder, Encoder},
+    function::{Decoder, DecoderWith, Encoder, EncoderWith},
 };
 pub use images::*;
 
+use super::format::{is_data_line, next_token, parse_token};
 use crate::function::{advance, is_null, read_bytes_before};
 use byteorder::{ReadBytesExt, WriteBytesExt, LE};
 use std::{
     ffi::CString,
     io::{BufReader, BufWriter, Read, Write},
+    path::Path,
 };
 
 /// A COLMAP image.
@@ -32,9 +35,49 @@ pub struct Image {
     pub camera_id: u32,
     /// Image file name.
     pub file_name: CString,
+    /// 2D points observed in the image.
+    ///
+    /// It is `None` if the points are not decoded.
+    /// See [`ImageDecodeOptions::points`] for more information.
+    pub points: Option<Vec<ImagePoint>>,
+}
+
+/// A 2D point observed in a COLMAP image.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ImagePoint {
+    /// Position in image space.
+    pub position: [f64; 2],
+    /// [Point ID](super::Point) in world space.
+    ///
+    /// It is [`ImagePoint::POINT_ID_INVALID`] if the point is not triangulated.
+    pub point_id: u64,
+}
+
+/// Options for decoding [`Images`].
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct ImageDecodeOptions {
+    /// Model format.
+    pub format: Format,
+    /// Whether to decode [`Image::points`].
+    ///
+    /// The points are skipped if it is `false`.
+    pub points: bool,
 }
 
 impl Image {
+    /// Check if the `path` refers to the image file.
+    ///
+    /// It returns `true` if the `path` ends with [`Self::file_name`].
+    #[inline]
+    pub fn matches_file_path(
+        &self,
+        path: impl AsRef<Path>,
+    ) -> bool {
+        self.file_name
+            .to_str()
+            .is_ok_and(|file_name| path.as_ref().ends_with(file_name))
+    }
+
     /// Return the 3D position in world space.
     ///
     /// It takes the 3D rotation from world space to view space, i.e., [`Self::rotation`].
@@ -79,10 +122,115 @@ impl Image {
     }
 }
 
+/// Modification of the pose.
+impl Image {
+    /// Set [`Self::translation`] from the 3D position in world space.
+    ///
+    /// It takes the 3D rotation from world space to view space, i.e., [`Self::rotation`].
+    ///
+    /// It is the inverse of [`Self::position`].
+    pub fn set_position(
+        &mut self,
+        position: &[f64; 3],
+        rotation: &[[f64; 3]; 3],
+    ) -> &mut Self {
+        let r = rotation;
+        let p = position;
+        self.translation = [
+            -r[0][0] * p[0] - r[1][0] * p[1] - r[2][0] * p[2],
+            -r[0][1] * p[0] - r[1][1] * p[1] - r[2][1] * p[2],
+            -r[0][2] * p[0] - r[1][2] * p[1] - r[2][2] * p[2],
+        ];
+        self
+    }
+
+    /// Set [`Self::quaternion`] from the 3D rotation from world space to view space.
+    ///
+    /// The rotation is in **column-major order**, i.e., `M[col][row]`.
+    ///
+    /// It is the inverse of [`Self::rotation`].
+    /// The scalar part of the quaternion is non-negative.
+    pub fn set_rotation(
+        &mut self,
+        rotation: &[[f64; 3]; 3],
+    ) -> &mut Self {
+        // NOTE: `r(row, col)` is the element in row-major order.
+        let r = |row: usize, col: usize| rotation[col][row];
+        let trace = r(0, 0) + r(1, 1) + r(2, 2);
+
+        let [w, x, y, z] = if trace > 0.0 {
+            let s = (trace + 1.0).sqrt() * 2.0;
+            [
+                s / 4.0,
+                (r(2, 1) - r(1, 2)) / s,
+                (r(0, 2) - r(2, 0)) / s,
+                (r(1, 0) - r(0, 1)) / s,
+            ]
+        } else if r(0, 0) > r(1, 1) && r(0, 0) > r(2, 2) {
+            let s = (1.0 + r(0, 0) - r(1, 1) - r(2, 2)).sqrt() * 2.0;
+            [
+                (r(2, 1) - r(1, 2)) / s,
+                s / 4.0,
+                (r(0, 1) + r(1, 0)) / s,
+                (r(0, 2) + r(2, 0)) / s,
+            ]
+        } else if r(1, 1) > r(2, 2) {
+            let s = (1.0 + r(1, 1) - r(0, 0) - r(2, 2)).sqrt() * 2.0;
+            [
+                (r(0, 2) - r(2, 0)) / s,
+                (r(0, 1) + r(1, 0)) / s,
+                s / 4.0,
+                (r(1, 2) + r(2, 1)) / s,
+            ]
+        } else {
+            let s = (1.0 + r(2, 2) - r(0, 0) - r(1, 1)).sqrt() * 2.0;
+            [
+                (r(1, 0) - r(0, 1)) / s,
+                (r(0, 2) + r(2, 0)) / s,
+                (r(1, 2) + r(2, 1)) / s,
+                s / 4.0,
+            ]
+        };
+
+        let norm = (w * w + x * x + y * y + z * z).sqrt().copysign(w);
+        self.quaternion = [w / norm, x / norm, y / norm, z / norm];
+        self
+    }
+}
+
+impl ImagePoint {
+    /// The point ID of the untriangulated point.
+    ///
+    /// It is `-1` in the COLMAP text format,
+    /// and any negative ID is decoded as it.
+    pub const POINT_ID_INVALID: u64 = u64::MAX;
+
+    /// Check if the point is triangulated.
+    #[inline]
+    pub const fn is_triangulated(&self) -> bool {
+        self.point_id != Self::POINT_ID_INVALID
+    }
+}
+
 impl Decoder for Image {
     type Err = Error;
 
+    #[inline]
     fn decode(reader: &mut impl Read) -> Result<Self, Self::Err> {
+        Self::decode_with(reader, false)
+    }
+}
+
+impl DecoderWith<bool> for Image {
+    type Err = Error;
+
+    /// Decoding from the `reader` in the binary format.
+    ///
+    /// The points are decoded if `init` is `true`.
+    fn decode_with(
+        reader: &mut impl Read,
+        init: bool,
+    ) -> Result<Self, Self::Err> {
         let image_id = reader.read_u32::<LE>()?;
         let quaternion = [
             reader.read_f64::<LE>()?,
@@ -101,9 +249,26 @@ impl Decoder for Image {
         // SAFETY: The result of `read_bytes_before` never include the null terminator.
         let file_name = unsafe { CString::from_vec_unchecked(file_name) };
 
-        // Skip points
         let point_count = reader.read_u64::<LE>()? as usize;
-        advance(reader, 24 * point_count)?;
+        let points = if init {
+            Some(
+                (0..point_count)
+                    .map(|_| {
+                        Ok(ImagePoint {
+                            position: [
+                                reader.read_f64::<LE>()?,
+                                reader.read_f64::<LE>()?,
+                            ],
+                            point_id: reader.read_u64::<LE>()?,
+                        })
+                    })
+                    .collect::<Result<_, Error>>()?,
+            )
+        } else {
+            // Skip points
+            advance(reader, 24 * point_count)?;
+            None
+        };
 
         Ok(Self {
             image_id,
@@ -111,6 +276,7 @@ impl Decoder for Image {
             translation,
             camera_id,
             file_name,
+            points,
         })
     }
 }
@@ -133,8 +299,14 @@ impl Encoder for Image {
         writer.write_u32::<LE>(self.camera_id)?;
         writer.write_all(self.file_name.as_bytes_with_nul())?;
 
-        // Write 0 to point count
-        writer.write_u64::<LE>(0)?;
+        // Write 0 to point count if the points are not decoded
+        let points = self.points.as_deref().unwrap_or_default();
+        writer.write_u64::<LE>(points.len() as u64)?;
+        points.iter().try_for_each(|point| {
+            writer.write_f64::<LE>(point.position[0])?;
+            writer.write_f64::<LE>(point.position[1])?;
+            writer.write_u64::<LE>(point.point_id)
+        })?;
 
         Ok(())
     }
@@ -142,6 +314,28 @@ impl Encoder for Image {
 
 #[cfg(test)]
 mod tests {
+    #[test]
+    fn matches_file_path() {
+        use super::*;
+
+        let image = Image {
+            file_name: c"cam-1/001.png".into(),
+            ..Default::default()
+        };
+
+        let target = true;
+        let output = image.matches_file_path("scene/images/cam-1/001.png");
+        assert_eq!(output, target);
+
+        let target = false;
+        let output = image.matches_file_path("scene/images/cam-2/001.png");
+        assert_eq!(output, target);
+
+        let target = false;
+        let output = image.matches_file_path("scene/images/cam-1/0001.png");
+        assert_eq!(output, target);
+    }
+
     #[test]
     fn position() {
         use super::*;
@@ -201,4 +395,52 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn set_position_and_set_rotation() {
+        use super::*;
+
+        let source = Image {
+            quaternion: [
+                0.9928923624805012,
+                0.006208227229002722,
+                -0.11837120574960786,
+                0.010699163142319695,
+            ],
+            translation: [2.1400970808418642, 0.18616441825409558, 4.726341984431894],
+            ..Default::default()
+        };
+        let rotation = source.rotation();
+        let position = source.position(&rotation);
+
+        let mut output = Image::default();
+        output
+            .set_rotation(&rotation)
+            .set_position(&position, &rotation);
+
+        let target = true;
+        let output = output
+            .quaternion
+            .iter()
+            .zip(source.quaternion)
+            .chain(output.translation.iter().zip(source.translation))
+            .all(|(output, target)| (output - target).abs() < 1e-12);
+        assert_eq!(output, target);
+
+        [
+            ([-0.5, 0.5, 0.5, 0.5], [0.5, -0.5, -0.5, -0.5]),
+            ([0.0, 1.0, 0.0, 0.0], [0.0, 1.0, 0.0, 0.0]),
+            ([0.0, 0.0, 1.0, 0.0], [0.0, 0.0, 1.0, 0.0]),
+            ([0.0, 0.0, 0.0, 1.0], [0.0, 0.0, 0.0, 1.0]),
+        ]
+        .into_iter()
+        .for_each(|(quaternion, target)| {
+            let source = Image {
+                quaternion,
+                ..Default::default()
+            };
+            let output = Image::default().set_rotation(&source.rotation()).quaternion;
+            assert_eq!(output, target);
+        });
+    }
 }
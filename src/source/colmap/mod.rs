@@ -3,15 +3,27 @@
 //! For more information, see the COLMAP [website](https://colmap.github.io/).
 
 pub mod camera;
+pub mod format;
 pub mod image;
 pub mod point;
+pub mod undistort;
+pub mod validate;
 
 pub use super::file::*;
 pub use camera::*;
+pub use format::*;
 pub use image::*;
 pub use point::*;
+pub use undistort::*;
+pub use validate::*;
 
-use std::fmt;
+use crate::source::image::Image as ImageEncoded;
+use std::{
+    collections::HashSet,
+    fmt, fs,
+    io::{self, BufWriter, Read},
+    path::Path,
+};
 
 /// Colmap source.
 #[derive(Clone, PartialEq)]
@@ -26,6 +38,258 @@ pub struct ColmapSource<S> {
     pub points: Points,
 }
 
+impl Opener for ColmapSource<fs::File> {
+    /// Opening the COLMAP scene in the root directory.
+    ///
+    /// The sparse model is located in `sparse/0` or `sparse`,
+    /// and its [`Format`] is detected automatically.
+    /// The image files are located in `images`.
+    ///
+    /// The files are opened in read-only mode.
+    ///
+    /// # Errors
+    ///
+    /// It returns [`Error::MissingFile`] with the path of any missing file or directory.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use gausplat_loader::source::colmap::{ColmapSource, Opener};
+    ///
+    /// let source = ColmapSource::open("examples/data/colmap/5").unwrap();
+    /// assert_eq!(source.images.len(), source.images_file.len());
+    /// ```
+    fn open(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+
+        let sparse_path = [path.join("sparse").join("0"), path.join("sparse")]
+            .into_iter()
+            .find(|path| path.is_dir())
+            .ok_or_else(|| Error::MissingFile(path.join("sparse")))?;
+        let format = Format::detect(&sparse_path)?;
+        let open_model = |stem| {
+            let path = sparse_path.join(format.file_name(stem));
+            if !path.is_file() {
+                return Err(Error::MissingFile(path));
+            }
+            Ok(std::io::BufReader::new(fs::File::open(path)?))
+        };
+
+        let cameras = Cameras::decode_with(&mut open_model("cameras")?, format)?;
+        let images = Images::decode_with(
+            &mut open_model("images")?,
+            ImageDecodeOptions {
+                format,
+                points: true,
+            },
+        )?;
+        let points = Points::decode_with(&mut open_model("points3D")?, format)?;
+
+        let images_path = path.join("images");
+        if !images_path.is_dir() {
+            return Err(Error::MissingFile(images_path));
+        }
+        let images_file = images
+            .values()
+            .map(|image| {
+                let file_name = image.file_name.to_str().map_err(|_| {
+                    Error::InvalidUtf8(image.file_name.to_string_lossy().into())
+                })?;
+                let path = images_path.join(file_name);
+                if !path.is_file() {
+                    return Err(Error::MissingFile(path));
+                }
+                let inner = fs::File::open(&path)?;
+                Ok((path.to_owned(), File { inner, path }))
+            })
+            .collect::<Result<_, Error>>()?;
+
+        #[cfg(all(debug_assertions, not(test)))]
+        log::debug!(target: "gausplat-loader::colmap", "ColmapSource::open");
+
+        Ok(Self {
+            cameras,
+            images,
+            images_file,
+            points,
+        })
+    }
+}
+
+impl<S: Read> ColmapSource<S> {
+    /// Saving the COLMAP scene to the root directory.
+    ///
+    /// The sparse model is written to `sparse/0` in the `format`,
+    /// and the image files are written to `images`.
+    /// See [`ImageEncoded::save`] for how the image files are copied or re-encoded.
+    ///
+    /// The scene is written and synced to a temporary directory next to the root directory
+    /// first, and then renamed to the root directory, so that it never leaves a partial scene.
+    /// The previous scene in the root directory is replaced.
+    ///
+    /// All image files are read to the end.
+    ///
+    /// # Errors
+    ///
+    /// It returns [`Error::Io`] if the root directory has other files than
+    /// `images` and `sparse/0`, e.g., `database.db`, since they would be removed.
+    pub fn save(
+        &mut self,
+        path: impl AsRef<Path>,
+        format: Format,
+    ) -> Result<&mut Self, Error> {
+        let path = path.as_ref();
+        let name = path.file_name().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("No file name: {path:?}"),
+            )
+        })?;
+        let path_with_suffix = |suffix| {
+            let mut name_with_suffix = std::ffi::OsString::from(".");
+            name_with_suffix.push(name);
+            name_with_suffix.push(suffix);
+            path.with_file_name(name_with_suffix)
+        };
+        let path_temp = path_with_suffix(".tmp");
+        let path_old = path_with_suffix(".old");
+        let path_parent = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent,
+            _ => Path::new("."),
+        };
+
+        if path.exists() && !is_saved_scene(path)? {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("Not a saved scene: {path:?}"),
+            )
+            .into());
+        }
+
+        if path_temp.exists() {
+            fs::remove_dir_all(&path_temp)?;
+        }
+        if let Err(err) = self.save_to(&path_temp, format) {
+            // NOTE: The error from removing the temporary directory is less important.
+            fs::remove_dir_all(&path_temp).ok();
+            return Err(err);
+        }
+        sync_dir(path_parent)?;
+
+        if path.exists() {
+            if path_old.exists() {
+                fs::remove_dir_all(&path_old)?;
+            }
+            fs::rename(path, &path_old)?;
+            fs::rename(&path_temp, path)?;
+            fs::remove_dir_all(&path_old)?;
+        } else {
+            fs::rename(&path_temp, path)?;
+        }
+        sync_dir(path_parent)?;
+
+        #[cfg(all(debug_assertions, not(test)))]
+        log::debug!(target: "gausplat-loader::colmap", "ColmapSource::save");
+
+        Ok(self)
+    }
+
+    /// Writing all parts of the scene to the root directory.
+    fn save_to(
+        &mut self,
+        path: &Path,
+        format: Format,
+    ) -> Result<(), Error> {
+        let sparse_path = path.join("sparse").join("0");
+        fs::create_dir_all(&sparse_path)?;
+        let create_model = |stem| -> Result<_, Error> {
+            Ok(BufWriter::new(fs::File::create(
+                sparse_path.join(format.file_name(stem)),
+            )?))
+        };
+        let finish_model = |writer: BufWriter<fs::File>| -> Result<(), Error> {
+            writer
+                .into_inner()
+                .map_err(|err| err.into_error())?
+                .sync_all()?;
+            Ok(())
+        };
+
+        let mut writer = create_model("cameras")?;
+        self.cameras.encode_with(&mut writer, format)?;
+        finish_model(writer)?;
+        let mut writer = create_model("images")?;
+        self.images.encode_with(&mut writer, format)?;
+        finish_model(writer)?;
+        let mut writer = create_model("points3D")?;
+        self.points.encode_with(&mut writer, format)?;
+        finish_model(writer)?;
+
+        let images_path = path.join("images");
+        fs::create_dir_all(&images_path)?;
+        let images_index = ImageFileIndex::new(&self.images);
+        let mut dir_paths = HashSet::from([
+            path.to_owned(),
+            path.join("sparse"),
+            sparse_path.to_owned(),
+            images_path.to_owned(),
+        ]);
+        self.images_file.iter_mut().try_for_each(|(path, file)| {
+            let image = images_index.get(path);
+            let image_file_path = match image {
+                Some(image) => {
+                    images_path.join(image.file_name.to_string_lossy().as_ref())
+                },
+                None => images_path.join(path.file_name().unwrap_or_default()),
+            };
+            if let Some(parent) = image_file_path.parent() {
+                fs::create_dir_all(parent)?;
+                dir_paths.insert(parent.to_owned());
+            }
+
+            ImageEncoded {
+                image_encoded: file.read_all()?,
+                image_file_path: image_file_path.to_owned(),
+                image_id: image.map(|image| image.image_id).unwrap_or_default(),
+            }
+            .save()?;
+            fs::OpenOptions::new()
+                .write(true)
+                .open(&image_file_path)?
+                .sync_all()?;
+            Ok::<_, Error>(())
+        })?;
+
+        dir_paths.iter().try_for_each(|path| sync_dir(path))
+    }
+}
+
+/// Check if the directory only has the parts written by [`ColmapSource::save`].
+fn is_saved_scene(path: &Path) -> Result<bool, Error> {
+    let has_only_dirs = |path: &Path, names: &[&str]| -> Result<bool, Error> {
+        fs::read_dir(path)?.try_fold(true, |has_only_dirs, entry| {
+            let entry = entry?;
+            Ok(has_only_dirs
+                && entry.file_type()?.is_dir()
+                && names.iter().any(|name| entry.file_name() == *name))
+        })
+    };
+    let sparse_path = path.join("sparse");
+    Ok(has_only_dirs(path, &["images", "sparse"])?
+        && (!sparse_path.exists() || has_only_dirs(&sparse_path, &["0"])?))
+}
+
+/// Syncing the entries of the directory.
+///
+/// It does nothing on the platforms where a directory cannot be opened as a file.
+fn sync_dir(path: &Path) -> Result<(), Error> {
+    #[cfg(unix)]
+    fs::File::open(path)?.sync_all()?;
+    #[cfg(not(unix))]
+    let _ = path;
+    Ok(())
+}
+
 impl<S> fmt::Debug for ColmapSource<S> {
     #[inline]
     fn fmt(
@@ -72,4 +336,157 @@ mod tests {
         let output = format!("{:?}", output).starts_with("ColmapSource");
         assert_eq!(output, target);
     }
+
+    #[test]
+    fn open() {
+        use super::*;
+        use std::io::Cursor;
+
+        let source = "examples/data/colmap/5";
+        let mut colmap_source = ColmapSource::open(source).unwrap();
+
+        let target = Cameras::decode(&mut Cursor::new(include_bytes!(
+            "../../../examples/data/colmap/0/cameras.bin"
+        )))
+        .unwrap();
+        let output = colmap_source.cameras.to_owned();
+        assert_eq!(output, target);
+
+        let target = 10;
+        let output = colmap_source.points.len();
+        assert_eq!(output, target);
+
+        let target = true;
+        let output = colmap_source
+            .images
+            .values()
+            .all(|image| image.points.is_some());
+        assert_eq!(output, target);
+
+        let target = ["001.png", "002.png", "003.png", "004.png", "005.png"]
+            .map(|file_name| Path::new(source).join("images").join(file_name));
+        let output = colmap_source
+            .images_file
+            .keys()
+            .cloned()
+            .collect::<Vec<_>>();
+        assert_eq!(output, target);
+
+        let target = include_bytes!("../../../examples/data/image/rainbow-8x8.png");
+        let output = colmap_source.images_file[0].read_all().unwrap();
+        assert_eq!(output, target);
+    }
+
+    #[test]
+    fn open_on_text() {
+        use super::*;
+
+        let target = ColmapSource::open("examples/data/colmap/5").unwrap();
+        let output = ColmapSource::open("examples/data/colmap/6").unwrap();
+        assert_eq!(output.cameras, target.cameras);
+        assert_eq!(output.points, target.points);
+        assert_eq!(output.images_file.len(), target.images_file.len());
+    }
+
+    #[test]
+    fn open_on_missing_files() {
+        use super::*;
+
+        let target = true;
+        let output = matches!(
+            ColmapSource::open("examples/data/colmap/0").unwrap_err(),
+            Error::MissingFile(path) if path.ends_with("colmap/0/sparse")
+        );
+        assert_eq!(output, target);
+
+        let target = true;
+        let output = matches!(
+            ColmapSource::open("examples/data/colmap/7").unwrap_err(),
+            Error::MissingFile(path) if path.ends_with("colmap/7/images")
+        );
+        assert_eq!(output, target);
+    }
+
+    #[test]
+    fn save() {
+        use super::*;
+        use std::env::temp_dir;
+
+        let path = temp_dir().join("gausplat-loader::tests::save.colmap");
+        let mut source = ColmapSource::open("examples/data/colmap/5").unwrap();
+
+        source.save(&path, Format::Text).unwrap();
+        let mut output = ColmapSource::open(&path).unwrap();
+        let target = ColmapSource::open("examples/data/colmap/5").unwrap();
+        assert_eq!(output.cameras, target.cameras);
+        assert_eq!(output.images, target.images);
+        assert_eq!(output.points, target.points);
+
+        let target = Format::Text;
+        let output_format = Format::detect(path.join("sparse/0")).unwrap();
+        assert_eq!(output_format, target);
+
+        let target = include_bytes!("../../../examples/data/image/rainbow-8x8.png");
+        let output_image = output.images_file[4].read_all().unwrap();
+        assert_eq!(output_image, target);
+
+        let mut output = ColmapSource::open(&path).unwrap();
+        output.save(&path, Format::Binary).unwrap();
+
+        let target = Format::Binary;
+        let output_format = Format::detect(path.join("sparse/0")).unwrap();
+        assert_eq!(output_format, target);
+
+        let target = false;
+        let output_exists = path.join("sparse/0/cameras.txt").exists()
+            || path
+                .with_file_name(".gausplat-loader::tests::save.colmap.tmp")
+                .exists()
+            || path
+                .with_file_name(".gausplat-loader::tests::save.colmap.old")
+                .exists();
+        assert_eq!(output_exists, target);
+
+        fs::remove_dir_all(&path).unwrap();
+    }
+
+    #[test]
+    fn save_on_invalid_path() {
+        use super::*;
+
+        let mut source = ColmapSource::<&[u8]>::default();
+        source.save("..", Format::Binary).unwrap_err();
+    }
+
+    #[test]
+    fn save_on_workspace() {
+        use super::*;
+        use std::env::temp_dir;
+
+        let path = temp_dir().join("gausplat-loader::tests::save_on_workspace.colmap");
+        fs::create_dir_all(path.join("sparse/1")).unwrap();
+        fs::write(path.join("database.db"), b"").unwrap();
+        let mut source = ColmapSource::open("examples/data/colmap/5").unwrap();
+
+        let target = true;
+        let output = matches!(
+            source.save(&path, Format::Binary).unwrap_err(),
+            Error::Io(err) if err.kind() == io::ErrorKind::AlreadyExists,
+        );
+        assert_eq!(output, target);
+
+        let target = true;
+        let output = path.join("database.db").exists() && path.join("sparse/1").exists();
+        assert_eq!(output, target);
+
+        fs::remove_dir_all(path.join("sparse/1")).unwrap();
+        fs::remove_file(path.join("database.db")).unwrap();
+        source.save(&path, Format::Binary).unwrap();
+
+        let target = Format::Binary;
+        let output = Format::detect(path.join("sparse/0")).unwrap();
+        assert_eq!(output, target);
+
+        fs::remove_dir_all(&path).unwrap();
+    }
 }
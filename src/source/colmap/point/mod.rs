@@ -2,23 +2,42 @@
 
 pub mod points;
 
+pub use super::format::Format;
 pub use crate::{
     error::Error,
-    function::{Decoder, Encoder},
+    function::{Decoder, DecoderWith, Encoder, EncoderWith},
 };
 pub use points::*;
 
-use crate::function::{advance, read_bytes_const};
+use super::format::{is_data_line, parse_token};
+use crate::function::read_bytes_const;
 use byteorder::{ReadBytesExt, WriteBytesExt, LE};
 use std::io::{BufReader, BufWriter, Read, Write};
 
 /// A COLMAP point.
-#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[derive(Clone, Debug, Default, PartialEq)]
 pub struct Point {
+    /// Point ID.
+    pub point_id: u64,
     /// Position in world space.
     pub position: [f64; 3],
     /// Color in 8-bit RGB.
     pub color_rgb: [u8; 3],
+    /// Re-projection error.
+    ///
+    /// It is negative if the error is unknown.
+    pub error: f64,
+    /// The observations of the point in images.
+    pub track: Vec<TrackElement>,
+}
+
+/// An observation of a [`Point`] in an image.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct TrackElement {
+    /// [Image ID](super::Image::image_id).
+    pub image_id: u32,
+    /// The index of the observed point in [`Image::points`](super::Image::points).
+    pub point_index: u32,
 }
 
 impl Point {
@@ -38,23 +57,31 @@ impl Decoder for Point {
 
     #[inline]
     fn decode(reader: &mut impl Read) -> Result<Self, Self::Err> {
+        let point_id = reader.read_u64::<LE>()?;
         let position = [
             reader.read_f64::<LE>()?,
             reader.read_f64::<LE>()?,
             reader.read_f64::<LE>()?,
         ];
         let color_rgb = read_bytes_const(reader)?;
+        let error = reader.read_f64::<LE>()?;
 
-        // Skip re-projection error
-        advance(reader, 8)?;
-
-        // Skip tracks
         let track_count = reader.read_u64::<LE>()? as usize;
-        advance(reader, 8 * track_count)?;
+        let track = (0..track_count)
+            .map(|_| {
+                Ok(TrackElement {
+                    image_id: reader.read_u32::<LE>()?,
+                    point_index: reader.read_u32::<LE>()?,
+                })
+            })
+            .collect::<Result<_, Error>>()?;
 
         Ok(Self {
+            point_id,
             position,
             color_rgb,
+            error,
+            track,
         })
     }
 }
@@ -67,16 +94,17 @@ impl Encoder for Point {
         &self,
         writer: &mut impl Write,
     ) -> Result<(), Self::Err> {
+        writer.write_u64::<LE>(self.point_id)?;
         writer.write_f64::<LE>(self.position[0])?;
         writer.write_f64::<LE>(self.position[1])?;
         writer.write_f64::<LE>(self.position[2])?;
         writer.write_all(&self.color_rgb)?;
-
-        // Write -1.0 to re-projection error
-        writer.write_f64::<LE>(-1.0)?;
-
-        // Write 0 to track count
-        writer.write_u64::<LE>(0)?;
+        writer.write_f64::<LE>(self.error)?;
+        writer.write_u64::<LE>(self.track.len() as u64)?;
+        self.track.iter().try_for_each(|element| {
+            writer.write_u32::<LE>(element.image_id)?;
+            writer.write_u32::<LE>(element.point_index)
+        })?;
 
         Ok(())
     }
@@ -89,8 +117,8 @@ mod tests {
         use super::*;
 
         let source = Point {
-            position: Default::default(),
             color_rgb: [255, 128, 0],
+            ..Default::default()
         };
 
         let target = [1.0, 0.5019608, 0.0];
@@ -1,9 +1,11 @@
 //! A collection of points.
+//!
+//! It is generally stored in the `points3D.bin` file.
 
 pub use super::*;
 
-/// A list of points.
-pub type Points = Vec<Point>;
+/// A map of [`Point::point_id`] to [`Point`].
+pub type Points = crate::collection::IndexMap<u64, Point>;
 
 impl Decoder for Points {
     type Err = Error;
@@ -14,9 +16,8 @@ impl Decoder for Points {
         let point_count = reader.read_u64::<LE>()?;
         let points = (0..point_count)
             .map(|_| {
-                // Skip point id
-                advance(reader, 8)?;
-                Point::decode(reader)
+                let point = Point::decode(reader)?;
+                Ok((point.point_id, point))
             })
             .collect();
 
@@ -37,13 +38,7 @@ impl Encoder for Points {
         let writer = &mut BufWriter::new(writer);
 
         writer.write_u64::<LE>(self.len() as u64)?;
-        self.iter()
-            .enumerate()
-            .try_for_each(|(point_index, point)| {
-                // Write point index to point id
-                writer.write_u64::<LE>(point_index as u64)?;
-                point.encode(writer)
-            })?;
+        self.values().try_for_each(|point| point.encode(writer))?;
 
         #[cfg(all(debug_assertions, not(test)))]
         log::debug!(target: "gausplat-loader::colmap::point", "Points::encode");
@@ -52,6 +47,117 @@ impl Encoder for Points {
     }
 }
 
+impl DecoderWith<Format> for Points {
+    type Err = Error;
+
+    fn decode_with(
+        reader: &mut impl Read,
+        init: Format,
+    ) -> Result<Self, Self::Err> {
+        if init == Format::Binary {
+            return Self::decode(reader);
+        }
+
+        let mut text = String::new();
+        BufReader::new(reader).read_to_string(&mut text)?;
+
+        let points = text
+            .lines()
+            .filter(|line| is_data_line(line))
+            .map(|line| {
+                let tokens = &mut line.split_whitespace().peekable();
+                let point_id = parse_token(tokens, "POINT3D_ID")?;
+                let position = [
+                    parse_token(tokens, "X")?,
+                    parse_token(tokens, "Y")?,
+                    parse_token(tokens, "Z")?,
+                ];
+                let color_rgb = [
+                    parse_token(tokens, "R")?,
+                    parse_token(tokens, "G")?,
+                    parse_token(tokens, "B")?,
+                ];
+                let error = parse_token(tokens, "ERROR")?;
+
+                let mut track = vec![];
+                while tokens.peek().is_some() {
+                    track.push(TrackElement {
+                        image_id: parse_token(tokens, "IMAGE_ID")?,
+                        point_index: parse_token(tokens, "POINT2D_IDX")?,
+                    });
+                }
+
+                Ok((
+                    point_id,
+                    Point {
+                        point_id,
+                        position,
+                        color_rgb,
+                        error,
+                        track,
+                    },
+                ))
+            })
+            .collect();
+
+        #[cfg(all(debug_assertions, not(test)))]
+        log::debug!(target: "gausplat-loader::colmap::point", "Points::decode_with");
+
+        points
+    }
+}
+
+impl EncoderWith<Format> for Points {
+    type Err = Error;
+
+    fn encode_with(
+        &self,
+        writer: &mut impl Write,
+        init: Format,
+    ) -> Result<(), Self::Err> {
+        if init == Format::Binary {
+            return self.encode(writer);
+        }
+
+        let writer = &mut BufWriter::new(writer);
+
+        let track_length = self.values().map(|point| point.track.len()).sum::<usize>();
+        writeln!(writer, "# 3D point list with one line of data per point:")?;
+        writeln!(
+            writer,
+            "#   POINT3D_ID, X, Y, Z, R, G, B, ERROR, TRACK[] as (IMAGE_ID, POINT2D_IDX)"
+        )?;
+        writeln!(
+            writer,
+            "# Number of points: {}, mean track length: {}",
+            self.len(),
+            if self.is_empty() {
+                0.0
+            } else {
+                track_length as f64 / self.len() as f64
+            }
+        )?;
+        self.values().try_for_each(|point| {
+            let [x, y, z] = point.position;
+            let [r, g, b] = point.color_rgb;
+            write!(
+                writer,
+                "{} {x} {y} {z} {r} {g} {b} {}",
+                point.point_id, point.error
+            )?;
+            point.track.iter().try_for_each(|element| {
+                write!(writer, " {} {}", element.image_id, element.point_index)
+            })?;
+            writeln!(writer)
+        })?;
+
+        #[cfg(all(debug_assertions, not(test)))]
+        log::debug!(target: "gausplat-loader::colmap::point", "Points::encode_with");
+
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -62,48 +168,340 @@ mod tests {
         let source =
             &include_bytes!("../../../../examples/data/colmap/0/points3D.bin")[..];
 
-        let targets = vec![
-            Point {
-                position: [1.5724178716968433, 1.3564240230221387, 1.7538898806469643],
-                color_rgb: [120, 119, 93],
-            },
-            Point {
-                position: [2.0051609433534487, -7.565764755951541, 13.772794612423496],
-                color_rgb: [56, 63, 27],
-            },
-            Point {
-                position: [0.903303165177694, 1.3450047957050342, 0.7132061653263634],
-                color_rgb: [120, 128, 81],
-            },
-            Point {
-                position: [-0.2538381433102123, 2.0167046225357734, 0.20054194403740716],
-                color_rgb: [89, 89, 82],
-            },
-            Point {
-                position: [1.9721978366381463, 2.0600807450742864, 0.9798867439849316],
-                color_rgb: [64, 62, 56],
-            },
-            Point {
-                position: [-0.7142955735092194, 0.3243117479195404, 1.4634002798548704],
-                color_rgb: [55, 59, 26],
-            },
-            Point {
-                position: [-5.274968015907949, 0.8415210125122136, -0.6782984199914783],
-                color_rgb: [59, 57, 40],
-            },
-            Point {
-                position: [5.82378588291083, 0.5095604394582246, 4.241006457627927],
-                color_rgb: [71, 73, 43],
-            },
-            Point {
-                position: [6.394665813759088, 1.1211348999682709, 3.4997745196316528],
-                color_rgb: [143, 144, 117],
-            },
-            Point {
-                position: [1.5805116148624903, 5.40206892716795, -19.4210684817658],
-                color_rgb: [58, 67, 50],
-            },
-        ];
+        let targets = [
+            (
+                69379,
+                Point {
+                    point_id: 69379,
+                    position: [
+                        1.5724178716968433,
+                        1.3564240230221387,
+                        1.7538898806469643,
+                    ],
+                    color_rgb: [120, 119, 93],
+                    error: 1.6180304626501716,
+                    track: vec![
+                        TrackElement {
+                            image_id: 123,
+                            point_index: 4895,
+                        },
+                        TrackElement {
+                            image_id: 122,
+                            point_index: 4855,
+                        },
+                        TrackElement {
+                            image_id: 120,
+                            point_index: 4924,
+                        },
+                        TrackElement {
+                            image_id: 105,
+                            point_index: 4785,
+                        },
+                        TrackElement {
+                            image_id: 115,
+                            point_index: 5221,
+                        },
+                        TrackElement {
+                            image_id: 116,
+                            point_index: 5132,
+                        },
+                        TrackElement {
+                            image_id: 117,
+                            point_index: 5075,
+                        },
+                        TrackElement {
+                            image_id: 118,
+                            point_index: 4977,
+                        },
+                    ],
+                },
+            ),
+            (
+                17942,
+                Point {
+                    point_id: 17942,
+                    position: [
+                        2.0051609433534487,
+                        -7.565764755951541,
+                        13.772794612423496,
+                    ],
+                    color_rgb: [56, 63, 27],
+                    error: 0.15457337764421342,
+                    track: vec![
+                        TrackElement {
+                            image_id: 24,
+                            point_index: 275,
+                        },
+                        TrackElement {
+                            image_id: 25,
+                            point_index: 198,
+                        },
+                        TrackElement {
+                            image_id: 160,
+                            point_index: 629,
+                        },
+                        TrackElement {
+                            image_id: 161,
+                            point_index: 627,
+                        },
+                        TrackElement {
+                            image_id: 26,
+                            point_index: 218,
+                        },
+                        TrackElement {
+                            image_id: 163,
+                            point_index: 537,
+                        },
+                        TrackElement {
+                            image_id: 27,
+                            point_index: 221,
+                        },
+                        TrackElement {
+                            image_id: 28,
+                            point_index: 2306,
+                        },
+                    ],
+                },
+            ),
+            (
+                62958,
+                Point {
+                    point_id: 62958,
+                    position: [0.903303165177694, 1.3450047957050342, 0.7132061653263634],
+                    color_rgb: [120, 128, 81],
+                    error: 0.2536246091926926,
+                    track: vec![
+                        TrackElement {
+                            image_id: 127,
+                            point_index: 3528,
+                        },
+                        TrackElement {
+                            image_id: 126,
+                            point_index: 3494,
+                        },
+                        TrackElement {
+                            image_id: 125,
+                            point_index: 3520,
+                        },
+                        TrackElement {
+                            image_id: 124,
+                            point_index: 2766,
+                        },
+                        TrackElement {
+                            image_id: 123,
+                            point_index: 2784,
+                        },
+                        TrackElement {
+                            image_id: 122,
+                            point_index: 1302,
+                        },
+                        TrackElement {
+                            image_id: 121,
+                            point_index: 1358,
+                        },
+                    ],
+                },
+            ),
+            (
+                11521,
+                Point {
+                    point_id: 11521,
+                    position: [
+                        -0.2538381433102123,
+                        2.0167046225357734,
+                        0.20054194403740716,
+                    ],
+                    color_rgb: [89, 89, 82],
+                    error: 0.25106389539011925,
+                    track: vec![
+                        TrackElement {
+                            image_id: 15,
+                            point_index: 1918,
+                        },
+                        TrackElement {
+                            image_id: 154,
+                            point_index: 2212,
+                        },
+                        TrackElement {
+                            image_id: 156,
+                            point_index: 2363,
+                        },
+                    ],
+                },
+            ),
+            (
+                66176,
+                Point {
+                    point_id: 66176,
+                    position: [
+                        1.9721978366381463,
+                        2.0600807450742864,
+                        0.9798867439849316,
+                    ],
+                    color_rgb: [64, 62, 56],
+                    error: 0.3618406501766754,
+                    track: vec![
+                        TrackElement {
+                            image_id: 110,
+                            point_index: 3748,
+                        },
+                        TrackElement {
+                            image_id: 111,
+                            point_index: 3782,
+                        },
+                        TrackElement {
+                            image_id: 113,
+                            point_index: 4217,
+                        },
+                        TrackElement {
+                            image_id: 112,
+                            point_index: 4264,
+                        },
+                        TrackElement {
+                            image_id: 114,
+                            point_index: 4326,
+                        },
+                        TrackElement {
+                            image_id: 115,
+                            point_index: 4446,
+                        },
+                    ],
+                },
+            ),
+            (
+                40459,
+                Point {
+                    point_id: 40459,
+                    position: [
+                        -0.7142955735092194,
+                        0.3243117479195404,
+                        1.4634002798548704,
+                    ],
+                    color_rgb: [55, 59, 26],
+                    error: 0.12359121061725875,
+                    track: vec![
+                        TrackElement {
+                            image_id: 48,
+                            point_index: 2508,
+                        },
+                        TrackElement {
+                            image_id: 49,
+                            point_index: 2502,
+                        },
+                        TrackElement {
+                            image_id: 50,
+                            point_index: 2568,
+                        },
+                    ],
+                },
+            ),
+            (
+                67782,
+                Point {
+                    point_id: 67782,
+                    position: [
+                        -5.274968015907949,
+                        0.8415210125122136,
+                        -0.6782984199914783,
+                    ],
+                    color_rgb: [59, 57, 40],
+                    error: 0.11394452670921876,
+                    track: vec![
+                        TrackElement {
+                            image_id: 113,
+                            point_index: 4366,
+                        },
+                        TrackElement {
+                            image_id: 116,
+                            point_index: 4458,
+                        },
+                        TrackElement {
+                            image_id: 119,
+                            point_index: 4471,
+                        },
+                        TrackElement {
+                            image_id: 120,
+                            point_index: 4493,
+                        },
+                    ],
+                },
+            ),
+            (
+                16345,
+                Point {
+                    point_id: 16345,
+                    position: [5.82378588291083, 0.5095604394582246, 4.241006457627927],
+                    color_rgb: [71, 73, 43],
+                    error: 0.2973679695942468,
+                    track: vec![
+                        TrackElement {
+                            image_id: 23,
+                            point_index: 1221,
+                        },
+                        TrackElement {
+                            image_id: 160,
+                            point_index: 1558,
+                        },
+                        TrackElement {
+                            image_id: 161,
+                            point_index: 1505,
+                        },
+                        TrackElement {
+                            image_id: 24,
+                            point_index: 1138,
+                        },
+                    ],
+                },
+            ),
+            (
+                42065,
+                Point {
+                    point_id: 42065,
+                    position: [6.394665813759088, 1.1211348999682709, 3.4997745196316528],
+                    color_rgb: [143, 144, 117],
+                    error: 0.24580060951650382,
+                    track: vec![
+                        TrackElement {
+                            image_id: 51,
+                            point_index: 790,
+                        },
+                        TrackElement {
+                            image_id: 52,
+                            point_index: 2468,
+                        },
+                        TrackElement {
+                            image_id: 53,
+                            point_index: 755,
+                        },
+                    ],
+                },
+            ),
+            (
+                54929,
+                Point {
+                    point_id: 54929,
+                    position: [1.5805116148624903, 5.40206892716795, -19.4210684817658],
+                    color_rgb: [58, 67, 50],
+                    error: 0.8572184480873727,
+                    track: vec![
+                        TrackElement {
+                            image_id: 82,
+                            point_index: 2175,
+                        },
+                        TrackElement {
+                            image_id: 86,
+                            point_index: 3340,
+                        },
+                        TrackElement {
+                            image_id: 88,
+                            point_index: 3379,
+                        },
+                    ],
+                },
+            ),
+        ]
+        .into_iter()
+        .collect::<Points>();
 
         (0..128).for_each(|i| {
             let mut reader = Cursor::new(&source[..i]);
@@ -139,50 +537,122 @@ mod tests {
         use super::*;
 
         let source = [
-            Point {
-                position: [-9.653762040829593, -4.102401892127109, 9.599685045896118],
-                color_rgb: [47, 51, 30],
-            },
-            Point {
-                position: [5.487944921401847, 0.2107494446297745, 3.114260873278527],
-                color_rgb: [165, 169, 126],
-            },
-            Point {
-                position: [0.1410007471542446, 0.291254708094473, 2.2554270470753965],
-                color_rgb: [121, 125, 94],
-            },
-            Point {
-                position: [-0.970841016641282, -0.48531157645971296, 2.3516242254018627],
-                color_rgb: [96, 96, 91],
-            },
-            Point {
-                position: [-0.8143227596488996, 3.1710185435453306, 0.3694397529877653],
-                color_rgb: [141, 139, 136],
-            },
-            Point {
-                position: [1.157534330380484, 1.508798212187828, 0.9037922130535186],
-                color_rgb: [131, 136, 98],
-            },
-            Point {
-                position: [5.834357348282835, 1.4493333604378096, 3.1080390945391643],
-                color_rgb: [151, 151, 147],
-            },
-            Point {
-                position: [-0.24065866398375135, 0.1763233421385975, 1.6066914460314323],
-                color_rgb: [141, 147, 89],
-            },
-            Point {
-                position: [0.7556535574483431, 0.6682392592540607, 3.120770469139577],
-                color_rgb: [153, 149, 137],
-            },
-            Point {
-                position: [
-                    -1.9299760562484711,
-                    -0.37688731833688194,
-                    0.8368212073339936,
-                ],
-                color_rgb: [84, 88, 78],
-            },
+            (
+                0,
+                Point {
+                    point_id: 0,
+                    position: [-9.653762040829593, -4.102401892127109, 9.599685045896118],
+                    color_rgb: [47, 51, 30],
+                    error: -1.0,
+                    track: vec![],
+                },
+            ),
+            (
+                1,
+                Point {
+                    point_id: 1,
+                    position: [5.487944921401847, 0.2107494446297745, 3.114260873278527],
+                    color_rgb: [165, 169, 126],
+                    error: -1.0,
+                    track: vec![],
+                },
+            ),
+            (
+                2,
+                Point {
+                    point_id: 2,
+                    position: [0.1410007471542446, 0.291254708094473, 2.2554270470753965],
+                    color_rgb: [121, 125, 94],
+                    error: -1.0,
+                    track: vec![],
+                },
+            ),
+            (
+                3,
+                Point {
+                    point_id: 3,
+                    position: [
+                        -0.970841016641282,
+                        -0.48531157645971296,
+                        2.3516242254018627,
+                    ],
+                    color_rgb: [96, 96, 91],
+                    error: -1.0,
+                    track: vec![],
+                },
+            ),
+            (
+                4,
+                Point {
+                    point_id: 4,
+                    position: [
+                        -0.8143227596488996,
+                        3.1710185435453306,
+                        0.3694397529877653,
+                    ],
+                    color_rgb: [141, 139, 136],
+                    error: -1.0,
+                    track: vec![],
+                },
+            ),
+            (
+                5,
+                Point {
+                    point_id: 5,
+                    position: [1.157534330380484, 1.508798212187828, 0.9037922130535186],
+                    color_rgb: [131, 136, 98],
+                    error: -1.0,
+                    track: vec![],
+                },
+            ),
+            (
+                6,
+                Point {
+                    point_id: 6,
+                    position: [5.834357348282835, 1.4493333604378096, 3.1080390945391643],
+                    color_rgb: [151, 151, 147],
+                    error: -1.0,
+                    track: vec![],
+                },
+            ),
+            (
+                7,
+                Point {
+                    point_id: 7,
+                    position: [
+                        -0.24065866398375135,
+                        0.1763233421385975,
+                        1.6066914460314323,
+                    ],
+                    color_rgb: [141, 147, 89],
+                    error: -1.0,
+                    track: vec![],
+                },
+            ),
+            (
+                8,
+                Point {
+                    point_id: 8,
+                    position: [0.7556535574483431, 0.6682392592540607, 3.120770469139577],
+                    color_rgb: [153, 149, 137],
+                    error: -1.0,
+                    track: vec![],
+                },
+            ),
+            (
+                9,
+                Point {
+                    point_id: 9,
+                    position: [
+                        -1.9299760562484711,
+                        -0.37688731833688194,
+                        0.8368212073339936,
+                    ],
+                    color_rgb: [84, 88, 78],
+                    error: -1.0,
+                    track: vec![],
+                },
+            ),
         ]
         .into_iter()
         .collect::<Points>();
@@ -207,4 +677,52 @@ mod tests {
         let output = writer.into_inner();
         assert_eq!(output, target);
     }
+
+    #[test]
+    fn decode_with_and_encode_with_on_text() {
+        use super::*;
+        use std::io::Cursor;
+
+        let source_binary =
+            &include_bytes!("../../../../examples/data/colmap/0/points3D.bin")[..];
+        let source_text =
+            &include_bytes!("../../../../examples/data/colmap/4/points3D.txt")[..];
+
+        let target = Points::decode(&mut Cursor::new(source_binary)).unwrap();
+        let output =
+            Points::decode_with(&mut Cursor::new(source_binary), Format::Binary).unwrap();
+        assert_eq!(output, target);
+        let output =
+            Points::decode_with(&mut Cursor::new(source_text), Format::Text).unwrap();
+        assert_eq!(output, target);
+
+        let target = source_text;
+        let mut writer = Cursor::new(vec![]);
+        output.encode_with(&mut writer, Format::Text).unwrap();
+        let output = writer.into_inner();
+        assert_eq!(output, target);
+    }
+
+    #[test]
+    fn decode_and_encode() {
+        use super::*;
+        use std::io::Cursor;
+
+        let source =
+            &include_bytes!("../../../../examples/data/colmap/0/points3D.bin")[..];
+
+        let target = source;
+        let points = Points::decode(&mut Cursor::new(source)).unwrap();
+        let mut writer = Cursor::new(vec![]);
+        points.encode(&mut writer).unwrap();
+        let output = writer.into_inner();
+        assert_eq!(output, target);
+
+        let target = 49;
+        let output = points
+            .values()
+            .map(|point| point.track.len())
+            .sum::<usize>();
+        assert_eq!(output, target);
+    }
 }
@@ -0,0 +1,519 @@
+//! COLMAP image undistortion.
+//!
+//! It warps the images from distorted cameras to pinhole cameras,
+//! like the `image_undistorter` command of COLMAP.
+
+pub use super::*;
+pub use ::image::RgbImage;
+
+use crate::source::image::Image as ImageEncoded;
+use rayon::{
+    iter::{IndexedParallelIterator, IntoParallelRefMutIterator, ParallelIterator},
+    slice::ParallelSliceMut,
+};
+use std::io::{Cursor, Read};
+
+/// Undistortion options.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct UndistortOptions {
+    /// The ratio of blank pixels to keep in the undistorted image.
+    ///
+    /// It ranges from `0.0` to `1.0`.
+    /// `0.0` crops the image to the valid region,
+    /// and `1.0` keeps all pixels of the distorted image.
+    pub blank_pixels: f64,
+    /// The maximum scale of the image size.
+    pub scale_max: f64,
+    /// The minimum scale of the image size.
+    pub scale_min: f64,
+    /// The maximum side length of the undistorted image.
+    pub size_max: Option<u64>,
+}
+
+impl Camera {
+    /// Return the undistorted pinhole camera.
+    ///
+    /// The dimensions and the principal point are adjusted to
+    /// the valid region specified by the `options`.
+    pub fn undistort_camera(
+        &self,
+        options: &UndistortOptions,
+    ) -> Camera {
+        let mut camera = Camera {
+            variant: CameraVariant::Pinhole {
+                focal_length_x: self.focal_length_x(),
+                focal_length_y: self.focal_length_y(),
+            },
+            ..*self
+        };
+
+        if !matches!(
+            self.variant,
+            CameraVariant::SimplePinhole { .. } | CameraVariant::Pinhole { .. }
+        ) {
+            let width = self.width as f64;
+            let height = self.height as f64;
+            let undistort = |pixel| camera.project(self.unproject(pixel, 1.0));
+
+            let (left, right) = (0..self.height).fold(
+                (
+                    [f64::INFINITY, f64::NEG_INFINITY],
+                    [f64::INFINITY, f64::NEG_INFINITY],
+                ),
+                |(left, right), y| {
+                    let y = y as f64 + 0.5;
+                    let [left_x, _] = undistort([0.5, y]);
+                    let [right_x, _] = undistort([width - 0.5, y]);
+                    (
+                        [left[0].min(left_x), left[1].max(left_x)],
+                        [right[0].min(right_x), right[1].max(right_x)],
+                    )
+                },
+            );
+            let (top, bottom) = (0..self.width).fold(
+                (
+                    [f64::INFINITY, f64::NEG_INFINITY],
+                    [f64::INFINITY, f64::NEG_INFINITY],
+                ),
+                |(top, bottom), x| {
+                    let x = x as f64 + 0.5;
+                    let [_, top_y] = undistort([x, 0.5]);
+                    let [_, bottom_y] = undistort([x, height - 0.5]);
+                    (
+                        [top[0].min(top_y), top[1].max(top_y)],
+                        [bottom[0].min(bottom_y), bottom[1].max(bottom_y)],
+                    )
+                },
+            );
+
+            let x = camera.principal_point_x;
+            let y = camera.principal_point_y;
+            // NOTE: The scales to contain all pixels of the distorted image.
+            let scale_all_x = (x / (x - left[0])).min((width - 0.5 - x) / (right[1] - x));
+            let scale_all_y =
+                (y / (y - top[0])).min((height - 0.5 - y) / (bottom[1] - y));
+            // NOTE: The scales to contain no blank pixels.
+            let scale_valid_x =
+                (x / (x - left[1])).max((width - 0.5 - x) / (right[0] - x));
+            let scale_valid_y =
+                (y / (y - top[1])).max((height - 0.5 - y) / (bottom[0] - y));
+
+            let blank_pixels = options.blank_pixels.clamp(0.0, 1.0);
+            let scale_x = (1.0
+                / (scale_all_x * blank_pixels + scale_valid_x * (1.0 - blank_pixels)))
+                .clamp(options.scale_min, options.scale_max);
+            let scale_y = (1.0
+                / (scale_all_y * blank_pixels + scale_valid_y * (1.0 - blank_pixels)))
+                .clamp(options.scale_min, options.scale_max);
+
+            camera.width = ((scale_x * width) as u64).max(1);
+            camera.height = ((scale_y * height) as u64).max(1);
+            camera.principal_point_x *= camera.width as f64 / width;
+            camera.principal_point_y *= camera.height as f64 / height;
+        }
+
+        if let Some(size_max) = options.size_max {
+            let scale = (size_max as f64 / camera.width as f64)
+                .min(size_max as f64 / camera.height as f64);
+            if scale < 1.0 {
+                let width = ((scale * camera.width as f64).round() as u64).max(1);
+                let height = ((scale * camera.height as f64).round() as u64).max(1);
+                let scale_x = width as f64 / camera.width as f64;
+                let scale_y = height as f64 / camera.height as f64;
+                camera.width = width;
+                camera.height = height;
+                camera.principal_point_x *= scale_x;
+                camera.principal_point_y *= scale_y;
+                camera.variant = CameraVariant::Pinhole {
+                    focal_length_x: camera.focal_length_x() * scale_x,
+                    focal_length_y: camera.focal_length_y() * scale_y,
+                };
+            }
+        }
+
+        camera
+    }
+
+    /// Undistort the image to an [`RgbImage`] with the pinhole camera.
+    ///
+    /// The pinhole camera is obtained from [`Camera::undistort_camera`].
+    pub fn undistort_image(
+        &self,
+        image: &ImageEncoded,
+        options: &UndistortOptions,
+    ) -> Result<(RgbImage, Camera), Error> {
+        let camera = self.undistort_camera(options);
+        let image = self.warp_rgb(&camera, &image.decode_rgb()?);
+        Ok((image, camera))
+    }
+
+    /// Warping the [`RgbImage`] of `self` to the `target` camera.
+    ///
+    /// The pixels out of the image are black.
+    pub fn warp_rgb(
+        &self,
+        target: &Camera,
+        image: &RgbImage,
+    ) -> RgbImage {
+        const CHANNEL_COUNT: usize = 3;
+
+        let width = target.width as usize;
+        let height = target.height as usize;
+        let mut data = vec![0; width * height * CHANNEL_COUNT];
+
+        data.par_chunks_mut(width * CHANNEL_COUNT)
+            .enumerate()
+            .for_each(|(y, row)| {
+                row.chunks_mut(CHANNEL_COUNT)
+                    .enumerate()
+                    .for_each(|(x, pixel)| {
+                        let point =
+                            target.unproject([x as f64 + 0.5, y as f64 + 0.5], 1.0);
+                        let [x, y] = self.project(point);
+                        pixel.copy_from_slice(&Self::sample_bilinear(
+                            image,
+                            x - 0.5,
+                            y - 0.5,
+                        ));
+                    });
+            });
+
+        // NOTE: The data size just fits.
+        RgbImage::from_raw(width as u32, height as u32, data).unwrap()
+    }
+
+    /// Sampling the pixel at `(x, y)` by bilinear interpolation.
+    fn sample_bilinear(
+        image: &RgbImage,
+        x: f64,
+        y: f64,
+    ) -> [u8; 3] {
+        let (width, height) = image.dimensions();
+        if !(x > -1.0 && y > -1.0 && x < width as f64 && y < height as f64) {
+            return [0; 3];
+        }
+
+        let x_0 = x.floor();
+        let y_0 = y.floor();
+        let d_x = x - x_0;
+        let d_y = y - y_0;
+        let get = |x: f64, y: f64| {
+            if x < 0.0 || y < 0.0 || x >= width as f64 || y >= height as f64 {
+                [0.0; 3]
+            } else {
+                image.get_pixel(x as u32, y as u32).0.map(f64::from)
+            }
+        };
+        let p_00 = get(x_0, y_0);
+        let p_01 = get(x_0 + 1.0, y_0);
+        let p_10 = get(x_0, y_0 + 1.0);
+        let p_11 = get(x_0 + 1.0, y_0 + 1.0);
+
+        [0, 1, 2].map(|c| {
+            let top = p_00[c] * (1.0 - d_x) + p_01[c] * d_x;
+            let bottom = p_10[c] * (1.0 - d_x) + p_11[c] * d_x;
+            (top * (1.0 - d_y) + bottom * d_y).round().clamp(0.0, 255.0) as u8
+        })
+    }
+}
+
+impl<S: Read + Send> ColmapSource<S> {
+    /// Undistort all images to pinhole cameras in parallel.
+    ///
+    /// The undistorted images are encoded in memory
+    /// with the formats of their file paths.
+    /// The files not referred by any image are copied.
+    ///
+    /// The positions of [`Image::points`] are undistorted as well.
+    pub fn undistort(
+        &mut self,
+        options: &UndistortOptions,
+    ) -> Result<ColmapSource<Cursor<Vec<u8>>>, Error> {
+        let cameras = self
+            .cameras
+            .iter()
+            .map(|(camera_id, camera)| (*camera_id, camera.undistort_camera(options)))
+            .collect::<Cameras>();
+        let cameras_source = &self.cameras;
+        let images = self
+            .images
+            .iter()
+            .map(|(image_id, image)| {
+                let mut image = image.to_owned();
+                let camera_pair = cameras_source
+                    .get(&image.camera_id)
+                    .zip(cameras.get(&image.camera_id));
+                if let Some(((source, target), points)) =
+                    camera_pair.zip(image.points.as_mut())
+                {
+                    points.iter_mut().for_each(|point| {
+                        point.position =
+                            target.project(source.unproject(point.position, 1.0));
+                    });
+                }
+                (*image_id, image)
+            })
+            .collect::<Images>();
+        let images_index = &ImageFileIndex::new(&images);
+
+        let images_file = self
+            .images_file
+            .par_iter_mut()
+            .map(|(path, file)| {
+                let mut image = ImageEncoded {
+                    image_encoded: file.read_all()?,
+                    image_file_path: path.to_owned(),
+                    image_id: Default::default(),
+                };
+
+                let camera_pair = images_index.get(path).and_then(|image_colmap| {
+                    image.image_id = image_colmap.image_id;
+                    Some((
+                        cameras_source.get(&image_colmap.camera_id)?,
+                        cameras.get(&image_colmap.camera_id)?,
+                    ))
+                });
+                if let Some((source, target)) = camera_pair {
+                    image.encode_rgb(source.warp_rgb(target, &image.decode_rgb()?))?;
+                }
+
+                let file = File {
+                    inner: Cursor::new(image.image_encoded),
+                    path: path.to_owned(),
+                };
+                Ok((path.to_owned(), file))
+            })
+            .collect::<Result<_, Error>>()?;
+
+        #[cfg(all(debug_assertions, not(test)))]
+        log::debug!(target: "gausplat-loader::colmap::undistort", "ColmapSource::undistort");
+
+        Ok(ColmapSource {
+            cameras,
+            images,
+            images_file,
+            points: self.points.to_owned(),
+        })
+    }
+}
+
+impl Default for UndistortOptions {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            blank_pixels: 0.0,
+            scale_max: 2.0,
+            scale_min: 0.2,
+            size_max: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn undistort_camera_on_pinhole() {
+        use super::*;
+
+        let source = Camera {
+            camera_id: 1,
+            width: 640,
+            height: 480,
+            principal_point_x: 320.0,
+            principal_point_y: 240.0,
+            variant: CameraVariant::SimplePinhole {
+                focal_length: 500.0,
+            },
+        };
+
+        let target = Camera {
+            variant: CameraVariant::Pinhole {
+                focal_length_x: 500.0,
+                focal_length_y: 500.0,
+            },
+            ..source
+        };
+        let output = source.undistort_camera(&Default::default());
+        assert_eq!(output, target);
+
+        let target = Camera {
+            width: 320,
+            height: 240,
+            principal_point_x: 160.0,
+            principal_point_y: 120.0,
+            variant: CameraVariant::Pinhole {
+                focal_length_x: 250.0,
+                focal_length_y: 250.0,
+            },
+            ..source
+        };
+        let output = source.undistort_camera(&UndistortOptions {
+            size_max: Some(320),
+            ..Default::default()
+        });
+        assert_eq!(output, target);
+    }
+
+    #[test]
+    fn undistort_camera_on_blank_pixels() {
+        use super::*;
+
+        let source = Camera {
+            camera_id: 1,
+            width: 640,
+            height: 480,
+            principal_point_x: 320.0,
+            principal_point_y: 240.0,
+            variant: CameraVariant::SimpleRadial {
+                focal_length: 500.0,
+                k: -0.1,
+            },
+        };
+
+        let output_valid = source.undistort_camera(&Default::default());
+        let output_all = source.undistort_camera(&UndistortOptions {
+            blank_pixels: 1.0,
+            ..Default::default()
+        });
+
+        let target = true;
+        let output = output_valid.width < output_all.width
+            && output_valid.height < output_all.height;
+        assert_eq!(output, target);
+
+        let target = true;
+        let output = output_all.width > source.width
+            && output_all.height > source.height
+            && output_valid.model_id() == 1
+            && output_all.model_id() == 1;
+        assert_eq!(output, target);
+    }
+
+    #[test]
+    fn undistort_image_on_pinhole() {
+        use super::*;
+
+        let source = &include_bytes!("../../../examples/data/image/rainbow-8x8.png")[..];
+        let image = ImageEncoded {
+            image_encoded: source.to_vec(),
+            image_file_path: "rainbow-8x8.png".into(),
+            image_id: Default::default(),
+        };
+        let camera = Camera {
+            camera_id: 1,
+            width: 8,
+            height: 8,
+            principal_point_x: 4.0,
+            principal_point_y: 4.0,
+            variant: CameraVariant::SimplePinhole { focal_length: 8.0 },
+        };
+
+        let target = image.decode_rgb().unwrap();
+        let (output, _) = camera.undistort_image(&image, &Default::default()).unwrap();
+        assert_eq!(output, target);
+    }
+
+    #[test]
+    fn undistort_on_source() {
+        use super::*;
+
+        let source = &include_bytes!("../../../examples/data/image/example.png")[..];
+        let mut colmap_source = ColmapSource::<Cursor<Vec<u8>>> {
+            cameras: [(
+                1,
+                Camera {
+                    camera_id: 1,
+                    width: 172,
+                    height: 178,
+                    principal_point_x: 86.0,
+                    principal_point_y: 89.0,
+                    variant: CameraVariant::OpenCv {
+                        focal_length_x: 150.0,
+                        focal_length_y: 150.0,
+                        k1: -0.2,
+                        k2: 0.05,
+                        p1: 0.001,
+                        p2: -0.001,
+                    },
+                },
+            )]
+            .into(),
+            images: [(
+                1,
+                Image {
+                    image_id: 1,
+                    camera_id: 1,
+                    quaternion: [1.0, 0.0, 0.0, 0.0],
+                    file_name: c"example.png".into(),
+                    points: Some(vec![
+                        ImagePoint {
+                            position: [86.0, 89.0],
+                            point_id: 1,
+                        },
+                        ImagePoint {
+                            position: [10.0, 20.0],
+                            point_id: ImagePoint::POINT_ID_INVALID,
+                        },
+                    ]),
+                    ..Default::default()
+                },
+            )]
+            .into(),
+            images_file: [
+                (
+                    "images/example.png".into(),
+                    File {
+                        inner: Cursor::new(source.to_vec()),
+                        path: "images/example.png".into(),
+                    },
+                ),
+                (
+                    "images/unknown.png".into(),
+                    File {
+                        inner: Cursor::new(source.to_vec()),
+                        path: "images/unknown.png".into(),
+                    },
+                ),
+            ]
+            .into(),
+            points: Default::default(),
+        };
+
+        let mut output = colmap_source.undistort(&Default::default()).unwrap();
+        let camera = output.cameras[&1];
+
+        let target = 1;
+        let output_model_id = camera.model_id();
+        assert_eq!(output_model_id, target);
+
+        let points_source = colmap_source.images[&1].points.as_ref().unwrap();
+        let points = output.images[&1].points.as_ref().unwrap();
+        let target = [camera.principal_point_x, camera.principal_point_y];
+        let output_position = points[0].position;
+        assert_eq!(output_position, target);
+
+        let target = points_source[1].position;
+        let output_position = colmap_source.cameras[&1]
+            .project(camera.unproject(points[1].position, 1.0))
+            .map(|value| (value * 1e6).round() / 1e6);
+        assert_eq!(output_position, target);
+
+        let target = true;
+        let output_moved = points[1].position != points_source[1].position
+            && points[1].point_id == points_source[1].point_id;
+        assert_eq!(output_moved, target);
+
+        let target = (camera.width as u32, camera.height as u32);
+        let image = ImageEncoded {
+            image_encoded: output.images_file[0].read_all().unwrap(),
+            image_file_path: "example.png".into(),
+            image_id: Default::default(),
+        };
+        let output_dimensions = image.decode_dimensions().unwrap();
+        assert_eq!(output_dimensions, target);
+
+        let target = source;
+        let output_unknown = output.images_file[1].read_all().unwrap();
+        assert_eq!(output_unknown, target);
+    }
+}
@@ -0,0 +1,523 @@
+//! COLMAP source validation.
+
+pub use super::*;
+
+use std::{
+    collections::HashSet,
+    fmt,
+    io::{BufReader, Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
+};
+
+/// An inconsistency found by [`ColmapSource::validate`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum ValidationIssue {
+    /// The image file is not referred by any image.
+    ExtraImageFile {
+        /// [File path](File::path).
+        path: PathBuf,
+    },
+    /// The image dimensions do not match the camera.
+    MismatchedImageDimensions {
+        /// [Image ID](Image::image_id).
+        image_id: u32,
+        /// The dimensions `(width, height)` of the image file.
+        dimensions: (u64, u64),
+        /// The dimensions `(width, height)` of the camera.
+        dimensions_camera: (u64, u64),
+    },
+    /// The camera referred by the image does not exist.
+    MissingCamera {
+        /// [Image ID](Image::image_id).
+        image_id: u32,
+        /// [Camera ID](Camera::camera_id).
+        camera_id: u32,
+    },
+    /// The image referred by the point track does not exist.
+    MissingImage {
+        /// [Point ID](Point::point_id).
+        point_id: u64,
+        /// [Image ID](Image::image_id).
+        image_id: u32,
+    },
+    /// The image file does not exist.
+    MissingImageFile {
+        /// [Image ID](Image::image_id).
+        image_id: u32,
+    },
+    /// The image point referred by the point track does not exist.
+    ///
+    /// It is checked only if [`Image::points`] are decoded.
+    MissingImagePoint {
+        /// [Point ID](Point::point_id).
+        point_id: u64,
+        /// [Image ID](Image::image_id).
+        image_id: u32,
+        /// [Point index](TrackElement::point_index).
+        point_index: u32,
+    },
+    /// The point referred by the image does not exist.
+    MissingPoint {
+        /// [Image ID](Image::image_id).
+        image_id: u32,
+        /// [Point ID](Point::point_id).
+        point_id: u64,
+    },
+    /// The value is not finite, i.e., NaN or infinite.
+    NonFiniteValue {
+        /// The kind of the entry, i.e., `"camera"`, `"image"` or `"point"`.
+        kind: &'static str,
+        /// The ID of the entry.
+        id: u64,
+    },
+    /// The quaternion of the image is not normalized.
+    NonUnitQuaternion {
+        /// [Image ID](Image::image_id).
+        image_id: u32,
+        /// The norm of the quaternion.
+        norm: f64,
+    },
+    /// The image file cannot be decoded.
+    UndecodableImageFile {
+        /// [File path](File::path).
+        path: PathBuf,
+        /// The error message.
+        message: String,
+    },
+}
+
+/// A report of [`ColmapSource::validate`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ValidationReport {
+    /// All inconsistencies found.
+    pub issues: Vec<ValidationIssue>,
+}
+
+impl ValidationReport {
+    /// Check if there is no issue.
+    #[inline]
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+impl<S: Read + Seek> ColmapSource<S> {
+    /// Validate the referential integrity and the values.
+    ///
+    /// It lists all inconsistencies in the [`ValidationReport`].
+    /// The image files are rewound after reading their dimensions.
+    pub fn validate(&mut self) -> Result<ValidationReport, Error> {
+        // The tolerance of the quaternion norm.
+        const QUATERNION_NORM_TOLERANCE: f64 = 1e-6;
+
+        use ValidationIssue::*;
+
+        let mut issues = vec![];
+        let images_index = ImageFileIndex::new(&self.images);
+        // NOTE: An image has a file if its file name is a trailing part of a file path.
+        let images_file_suffixes = self
+            .images_file
+            .keys()
+            .flat_map(|path| {
+                let components = path.components().collect::<Vec<_>>();
+                (0..=components.len())
+                    .map(|start| components[start..].iter().collect::<PathBuf>())
+                    .collect::<Vec<_>>()
+            })
+            .collect::<HashSet<_>>();
+
+        self.cameras.values().for_each(|camera| {
+            if !camera.parameters().iter().all(|value| value.is_finite()) {
+                issues.push(NonFiniteValue {
+                    kind: "camera",
+                    id: camera.camera_id as u64,
+                });
+            }
+        });
+
+        self.images.values().for_each(|image| {
+            let image_id = image.image_id;
+
+            if !image
+                .quaternion
+                .iter()
+                .chain(&image.translation)
+                .all(|v| v.is_finite())
+                || image
+                    .points
+                    .iter()
+                    .flatten()
+                    .any(|point| !point.position.iter().all(|v| v.is_finite()))
+            {
+                issues.push(NonFiniteValue {
+                    kind: "image",
+                    id: image_id as u64,
+                });
+            } else {
+                let norm = image.quaternion.iter().map(|v| v * v).sum::<f64>().sqrt();
+                if (norm - 1.0).abs() > QUATERNION_NORM_TOLERANCE {
+                    issues.push(NonUnitQuaternion { image_id, norm });
+                }
+            }
+
+            if !self.cameras.contains_key(&image.camera_id) {
+                issues.push(MissingCamera {
+                    image_id,
+                    camera_id: image.camera_id,
+                });
+            }
+
+            image
+                .points
+                .iter()
+                .flatten()
+                .filter(|point| {
+                    point.is_triangulated() && !self.points.contains_key(&point.point_id)
+                })
+                .for_each(|point| {
+                    issues.push(MissingPoint {
+                        image_id,
+                        point_id: point.point_id,
+                    });
+                });
+
+            if !image.file_name.to_str().is_ok_and(|file_name| {
+                images_file_suffixes.contains(Path::new(file_name))
+            }) {
+                issues.push(MissingImageFile { image_id });
+            }
+        });
+
+        self.points.values().for_each(|point| {
+            let point_id = point.point_id;
+
+            if !point.position.iter().all(|v| v.is_finite()) || point.error.is_nan() {
+                issues.push(NonFiniteValue {
+                    kind: "point",
+                    id: point_id,
+                });
+            }
+
+            point.track.iter().for_each(|element| {
+                let image_id = element.image_id;
+                let point_index = element.point_index;
+                match self.images.get(&image_id) {
+                    None => issues.push(MissingImage { point_id, image_id }),
+                    Some(Image {
+                        points: Some(points),
+                        ..
+                    }) if point_index as usize >= points.len() => {
+                        issues.push(MissingImagePoint {
+                            point_id,
+                            image_id,
+                            point_index,
+                        })
+                    },
+                    _ => {},
+                }
+            });
+        });
+
+        for (path, file) in self.images_file.iter_mut() {
+            let Some(image) = images_index.get(path) else {
+                issues.push(ExtraImageFile {
+                    path: path.to_owned(),
+                });
+                continue;
+            };
+            let Some(camera) = self.cameras.get(&image.camera_id) else {
+                continue;
+            };
+
+            file.seek(SeekFrom::Start(0))?;
+            let dimensions = ::image::ImageReader::new(BufReader::new(&mut file.inner))
+                .with_guessed_format()?
+                .into_dimensions();
+            file.seek(SeekFrom::Start(0))?;
+
+            match dimensions {
+                Ok((width, height)) => {
+                    let dimensions = (width as u64, height as u64);
+                    let dimensions_camera = (camera.width, camera.height);
+                    if dimensions != dimensions_camera {
+                        issues.push(MismatchedImageDimensions {
+                            image_id: image.image_id,
+                            dimensions,
+                            dimensions_camera,
+                        });
+                    }
+                },
+                Err(err) => issues.push(UndecodableImageFile {
+                    path: path.to_owned(),
+                    message: err.to_string(),
+                }),
+            }
+        }
+
+        #[cfg(all(debug_assertions, not(test)))]
+        log::debug!(target: "gausplat-loader::colmap::validate", "ColmapSource::validate");
+
+        Ok(ValidationReport { issues })
+    }
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        use ValidationIssue::*;
+
+        match self {
+            ExtraImageFile { path } => {
+                write!(f, "The image file {path:?} is not referred by any image.")
+            },
+            MismatchedImageDimensions {
+                image_id,
+                dimensions,
+                dimensions_camera,
+            } => write!(
+                f,
+                "The image {image_id} has dimensions {dimensions:?}. \
+                It should be {dimensions_camera:?}."
+            ),
+            MissingCamera {
+                image_id,
+                camera_id,
+            } => write!(
+                f,
+                "The image {image_id} refers to a missing camera {camera_id}."
+            ),
+            MissingImage { point_id, image_id } => {
+                write!(
+                    f,
+                    "The point {point_id} refers to a missing image {image_id}."
+                )
+            },
+            MissingImageFile { image_id } => {
+                write!(f, "The image {image_id} has no image file.")
+            },
+            MissingImagePoint {
+                point_id,
+                image_id,
+                point_index,
+            } => write!(
+                f,
+                "The point {point_id} refers to a missing point {point_index} \
+                in the image {image_id}."
+            ),
+            MissingPoint { image_id, point_id } => {
+                write!(
+                    f,
+                    "The image {image_id} refers to a missing point {point_id}."
+                )
+            },
+            NonFiniteValue { kind, id } => {
+                write!(f, "The {kind} {id} has a non-finite value.")
+            },
+            NonUnitQuaternion { image_id, norm } => write!(
+                f,
+                "The image {image_id} has a quaternion of norm {norm}. It should be 1."
+            ),
+            UndecodableImageFile { path, message } => {
+                write!(f, "The image file {path:?} cannot be decoded: {message}")
+            },
+        }
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        self.issues
+            .iter()
+            .try_for_each(|issue| writeln!(f, "{issue}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn validate() {
+        use super::*;
+        use ValidationIssue::*;
+
+        let source = "examples/data/colmap/5";
+        let mut colmap_source = ColmapSource::open(source).unwrap();
+        let report = colmap_source.validate().unwrap();
+
+        // NOTE: The tracks refer to the images not in the fixture.
+        let target = true;
+        let output = report.issues.iter().all(|issue| {
+            matches!(
+                issue,
+                MissingPoint { .. }
+                    | MissingImage { .. }
+                    | MismatchedImageDimensions { .. }
+            )
+        });
+        assert_eq!(output, target);
+
+        let target = 5;
+        let output = report
+            .issues
+            .iter()
+            .filter(|issue| {
+                matches!(
+                    issue,
+                    MismatchedImageDimensions {
+                        dimensions: (8, 8),
+                        ..
+                    }
+                )
+            })
+            .count();
+        assert_eq!(output, target);
+
+        let target = include_bytes!("../../../examples/data/image/rainbow-8x8.png");
+        let output = colmap_source.images_file[0].read_all().unwrap();
+        assert_eq!(output, target);
+    }
+
+    #[test]
+    fn validate_on_inconsistencies() {
+        use super::*;
+        use std::io::Cursor;
+        use ValidationIssue::*;
+
+        let source = &include_bytes!("../../../examples/data/image/rainbow-8x8.png")[..];
+        let mut colmap_source = ColmapSource::<Cursor<Vec<u8>>> {
+            cameras: [(
+                1,
+                Camera {
+                    camera_id: 1,
+                    width: 8,
+                    height: 8,
+                    principal_point_x: f64::NAN,
+                    ..Default::default()
+                },
+            )]
+            .into(),
+            images: [
+                (
+                    1,
+                    Image {
+                        image_id: 1,
+                        camera_id: 1,
+                        quaternion: [1.0, 0.0, 0.0, 0.0],
+                        file_name: c"1.png".into(),
+                        points: Some(vec![ImagePoint {
+                            position: [0.0, 0.0],
+                            point_id: 2,
+                        }]),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    2,
+                    Image {
+                        image_id: 2,
+                        camera_id: 3,
+                        quaternion: [2.0, 0.0, 0.0, 0.0],
+                        file_name: c"2.png".into(),
+                        ..Default::default()
+                    },
+                ),
+            ]
+            .into(),
+            images_file: [
+                (
+                    "images/1.png".into(),
+                    File {
+                        inner: Cursor::new(source.to_vec()),
+                        path: "images/1.png".into(),
+                    },
+                ),
+                (
+                    "images/3.png".into(),
+                    File {
+                        inner: Cursor::new(source.to_vec()),
+                        path: "images/3.png".into(),
+                    },
+                ),
+            ]
+            .into(),
+            points: [(
+                1,
+                Point {
+                    point_id: 1,
+                    position: [0.0, f64::INFINITY, 0.0],
+                    track: vec![
+                        TrackElement {
+                            image_id: 4,
+                            point_index: 0,
+                        },
+                        TrackElement {
+                            image_id: 1,
+                            point_index: 0,
+                        },
+                        TrackElement {
+                            image_id: 1,
+                            point_index: 1,
+                        },
+                        TrackElement {
+                            image_id: 2,
+                            point_index: 1,
+                        },
+                    ],
+                    ..Default::default()
+                },
+            )]
+            .into(),
+        };
+
+        let target = vec![
+            NonFiniteValue {
+                kind: "camera",
+                id: 1,
+            },
+            MissingPoint {
+                image_id: 1,
+                point_id: 2,
+            },
+            NonUnitQuaternion {
+                image_id: 2,
+                norm: 2.0,
+            },
+            MissingCamera {
+                image_id: 2,
+                camera_id: 3,
+            },
+            MissingImageFile { image_id: 2 },
+            NonFiniteValue {
+                kind: "point",
+                id: 1,
+            },
+            MissingImage {
+                point_id: 1,
+                image_id: 4,
+            },
+            MissingImagePoint {
+                point_id: 1,
+                image_id: 1,
+                point_index: 1,
+            },
+            ExtraImageFile {
+                path: "images/3.png".into(),
+            },
+        ];
+        let report = colmap_source.validate().unwrap();
+        let output = report.issues.to_owned();
+        assert_eq!(output, target);
+
+        let target = false;
+        let output = report.is_valid();
+        assert_eq!(output, target);
+
+        let target = 9;
+        let output = report.to_string().lines().count();
+        assert_eq!(output, target);
+    }
+}
@@ -31,6 +31,36 @@ impl Image {
         Ok(image::load_from_memory(&self.image_encoded)?.into_rgb8())
     }
 
+    /// Decoding an [`RgbImage`] from [`Self::image_encoded`]
+    /// with the alpha channel composited over the `background`.
+    ///
+    /// The `background` is generally white (`[255; 3]`) or black (`[0; 3]`).
+    pub fn decode_rgb_with_background(
+        &self,
+        background: [u8; 3],
+    ) -> Result<RgbImage, Error> {
+        let image = image::load_from_memory(&self.image_encoded)?;
+        if !image.color().has_alpha() {
+            return Ok(image.into_rgb8());
+        }
+
+        let image = image.into_rgba8();
+        let (width, height) = image.dimensions();
+        let value = image
+            .pixels()
+            .flat_map(|pixel| {
+                let [r, g, b, a] = pixel.0.map(|v| v as u32);
+                let [r_b, g_b, b_b] = background.map(|v| v as u32);
+                // NOTE: It rounds to the nearest integer.
+                [(r, r_b), (g, g_b), (b, b_b)]
+                    .map(|(c, c_b)| ((c * a + c_b * (255 - a) + 127) / 255) as u8)
+            })
+            .collect();
+
+        // NOTE: The data size just fits.
+        Ok(RgbImage::from_raw(width, height, value).unwrap())
+    }
+
     /// Encoding an [`RgbImage`] to [`Self::image_encoded`].
     pub fn encode_rgb(
         &mut self,
@@ -436,4 +466,34 @@ mod tests {
             .unwrap();
         assert_eq!(output, target);
     }
+
+    #[test]
+    fn decode_rgb_with_background() {
+        use super::*;
+
+        let image = Image {
+            image_encoded: include_bytes!("../../../examples/data/nerf/0/train/r_0.png")
+                .to_vec(),
+            ..Default::default()
+        };
+
+        let target = [[255, 255, 255], [255, 255, 127], [0, 255, 0], [0, 255, 0]];
+        let output = image.decode_rgb_with_background([255; 3]).unwrap();
+        let output = (0..4).map(|x| output.get_pixel(x, 0).0).collect::<Vec<_>>();
+        assert_eq!(output, target);
+
+        let target = [[0, 0, 0], [128, 0, 0], [0, 0, 0], [0, 0, 0]];
+        let output = image.decode_rgb_with_background([0; 3]).unwrap();
+        let output = (0..4).map(|x| output.get_pixel(x, 3).0).collect::<Vec<_>>();
+        assert_eq!(output, target);
+
+        let image = Image {
+            image_encoded: include_bytes!("../../../examples/data/image/rainbow-8x8.png")
+                .to_vec(),
+            ..Default::default()
+        };
+        let target = image.decode_rgb().unwrap();
+        let output = image.decode_rgb_with_background([255; 3]).unwrap();
+        assert_eq!(output, target);
+    }
 }
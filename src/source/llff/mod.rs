@@ -0,0 +1,346 @@
+//! LLFF source.
+//!
+//! It supports the `poses_bounds.npy` format of LLFF and Mip-NeRF 360 datasets.
+//!
+//! For more information, see the LLFF
+//! [repository](https://github.com/Fyusion/LLFF#using-your-own-poses-without-running-colmap).
+
+pub use super::colmap::{Camera, CameraVariant, Cameras, ColmapSource, Image, Images};
+pub use super::file::*;
+pub use super::npy::*;
+pub use crate::collection::IndexMap;
+
+use std::{
+    ffi::CString,
+    fmt, fs,
+    path::{Path, PathBuf},
+};
+
+/// The column count of `poses_bounds.npy`.
+///
+/// It is the sum of a 3x5 pose matrix and 2 bounds.
+pub const POSES_BOUNDS_COLUMN_COUNT: usize = 17;
+
+/// LLFF source.
+///
+/// It is converted to the cameras and images of [`ColmapSource`].
+#[derive(Clone, PartialEq)]
+pub struct LlffSource<S> {
+    /// Cameras.
+    pub cameras: Cameras,
+    /// Images.
+    pub images: Images,
+    /// Images' file.
+    pub images_file: Files<S>,
+    /// The near and far bounds mapped by [image ID](Image::image_id).
+    pub bounds: IndexMap<u32, [f64; 2]>,
+}
+
+impl LlffSource<fs::File> {
+    /// Opening the LLFF scene in the directory
+    /// with the images downsampled by the `factor`.
+    ///
+    /// The images are read from `images/` if the `factor` is `1`,
+    /// or `images_{factor}/` otherwise, e.g., `images_4/`.
+    /// They are sorted by the file name and matched to the rows of `poses_bounds.npy`.
+    ///
+    /// The focal lengths are scaled to the dimensions of the image files.
+    ///
+    /// The files are opened in read-only mode.
+    ///
+    /// # Errors
+    ///
+    /// It returns [`Error::MissingFile`] with the path of any missing file.
+    ///
+    /// It returns [`Error::MismatchedTensorShape`]
+    /// if the poses do not match the images.
+    pub fn open_with_factor(
+        path: impl AsRef<Path>,
+        factor: u32,
+    ) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let poses_bounds_path = path.join("poses_bounds.npy");
+        if !poses_bounds_path.is_file() {
+            return Err(Error::MissingFile(poses_bounds_path));
+        }
+        let images_path = match factor {
+            1 => path.join("images"),
+            _ => path.join(format!("images_{factor}")),
+        };
+        if !images_path.is_dir() {
+            return Err(Error::MissingFile(images_path));
+        }
+
+        let poses_bounds = NpyArray::decode(&mut fs::File::open(&poses_bounds_path)?)?;
+        let file_paths = Self::image_file_paths(&images_path)?;
+        let shape_target = vec![file_paths.len(), POSES_BOUNDS_COLUMN_COUNT];
+        if poses_bounds.shape != shape_target {
+            return Err(Error::MismatchedTensorShape(
+                poses_bounds.shape,
+                shape_target,
+            ));
+        }
+
+        let mut cameras = Cameras::default();
+        let mut images = Images::default();
+        let mut images_file = Files::default();
+        let mut bounds = IndexMap::default();
+
+        for (index, file_path) in file_paths.into_iter().enumerate() {
+            let image_id = index as u32 + 1;
+            let row = poses_bounds.row(index);
+            // NOTE: The pose is a 3x5 matrix in row-major order.
+            let pose = |r: usize, c: usize| row[r * 5 + c];
+
+            let (width, height) =
+                ::image::ImageReader::open(&file_path)?.into_dimensions()?;
+            let [height_pose, width_pose, focal_length] = [0, 1, 2].map(|r| pose(r, 4));
+            let mut camera = Camera {
+                camera_id: cameras.len() as u32 + 1,
+                width: width as u64,
+                height: height as u64,
+                principal_point_x: width as f64 / 2.0,
+                principal_point_y: height as f64 / 2.0,
+                variant: CameraVariant::Pinhole {
+                    focal_length_x: focal_length * width as f64 / width_pose,
+                    focal_length_y: focal_length * height as f64 / height_pose,
+                },
+            };
+            match cameras.values().find(|other| {
+                Camera {
+                    camera_id: camera.camera_id,
+                    ..**other
+                } == camera
+            }) {
+                Some(other) => camera.camera_id = other.camera_id,
+                None => {
+                    cameras.insert(camera.camera_id, camera);
+                },
+            }
+
+            // NOTE: The axes of LLFF view space are down, right and backwards.
+            // The rows of the camera-to-world rotation are
+            // the columns of the world-to-view rotation.
+            let rotation = [0, 1, 2].map(|r| [pose(r, 1), pose(r, 0), -pose(r, 2)]);
+            let position = [0, 1, 2].map(|r| pose(r, 3));
+
+            let file_name = file_path
+                .file_name()
+                .and_then(|file_name| file_name.to_str())
+                .ok_or_else(|| Error::InvalidUtf8(file_path.to_string_lossy().into()))?;
+            let file_name = CString::new(file_name)
+                .map_err(|err| Error::InvalidUtf8(format!("{:?}", err.into_vec())))?;
+            let mut image = Image {
+                image_id,
+                camera_id: camera.camera_id,
+                file_name,
+                ..Default::default()
+            };
+            image
+                .set_rotation(&rotation)
+                .set_position(&position, &rotation);
+            images.insert(image_id, image);
+
+            bounds.insert(image_id, [row[15], row[16]]);
+
+            let inner = fs::File::open(&file_path)?;
+            images_file.insert(
+                file_path.to_owned(),
+                File {
+                    inner,
+                    path: file_path,
+                },
+            );
+        }
+
+        #[cfg(all(debug_assertions, not(test)))]
+        log::debug!(target: "gausplat-loader::llff", "LlffSource::open_with_factor");
+
+        Ok(Self {
+            cameras,
+            images,
+            images_file,
+            bounds,
+        })
+    }
+
+    /// Return the image file paths in the directory sorted by the file name.
+    ///
+    /// Only the files with the extension `png`, `jpg` or `jpeg` are listed.
+    fn image_file_paths(directory: &Path) -> Result<Vec<PathBuf>, Error> {
+        let mut paths = fs::read_dir(directory)?
+            .map(|entry| Ok(entry?.path()))
+            .collect::<Result<Vec<_>, Error>>()?
+            .into_iter()
+            .filter(|path| {
+                path.is_file()
+                    && path
+                        .extension()
+                        .and_then(|extension| extension.to_str())
+                        .is_some_and(|extension| {
+                            ["png", "jpg", "jpeg"]
+                                .contains(&extension.to_ascii_lowercase().as_str())
+                        })
+            })
+            .collect::<Vec<_>>();
+        paths.sort();
+        Ok(paths)
+    }
+}
+
+impl Opener for LlffSource<fs::File> {
+    /// Opening the LLFF scene in the directory with the images in `images/`.
+    ///
+    /// See [`LlffSource::open_with_factor`] for more information.
+    #[inline]
+    fn open(path: impl AsRef<Path>) -> Result<Self, Error> {
+        Self::open_with_factor(path, 1)
+    }
+}
+
+impl<S> From<LlffSource<S>> for ColmapSource<S> {
+    /// The points are empty. The bounds are dropped.
+    #[inline]
+    fn from(source: LlffSource<S>) -> Self {
+        Self {
+            cameras: source.cameras,
+            images: source.images,
+            images_file: source.images_file,
+            points: Default::default(),
+        }
+    }
+}
+
+impl<S> fmt::Debug for LlffSource<S> {
+    #[inline]
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        f.debug_struct("LlffSource")
+            .field("cameras.len()", &self.cameras.len())
+            .field("images.len()", &self.images.len())
+            .field("images_file.len()", &self.images_file.len())
+            .field("bounds.len()", &self.bounds.len())
+            .finish()
+    }
+}
+
+impl<S: Default> Default for LlffSource<S> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            cameras: Default::default(),
+            images: Default::default(),
+            images_file: Default::default(),
+            bounds: Default::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn open() {
+        use super::*;
+
+        let source = LlffSource::open("examples/data/llff/0").unwrap();
+
+        let target = Camera {
+            camera_id: 1,
+            width: 8,
+            height: 8,
+            principal_point_x: 4.0,
+            principal_point_y: 4.0,
+            variant: CameraVariant::Pinhole {
+                focal_length_x: 10.0,
+                focal_length_y: 10.0,
+            },
+        };
+        let output = source.cameras.values().copied().collect::<Vec<_>>();
+        assert_eq!(output, [target]);
+
+        let target = [c"001.png", c"002.png"];
+        let output = source
+            .images
+            .values()
+            .map(|image| image.file_name.as_c_str())
+            .collect::<Vec<_>>();
+        assert_eq!(output, target);
+
+        let target = [[0.5, 10.0], [1.5, 20.0]];
+        let output = source.bounds.values().copied().collect::<Vec<_>>();
+        assert_eq!(output, target);
+
+        // NOTE: The first pose looks at -Z with Y up in OpenGL convention.
+        let target = ([1.0, 2.0, 3.0], [0.0, -1.0, 0.0, 0.0]);
+        let image = &source.images[&1];
+        let output = (image.position(&image.rotation()), image.quaternion);
+        assert_eq!(output, target);
+
+        let target = ([-1.0, 0.0, 0.0], [1.0, 0.0, 0.0, 0.0]);
+        let image = &source.images[&2];
+        let output = (image.position(&image.rotation()), image.quaternion);
+        assert_eq!(output, target);
+
+        let target = (1, 2, 2, 0);
+        let output = ColmapSource::from(source);
+        let output = (
+            output.cameras.len(),
+            output.images.len(),
+            output.images_file.len(),
+            output.points.len(),
+        );
+        assert_eq!(output, target);
+    }
+
+    #[test]
+    fn open_with_factor() {
+        use super::*;
+
+        let source = LlffSource::open_with_factor("examples/data/llff/0", 2).unwrap();
+
+        let target = true;
+        let output = source
+            .images_file
+            .keys()
+            .all(|path| path.parent().unwrap().ends_with("images_2"));
+        assert_eq!(output, target);
+
+        let target = true;
+        let output = format!("{:?}", source).starts_with("LlffSource");
+        assert_eq!(output, target);
+    }
+
+    #[test]
+    fn open_on_missing_files() {
+        use super::*;
+
+        let target = true;
+        let output = matches!(
+            LlffSource::open_with_factor("examples/data/llff/0", 4).unwrap_err(),
+            Error::MissingFile(path) if path.ends_with("images_4")
+        );
+        assert_eq!(output, target);
+
+        let target = true;
+        let output = matches!(
+            LlffSource::open("examples/data/llff").unwrap_err(),
+            Error::MissingFile(path) if path.ends_with("poses_bounds.npy")
+        );
+        assert_eq!(output, target);
+    }
+
+    #[test]
+    fn open_on_mismatched_images() {
+        use super::*;
+
+        let target = true;
+        let output = matches!(
+            LlffSource::open_with_factor("examples/data/llff/0", 8).unwrap_err(),
+            Error::MismatchedTensorShape(output, target)
+                if output == [2, 17] && target == [1, 17]
+        );
+        assert_eq!(output, target);
+    }
+}
@@ -0,0 +1,163 @@
+//! Meshroom source.
+//!
+//! It supports the `cameras.sfm` and `sfm.json` formats of AliceVision and Meshroom.
+//!
+//! For more information, see the AliceVision
+//! [repository](https://github.com/alicevision/AliceVision/blob/develop/src/aliceVision/sfmDataIO/jsonIO.cpp).
+
+pub mod sfm;
+
+pub use super::colmap::{
+    Camera, CameraVariant, Cameras, ColmapSource, Image, ImagePoint, Images, Point,
+    Points, TrackElement,
+};
+pub use super::file::*;
+pub use crate::function::Decoder;
+pub use sfm::*;
+
+use std::{ffi::CString, fmt, fs, path::Path};
+
+/// Meshroom source.
+///
+/// It is converted to the cameras, images and points of [`ColmapSource`].
+#[derive(Clone, PartialEq)]
+pub struct MeshroomSource<S> {
+    /// Cameras.
+    pub cameras: Cameras,
+    /// Images.
+    pub images: Images,
+    /// Images' file.
+    pub images_file: Files<S>,
+    /// Points.
+    pub points: Points,
+}
+
+impl Opener for MeshroomSource<fs::File> {
+    /// Opening the Meshroom scene from the scene file,
+    /// e.g., `cameras.sfm`.
+    ///
+    /// The relative image file paths are relative to the directory of the scene file.
+    /// The image files of the views without poses are not opened.
+    ///
+    /// The files are opened in read-only mode.
+    ///
+    /// # Errors
+    ///
+    /// It returns [`Error::MissingFile`] with the path of any missing file.
+    fn open(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        if !path.is_file() {
+            return Err(Error::MissingFile(path.to_owned()));
+        }
+        let directory = path.parent().unwrap_or(Path::new("."));
+        let sfm = Sfm::decode(&mut fs::File::open(path)?)?;
+
+        let cameras = sfm.cameras()?;
+        let (images, points) = sfm.images_and_points()?;
+
+        let images_file = sfm
+            .views
+            .iter()
+            .filter(|view| images.contains_key(&view.view_id.0))
+            .map(|view| {
+                let path = directory.join(&view.path);
+                if !path.is_file() {
+                    return Err(Error::MissingFile(path));
+                }
+                let inner = fs::File::open(&path)?;
+                Ok((path.to_owned(), File { inner, path }))
+            })
+            .collect::<Result<_, Error>>()?;
+
+        #[cfg(all(debug_assertions, not(test)))]
+        log::debug!(target: "gausplat-loader::meshroom", "MeshroomSource::open");
+
+        Ok(Self {
+            cameras,
+            images,
+            images_file,
+            points,
+        })
+    }
+}
+
+impl<S> From<MeshroomSource<S>> for ColmapSource<S> {
+    #[inline]
+    fn from(source: MeshroomSource<S>) -> Self {
+        Self {
+            cameras: source.cameras,
+            images: source.images,
+            images_file: source.images_file,
+            points: source.points,
+        }
+    }
+}
+
+impl<S> fmt::Debug for MeshroomSource<S> {
+    #[inline]
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        f.debug_struct("MeshroomSource")
+            .field("cameras.len()", &self.cameras.len())
+            .field("images.len()", &self.images.len())
+            .field("images_file.len()", &self.images_file.len())
+            .field("points.len()", &self.points.len())
+            .finish()
+    }
+}
+
+impl<S: Default> Default for MeshroomSource<S> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            cameras: Default::default(),
+            images: Default::default(),
+            images_file: Default::default(),
+            points: Default::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn open() {
+        use super::*;
+
+        let source =
+            MeshroomSource::open("examples/data/meshroom/0/cameras.sfm").unwrap();
+
+        let target = true;
+        let output = source.images.values().all(|image| {
+            source
+                .images_file
+                .keys()
+                .any(|path| image.matches_file_path(path))
+        });
+        assert_eq!(output, target);
+
+        let target = (2, 2, 2, 1);
+        let output = ColmapSource::from(source);
+        let output = (
+            output.cameras.len(),
+            output.images.len(),
+            output.images_file.len(),
+            output.points.len(),
+        );
+        assert_eq!(output, target);
+    }
+
+    #[test]
+    fn open_on_missing_files() {
+        use super::*;
+
+        let target = true;
+        let output = matches!(
+            MeshroomSource::open("examples/data/meshroom/0/cameras.abc").unwrap_err(),
+            Error::MissingFile(path) if path.ends_with("cameras.abc")
+        );
+        assert_eq!(output, target);
+    }
+}
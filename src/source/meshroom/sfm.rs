@@ -0,0 +1,572 @@
+//! Meshroom scene module.
+
+pub use super::*;
+
+use serde::{Deserialize, Deserializer};
+use std::{
+    collections::HashMap,
+    fmt,
+    io::{BufReader, Read},
+    str::FromStr,
+};
+
+/// The first version with the principal point relative to the image center.
+pub const VERSION_PRINCIPAL_POINT_OFFSET: [u32; 3] = [1, 2, 0];
+
+/// Meshroom scene in `cameras.sfm` or `sfm.json`.
+///
+/// The numbers are quoted in the file.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Sfm {
+    /// The version, e.g., `["1", "2", "0"]`.
+    #[serde(default)]
+    pub version: Vec<Quoted<u32>>,
+    /// Views.
+    #[serde(default)]
+    pub views: Vec<SfmView>,
+    /// Intrinsics.
+    #[serde(default)]
+    pub intrinsics: Vec<SfmIntrinsic>,
+    /// Poses.
+    #[serde(default)]
+    pub poses: Vec<SfmPose>,
+    /// Landmarks.
+    #[serde(default)]
+    pub structure: Vec<SfmLandmark>,
+}
+
+/// A view in [`Sfm`].
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SfmView {
+    /// View ID.
+    pub view_id: Quoted<u32>,
+    /// Pose ID.
+    pub pose_id: Quoted<u32>,
+    /// Intrinsic ID.
+    pub intrinsic_id: Quoted<u32>,
+    /// Image file path.
+    pub path: String,
+}
+
+/// An intrinsic in [`Sfm`].
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SfmIntrinsic {
+    /// Intrinsic ID.
+    pub intrinsic_id: Quoted<u32>,
+    /// Image width.
+    pub width: Quoted<u64>,
+    /// Image height.
+    pub height: Quoted<u64>,
+    /// Intrinsic type, e.g., `radial3`.
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// Sensor width in millimeters.
+    #[serde(default)]
+    pub sensor_width: Option<Quoted<f64>>,
+    /// Focal length in millimeters.
+    #[serde(default)]
+    pub focal_length: Option<Quoted<f64>>,
+    /// The ratio of the focal length x to the focal length y.
+    #[serde(default)]
+    pub pixel_ratio: Option<Quoted<f64>>,
+    /// Focal length in pixels of the older versions.
+    ///
+    /// It is either one value or `[x, y]`.
+    #[serde(default, deserialize_with = "deserialize_px_focal_length")]
+    pub px_focal_length: Option<[Quoted<f64>; 2]>,
+    /// Principal point.
+    ///
+    /// It is relative to the image center since
+    /// [version 1.2.0](VERSION_PRINCIPAL_POINT_OFFSET).
+    pub principal_point: [Quoted<f64>; 2],
+    /// Distortion coefficients.
+    ///
+    /// It is written as an empty string if there is no coefficient.
+    #[serde(default, deserialize_with = "deserialize_distortion_params")]
+    pub distortion_params: Vec<Quoted<f64>>,
+}
+
+/// A pose in [`Sfm`].
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SfmPose {
+    /// Pose ID.
+    pub pose_id: Quoted<u32>,
+    /// Pose.
+    pub pose: SfmPoseInner,
+}
+
+/// The inner pose of [`SfmPose`].
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+pub struct SfmPoseInner {
+    /// Transform.
+    pub transform: SfmTransform,
+}
+
+/// A transform in [`SfmPose`].
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+pub struct SfmTransform {
+    /// The 3D rotation from world space to view space.
+    ///
+    /// It is in **column-major order**, i.e., `M[col][row]`.
+    pub rotation: [Quoted<f64>; 9],
+    /// The 3D position in world space.
+    pub center: [Quoted<f64>; 3],
+}
+
+/// A landmark in [`Sfm`].
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SfmLandmark {
+    /// Landmark ID.
+    pub landmark_id: Quoted<u64>,
+    /// Color in RGB.
+    #[serde(default)]
+    pub color: [Quoted<u8>; 3],
+    /// Position in world space.
+    #[serde(rename = "X")]
+    pub position: [Quoted<f64>; 3],
+    /// Observations.
+    #[serde(default)]
+    pub observations: Vec<SfmObservation>,
+}
+
+/// An observation in [`SfmLandmark`].
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SfmObservation {
+    /// View ID.
+    pub observation_id: Quoted<u32>,
+    /// Position in image space.
+    #[serde(rename = "x")]
+    pub position: [Quoted<f64>; 2],
+}
+
+/// A value quoted as a string or not.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Quoted<T>(pub T);
+
+impl SfmIntrinsic {
+    /// Return the COLMAP camera of the intrinsic type.
+    ///
+    /// The intrinsic type is mapped as follows:
+    /// - `pinhole` to [`CameraVariant::Pinhole`].
+    /// - `radial1` to [`CameraVariant::SimpleRadial`].
+    /// - `radial3` and `brown` to [`CameraVariant::FullOpenCv`].
+    /// - `fisheye4` to [`CameraVariant::OpenCvFisheye`].
+    ///
+    /// # Errors
+    ///
+    /// It returns [`Error::UnsupportedKind`] for other intrinsic types,
+    /// e.g., `equidistant_r3`.
+    ///
+    /// It returns [`Error::MissingSymbol`] if the focal length
+    /// or the distortion coefficients are absent.
+    pub fn camera(
+        &self,
+        version: &[u32],
+    ) -> Result<Camera, Error> {
+        use CameraVariant::*;
+
+        let width = self.width.0;
+        let height = self.height.0;
+        let [focal_length_x, focal_length_y] =
+            match (self.px_focal_length, self.focal_length, self.sensor_width) {
+                (Some([x, y]), ..) => [x.0, y.0],
+                (None, Some(focal_length), Some(sensor_width)) => {
+                    let focal_length_x = focal_length.0 * width as f64 / sensor_width.0;
+                    let pixel_ratio =
+                        self.pixel_ratio.map(|ratio| ratio.0).unwrap_or(1.0);
+                    [focal_length_x, focal_length_x / pixel_ratio]
+                },
+                _ => return Err(Error::MissingSymbol("focalLength".into())),
+            };
+        let coefficients = |count: usize| {
+            self.distortion_params
+                .get(..count)
+                .map(|params| params.iter().map(|param| param.0).collect::<Vec<_>>())
+                .ok_or_else(|| Error::MissingSymbol("distortionParams".into()))
+        };
+
+        let variant = match self.kind.as_str() {
+            "pinhole" => Pinhole {
+                focal_length_x,
+                focal_length_y,
+            },
+            "radial1" => SimpleRadial {
+                focal_length: focal_length_x,
+                k: coefficients(1)?[0],
+            },
+            "radial3" | "brown" => {
+                let count = if self.kind == "brown" { 5 } else { 3 };
+                let mut params = coefficients(count)?;
+                params.resize(5, 0.0);
+                FullOpenCv {
+                    focal_length_x,
+                    focal_length_y,
+                    k1: params[0],
+                    k2: params[1],
+                    p1: params[3],
+                    p2: params[4],
+                    k3: params[2],
+                    k4: 0.0,
+                    k5: 0.0,
+                    k6: 0.0,
+                }
+            },
+            "fisheye4" => {
+                let params = coefficients(4)?;
+                OpenCvFisheye {
+                    focal_length_x,
+                    focal_length_y,
+                    k1: params[0],
+                    k2: params[1],
+                    k3: params[2],
+                    k4: params[3],
+                }
+            },
+            kind => return Err(Error::UnsupportedKind(kind.into())),
+        };
+
+        let [principal_point_x, principal_point_y] = self.principal_point.map(|v| v.0);
+        let [principal_point_x, principal_point_y] =
+            if version >= &VERSION_PRINCIPAL_POINT_OFFSET[..] {
+                [
+                    principal_point_x + width as f64 / 2.0,
+                    principal_point_y + height as f64 / 2.0,
+                ]
+            } else {
+                [principal_point_x, principal_point_y]
+            };
+
+        Ok(Camera {
+            camera_id: self.intrinsic_id.0,
+            width,
+            height,
+            principal_point_x,
+            principal_point_y,
+            variant,
+        })
+    }
+}
+
+impl Sfm {
+    /// Return the COLMAP cameras.
+    ///
+    /// The [camera ID](Camera::camera_id) is the intrinsic ID.
+    pub fn cameras(&self) -> Result<Cameras, Error> {
+        let version = self.version.iter().map(|v| v.0).collect::<Vec<_>>();
+        self.intrinsics
+            .iter()
+            .map(|intrinsic| {
+                let camera = intrinsic.camera(&version)?;
+                Ok((camera.camera_id, camera))
+            })
+            .collect()
+    }
+
+    /// Return the COLMAP images and points.
+    ///
+    /// The [image ID](Image::image_id) is the view ID,
+    /// and the [point ID](Point::point_id) is the landmark ID.
+    /// The views without poses are skipped.
+    pub fn images_and_points(&self) -> Result<(Images, Points), Error> {
+        let poses = self
+            .poses
+            .iter()
+            .map(|pose| (pose.pose_id.0, &pose.pose.transform))
+            .collect::<HashMap<_, _>>();
+
+        let mut images = self
+            .views
+            .iter()
+            .filter_map(|view| {
+                let transform = poses.get(&view.pose_id.0)?;
+                Some(view.file_name().map(|file_name| {
+                    let r = transform.rotation.map(|v| v.0);
+                    let rotation =
+                        [[r[0], r[1], r[2]], [r[3], r[4], r[5]], [r[6], r[7], r[8]]];
+                    let mut image = Image {
+                        image_id: view.view_id.0,
+                        camera_id: view.intrinsic_id.0,
+                        file_name,
+                        points: Some(vec![]),
+                        ..Default::default()
+                    };
+                    image
+                        .set_rotation(&rotation)
+                        .set_position(&transform.center.map(|v| v.0), &rotation);
+                    (image.image_id, image)
+                }))
+            })
+            .collect::<Result<Images, Error>>()?;
+
+        let points = self
+            .structure
+            .iter()
+            .map(|landmark| {
+                let point_id = landmark.landmark_id.0;
+                let track = landmark
+                    .observations
+                    .iter()
+                    .filter_map(|observation| {
+                        let image_id = observation.observation_id.0;
+                        let points = images.get_mut(&image_id)?.points.as_mut()?;
+                        points.push(ImagePoint {
+                            position: observation.position.map(|v| v.0),
+                            point_id,
+                        });
+                        Some(TrackElement {
+                            image_id,
+                            point_index: points.len() as u32 - 1,
+                        })
+                    })
+                    .collect();
+                let point = Point {
+                    point_id,
+                    position: landmark.position.map(|v| v.0),
+                    color_rgb: landmark.color.map(|v| v.0),
+                    // NOTE: Meshroom exports no re-projection error.
+                    error: -1.0,
+                    track,
+                };
+                (point_id, point)
+            })
+            .collect();
+
+        Ok((images, points))
+    }
+}
+
+impl SfmView {
+    /// Return the image file name of the [path](Self::path).
+    pub fn file_name(&self) -> Result<CString, Error> {
+        let file_name = Path::new(&self.path)
+            .file_name()
+            .and_then(|file_name| file_name.to_str())
+            .ok_or_else(|| Error::InvalidUtf8(self.path.to_owned()))?;
+        CString::new(file_name)
+            .map_err(|err| Error::InvalidUtf8(format!("{:?}", err.into_vec())))
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Quoted<T>
+where
+    T: Deserialize<'de> + FromStr,
+    T::Err: fmt::Display,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Value<T> {
+            Quoted(String),
+            Unquoted(T),
+        }
+
+        match Value::deserialize(deserializer)? {
+            Value::Quoted(value) => value
+                .trim()
+                .parse()
+                .map(Self)
+                .map_err(serde::de::Error::custom),
+            Value::Unquoted(value) => Ok(Self(value)),
+        }
+    }
+}
+
+/// Deserializing [`SfmIntrinsic::px_focal_length`] from one value or `[x, y]`.
+fn deserialize_px_focal_length<'de, D: Deserializer<'de>>(
+    deserializer: D
+) -> Result<Option<[Quoted<f64>; 2]>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Value {
+        One(Quoted<f64>),
+        Two([Quoted<f64>; 2]),
+    }
+
+    Ok(
+        Option::<Value>::deserialize(deserializer)?.map(|value| match value {
+            Value::One(value) => [value; 2],
+            Value::Two(value) => value,
+        }),
+    )
+}
+
+/// Deserializing [`SfmIntrinsic::distortion_params`] from a sequence or an empty string.
+fn deserialize_distortion_params<'de, D: Deserializer<'de>>(
+    deserializer: D
+) -> Result<Vec<Quoted<f64>>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Value {
+        Empty(String),
+        Many(Vec<Quoted<f64>>),
+    }
+
+    match Value::deserialize(deserializer)? {
+        Value::Empty(value) if value.trim().is_empty() => Ok(vec![]),
+        Value::Empty(value) => Err(serde::de::Error::custom(format!(
+            "invalid distortion parameters: {value:?}"
+        ))),
+        Value::Many(value) => Ok(value),
+    }
+}
+
+impl Decoder for Sfm {
+    type Err = Error;
+
+    #[inline]
+    fn decode(reader: &mut impl Read) -> Result<Self, Self::Err> {
+        let sfm = serde_json::from_reader(BufReader::new(reader))?;
+
+        #[cfg(all(debug_assertions, not(test)))]
+        log::debug!(target: "gausplat-loader::meshroom::sfm", "Sfm::decode");
+
+        Ok(sfm)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn cameras() {
+        use super::*;
+
+        let source =
+            &mut std::fs::File::open("examples/data/meshroom/0/cameras.sfm").unwrap();
+        let sfm = Sfm::decode(source).unwrap();
+
+        let target = vec![
+            Camera {
+                camera_id: 200,
+                width: 8,
+                height: 8,
+                principal_point_x: 4.5,
+                principal_point_y: 3.5,
+                variant: CameraVariant::FullOpenCv {
+                    focal_length_x: 10.0,
+                    focal_length_y: 10.0,
+                    k1: 0.1,
+                    k2: 0.01,
+                    p1: 0.0,
+                    p2: 0.0,
+                    k3: 0.001,
+                    k4: 0.0,
+                    k5: 0.0,
+                    k6: 0.0,
+                },
+            },
+            Camera {
+                camera_id: 201,
+                width: 8,
+                height: 8,
+                principal_point_x: 4.0,
+                principal_point_y: 4.0,
+                variant: CameraVariant::Pinhole {
+                    focal_length_x: 12.0,
+                    focal_length_y: 6.0,
+                },
+            },
+        ];
+        let output = sfm.cameras().unwrap().into_values().collect::<Vec<_>>();
+        assert_eq!(output, target);
+    }
+
+    #[test]
+    fn cameras_on_older_version() {
+        use super::*;
+
+        let source = r#"{
+            "version": ["1", "0", "0"],
+            "intrinsics": [{
+                "intrinsicId": "1", "width": "8", "height": "8", "type": "radial1",
+                "pxFocalLength": "10", "principalPoint": ["4", "5"],
+                "distortionParams": ["0.5"]
+            }]
+        }"#;
+        let sfm = Sfm::decode(&mut source.as_bytes()).unwrap();
+
+        let target = Camera {
+            camera_id: 1,
+            width: 8,
+            height: 8,
+            principal_point_x: 4.0,
+            principal_point_y: 5.0,
+            variant: CameraVariant::SimpleRadial {
+                focal_length: 10.0,
+                k: 0.5,
+            },
+        };
+        let output = sfm.cameras().unwrap()[&1];
+        assert_eq!(output, target);
+    }
+
+    #[test]
+    fn cameras_on_unsupported_kind() {
+        use super::*;
+
+        let intrinsic = SfmIntrinsic {
+            kind: "equidistant_r3".into(),
+            px_focal_length: Some([Quoted(1.0); 2]),
+            ..Default::default()
+        };
+
+        let target = true;
+        let output = matches!(
+            intrinsic.camera(&[1, 2, 0]).unwrap_err(),
+            Error::UnsupportedKind(kind) if kind == "equidistant_r3"
+        );
+        assert_eq!(output, target);
+    }
+
+    #[test]
+    fn images_and_points() {
+        use super::*;
+
+        let source =
+            &mut std::fs::File::open("examples/data/meshroom/0/cameras.sfm").unwrap();
+        let sfm = Sfm::decode(source).unwrap();
+        let (images, points) = sfm.images_and_points().unwrap();
+
+        let target = vec![100, 102];
+        let output = images.keys().copied().collect::<Vec<_>>();
+        assert_eq!(output, target);
+
+        let target = (c"001.png", [0.0, 0.0, 5.0], [0.0, 0.0, 5.0]);
+        let image = &images[&100];
+        let output = (
+            image.file_name.as_c_str(),
+            image.translation,
+            image.position(&image.rotation()),
+        );
+        assert_eq!(output, target);
+
+        // NOTE: The rotation is column-major in both.
+        let target = [[0.0, 1.0, 0.0], [-1.0, 0.0, 0.0], [0.0, 0.0, 1.0]];
+        let output = images[&102].rotation().map(|col| col.map(f64::round));
+        assert_eq!(output, target);
+
+        let target = Point {
+            point_id: 7,
+            position: [1.0, 2.0, 3.0],
+            color_rgb: [255, 128, 0],
+            error: -1.0,
+            track: vec![
+                TrackElement {
+                    image_id: 100,
+                    point_index: 0,
+                },
+                TrackElement {
+                    image_id: 102,
+                    point_index: 0,
+                },
+            ],
+        };
+        let output = points[&7].to_owned();
+        assert_eq!(output, target);
+    }
+}
@@ -0,0 +1,632 @@
+//! Metashape document module.
+
+pub use super::*;
+
+use roxmltree::Node;
+use std::io::{BufReader, Read};
+
+/// Metashape document in `cameras.xml`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Document {
+    /// Chunks.
+    pub chunks: Vec<Chunk>,
+}
+
+/// A chunk in [`Document`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Chunk {
+    /// Sensors.
+    pub sensors: Vec<Sensor>,
+    /// Cameras, which are the views of sensors.
+    pub views: Vec<View>,
+    /// The transformation from chunk space to world space.
+    pub transform: ChunkTransform,
+}
+
+/// A sensor in [`Chunk`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Sensor {
+    /// Sensor ID.
+    pub sensor_id: u32,
+    /// Sensor type, e.g., `frame` or `fisheye`.
+    pub kind: String,
+    /// Image width.
+    pub width: u64,
+    /// Image height.
+    pub height: u64,
+    /// Calibration.
+    pub calibration: Calibration,
+}
+
+/// A calibration of [`Sensor`].
+///
+/// The absent values are zero.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Calibration {
+    /// Focal length.
+    pub f: f64,
+    /// Principal point x value relative to the image center.
+    pub cx: f64,
+    /// Principal point y value relative to the image center.
+    pub cy: f64,
+    /// Radial distortion coefficient 1.
+    pub k1: f64,
+    /// Radial distortion coefficient 2.
+    pub k2: f64,
+    /// Radial distortion coefficient 3.
+    pub k3: f64,
+    /// Radial distortion coefficient 4.
+    pub k4: f64,
+    /// Tangential distortion coefficient 1.
+    pub p1: f64,
+    /// Tangential distortion coefficient 2.
+    pub p2: f64,
+    /// Affinity coefficient.
+    pub b1: f64,
+    /// Skew coefficient.
+    pub b2: f64,
+}
+
+/// A camera in [`Chunk`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct View {
+    /// Camera ID.
+    pub camera_id: u32,
+    /// [Sensor ID](Sensor::sensor_id).
+    pub sensor_id: u32,
+    /// Label, which is generally the image file stem.
+    pub label: String,
+    /// The transformation from view space to chunk space.
+    ///
+    /// It is in **row-major order**, i.e., `M[row][col]`.
+    /// It is `None` if the camera is not aligned.
+    pub transform: Option<[[f64; 4]; 4]>,
+}
+
+/// The transformation of [`Chunk`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ChunkTransform {
+    /// Rotation.
+    ///
+    /// It is in **row-major order**, i.e., `M[row][col]`.
+    pub rotation: [[f64; 3]; 3],
+    /// Translation.
+    pub translation: [f64; 3],
+    /// Scale.
+    pub scale: f64,
+}
+
+impl Calibration {
+    /// Return the camera variant of the sensor type.
+    ///
+    /// The sensor type is mapped as follows:
+    /// - `frame` to [`CameraVariant::Pinhole`] if there is no distortion,
+    ///   [`CameraVariant::OpenCv`] if `k3` is zero,
+    ///   or [`CameraVariant::FullOpenCv`] otherwise.
+    /// - `fisheye` to [`CameraVariant::OpenCvFisheye`].
+    ///
+    /// `p1` and `p2` are swapped to match OpenCV.
+    /// `b1` is added to the focal length x value.
+    ///
+    /// # Errors
+    ///
+    /// It returns [`Error::UnsupportedKind`] for other sensor types,
+    /// e.g., `spherical`, or if a coefficient without a COLMAP term is non-zero,
+    /// i.e., `b2`, the `k4` of `frame` sensors,
+    /// or the `p1` and `p2` of `fisheye` sensors.
+    pub fn variant(
+        &self,
+        kind: &str,
+    ) -> Result<CameraVariant, Error> {
+        use CameraVariant::*;
+
+        let focal_length_x = self.f + self.b1;
+        let focal_length_y = self.f;
+        let Self {
+            k1,
+            k2,
+            k3,
+            k4,
+            p1,
+            p2,
+            b2,
+            ..
+        } = *self;
+
+        // NOTE: The coefficients without a COLMAP term must be zero.
+        let unsupported = [
+            ("b2", b2, ["frame", "fisheye"].as_slice()),
+            ("k4", k4, ["frame"].as_slice()),
+            ("p1", p1, ["fisheye"].as_slice()),
+            ("p2", p2, ["fisheye"].as_slice()),
+        ]
+        .into_iter()
+        .find(|(_, value, kinds)| *value != 0.0 && kinds.contains(&kind));
+        if let Some((name, ..)) = unsupported {
+            return Err(Error::UnsupportedKind(format!("{kind}: {name}")));
+        }
+
+        Ok(match kind {
+            "frame" if [k1, k2, k3, p1, p2].iter().all(|k| *k == 0.0) => Pinhole {
+                focal_length_x,
+                focal_length_y,
+            },
+            "frame" if k3 == 0.0 => OpenCv {
+                focal_length_x,
+                focal_length_y,
+                k1,
+                k2,
+                p1: p2,
+                p2: p1,
+            },
+            "frame" => FullOpenCv {
+                focal_length_x,
+                focal_length_y,
+                k1,
+                k2,
+                p1: p2,
+                p2: p1,
+                k3,
+                k4: 0.0,
+                k5: 0.0,
+                k6: 0.0,
+            },
+            "fisheye" => OpenCvFisheye {
+                focal_length_x,
+                focal_length_y,
+                k1,
+                k2,
+                k3,
+                k4,
+            },
+            _ => return Err(Error::UnsupportedKind(kind.into())),
+        })
+    }
+}
+
+impl Chunk {
+    /// Return the COLMAP cameras.
+    ///
+    /// The [camera ID](Camera::camera_id) is the sensor ID.
+    pub fn cameras(&self) -> Result<Cameras, Error> {
+        self.sensors
+            .iter()
+            .map(|sensor| {
+                let camera = Camera {
+                    camera_id: sensor.sensor_id,
+                    width: sensor.width,
+                    height: sensor.height,
+                    principal_point_x: sensor.width as f64 / 2.0 + sensor.calibration.cx,
+                    principal_point_y: sensor.height as f64 / 2.0 + sensor.calibration.cy,
+                    variant: sensor.calibration.variant(&sensor.kind)?,
+                };
+                Ok((camera.camera_id, camera))
+            })
+            .collect()
+    }
+
+    /// Return the COLMAP images in world space.
+    ///
+    /// The [image ID](Image::image_id) is the camera ID,
+    /// and the [file name](Image::file_name) is the label.
+    /// The cameras not aligned are skipped.
+    pub fn images(&self) -> Result<Images, Error> {
+        self.views
+            .iter()
+            .filter_map(|view| {
+                let transform = view.transform.as_ref()?;
+                let file_name = CString::new(view.label.as_str())
+                    .map_err(|err| Error::InvalidUtf8(format!("{:?}", err.into_vec())));
+                Some(file_name.map(|file_name| {
+                    let (rotation, position) = self.transform.apply(transform);
+                    let mut image = Image {
+                        image_id: view.camera_id,
+                        camera_id: view.sensor_id,
+                        file_name,
+                        ..Default::default()
+                    };
+                    // NOTE: The rows of the camera-to-world rotation are
+                    // the columns of the world-to-view rotation.
+                    image
+                        .set_rotation(&rotation)
+                        .set_position(&position, &rotation);
+                    (image.image_id, image)
+                }))
+            })
+            .collect()
+    }
+}
+
+impl ChunkTransform {
+    /// Apply the transformation to the view-to-chunk transformation.
+    ///
+    /// It returns the view-to-world rotation in **row-major order**
+    /// and the position in world space.
+    pub fn apply(
+        &self,
+        transform: &[[f64; 4]; 4],
+    ) -> ([[f64; 3]; 3], [f64; 3]) {
+        let r = &self.rotation;
+        let m = transform;
+        let rotation =
+            [0, 1, 2].map(|i| [0, 1, 2].map(|j| (0..3).map(|k| r[i][k] * m[k][j]).sum()));
+        let position = [0, 1, 2].map(|i| {
+            (0..3).map(|k| r[i][k] * m[k][3]).sum::<f64>() * self.scale
+                + self.translation[i]
+        });
+        (rotation, position)
+    }
+}
+
+impl Default for ChunkTransform {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            rotation: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+            translation: [0.0; 3],
+            scale: 1.0,
+        }
+    }
+}
+
+/// Finding the first child element with the tag name.
+#[inline]
+fn child<'a, 'i>(
+    node: Node<'a, 'i>,
+    name: &str,
+) -> Option<Node<'a, 'i>> {
+    node.children().find(|child| child.has_tag_name(name))
+}
+
+/// Parsing the attribute.
+///
+/// It returns [`Error::MissingSymbol`] with the `name` if the attribute is absent.
+#[inline]
+fn parse_attribute<T: std::str::FromStr>(
+    node: Node,
+    name: &str,
+) -> Result<T, Error>
+where
+    Error: From<T::Err>,
+{
+    Ok(node
+        .attribute(name)
+        .ok_or_else(|| Error::MissingSymbol(name.into()))?
+        .parse()?)
+}
+
+/// Parsing the whitespace-separated values in the text.
+#[inline]
+fn parse_values<const N: usize>(node: Node) -> Result<[f64; N], Error> {
+    let mut values = [0.0; N];
+    let tokens = &mut node.text().unwrap_or_default().split_whitespace();
+    for value in values.iter_mut() {
+        *value = tokens
+            .next()
+            .ok_or_else(|| Error::MissingSymbol(node.tag_name().name().into()))?
+            .parse()?;
+    }
+    Ok(values)
+}
+
+impl Decoder for Document {
+    type Err = Error;
+
+    fn decode(reader: &mut impl Read) -> Result<Self, Self::Err> {
+        let mut text = String::new();
+        BufReader::new(reader).read_to_string(&mut text)?;
+        let document = roxmltree::Document::parse(&text)?;
+
+        let chunks = document
+            .root_element()
+            .descendants()
+            .filter(|node| node.has_tag_name("chunk"))
+            .map(|chunk| {
+                let sensors = child(chunk, "sensors")
+                    .into_iter()
+                    .flat_map(|sensors| sensors.children())
+                    .filter(|node| node.has_tag_name("sensor"))
+                    .map(|sensor| {
+                        let resolution = child(sensor, "resolution")
+                            .ok_or_else(|| Error::MissingSymbol("resolution".into()))?;
+                        // NOTE: The adjusted calibration takes precedence.
+                        let calibrations = sensor
+                            .children()
+                            .filter(|node| node.has_tag_name("calibration"))
+                            .collect::<Vec<_>>();
+                        let calibration = calibrations
+                            .iter()
+                            .find(|node| node.attribute("class") == Some("adjusted"))
+                            .or(calibrations.first())
+                            .map(|calibration| {
+                                let value = |name| {
+                                    child(*calibration, name)
+                                        .map(|node| {
+                                            Ok::<_, Error>(parse_values::<1>(node)?[0])
+                                        })
+                                        .unwrap_or(Ok(0.0))
+                                };
+                                Ok::<_, Error>(Calibration {
+                                    f: value("f")?,
+                                    cx: value("cx")?,
+                                    cy: value("cy")?,
+                                    k1: value("k1")?,
+                                    k2: value("k2")?,
+                                    k3: value("k3")?,
+                                    k4: value("k4")?,
+                                    p1: value("p1")?,
+                                    p2: value("p2")?,
+                                    b1: value("b1")?,
+                                    b2: value("b2")?,
+                                })
+                            })
+                            .ok_or_else(|| {
+                                Error::MissingSymbol("calibration".into())
+                            })??;
+
+                        Ok(Sensor {
+                            sensor_id: parse_attribute(sensor, "id")?,
+                            kind: sensor.attribute("type").unwrap_or("frame").into(),
+                            width: parse_attribute(resolution, "width")?,
+                            height: parse_attribute(resolution, "height")?,
+                            calibration,
+                        })
+                    })
+                    .collect::<Result<_, Error>>()?;
+
+                // NOTE: The cameras may be nested in groups.
+                let views = child(chunk, "cameras")
+                    .into_iter()
+                    .flat_map(|cameras| cameras.descendants())
+                    .filter(|node| node.has_tag_name("camera"))
+                    .map(|camera| {
+                        let transform = child(camera, "transform")
+                            .map(|transform| {
+                                let m = parse_values::<16>(transform)?;
+                                Ok::<_, Error>([0, 1, 2, 3].map(|r| {
+                                    [m[r * 4], m[r * 4 + 1], m[r * 4 + 2], m[r * 4 + 3]]
+                                }))
+                            })
+                            .transpose()?;
+                        Ok(View {
+                            camera_id: parse_attribute(camera, "id")?,
+                            sensor_id: parse_attribute(camera, "sensor_id")?,
+                            label: camera.attribute("label").unwrap_or_default().into(),
+                            transform,
+                        })
+                    })
+                    .collect::<Result<_, Error>>()?;
+
+                let transform = child(chunk, "transform")
+                    .map(|transform| {
+                        let mut chunk_transform = ChunkTransform::default();
+                        if let Some(rotation) = child(transform, "rotation") {
+                            let r = parse_values::<9>(rotation)?;
+                            chunk_transform.rotation =
+                                [0, 1, 2].map(|i| [r[i * 3], r[i * 3 + 1], r[i * 3 + 2]]);
+                        }
+                        if let Some(translation) = child(transform, "translation") {
+                            chunk_transform.translation = parse_values(translation)?;
+                        }
+                        if let Some(scale) = child(transform, "scale") {
+                            chunk_transform.scale = parse_values::<1>(scale)?[0];
+                        }
+                        Ok::<_, Error>(chunk_transform)
+                    })
+                    .transpose()?
+                    .unwrap_or_default();
+
+                Ok(Chunk {
+                    sensors,
+                    views,
+                    transform,
+                })
+            })
+            .collect::<Result<_, Error>>()?;
+
+        #[cfg(all(debug_assertions, not(test)))]
+        log::debug!(target: "gausplat-loader::metashape::document", "Document::decode");
+
+        Ok(Self { chunks })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn cameras() {
+        use super::*;
+
+        let source =
+            &mut std::fs::File::open("examples/data/metashape/0/cameras.xml").unwrap();
+        let document = Document::decode(source).unwrap();
+
+        let target = vec![
+            Camera {
+                camera_id: 0,
+                width: 8,
+                height: 8,
+                principal_point_x: 4.5,
+                principal_point_y: 3.5,
+                variant: CameraVariant::OpenCv {
+                    focal_length_x: 11.0,
+                    focal_length_y: 10.0,
+                    k1: 0.1,
+                    k2: 0.01,
+                    p1: 0.002,
+                    p2: 0.001,
+                },
+            },
+            Camera {
+                camera_id: 1,
+                width: 8,
+                height: 8,
+                principal_point_x: 4.0,
+                principal_point_y: 4.0,
+                variant: CameraVariant::OpenCvFisheye {
+                    focal_length_x: 6.0,
+                    focal_length_y: 6.0,
+                    k1: 0.1,
+                    k2: 0.2,
+                    k3: 0.3,
+                    k4: 0.4,
+                },
+            },
+        ];
+        let output = document.chunks[0]
+            .cameras()
+            .unwrap()
+            .into_values()
+            .collect::<Vec<_>>();
+        assert_eq!(output, target);
+    }
+
+    #[test]
+    fn images() {
+        use super::*;
+
+        let source =
+            &mut std::fs::File::open("examples/data/metashape/0/cameras.xml").unwrap();
+        let document = Document::decode(source).unwrap();
+        let images = document.chunks[0].images().unwrap();
+
+        let target = vec![(0, 0, c"001"), (2, 1, c"003.png")];
+        let output = images
+            .values()
+            .map(|image| (image.image_id, image.camera_id, image.file_name.as_c_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(output, target);
+
+        // NOTE: The chunk transform rotates around the z axis by 90 degrees,
+        // scales by 2 and translates by 1 along the x axis.
+        let target = (
+            [1.0, 2.0, 0.0],
+            [[0.0, -1.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, 1.0]],
+        );
+        let image = &images[&0];
+        let output = (
+            image.position(&image.rotation()).map(f64::round),
+            image.rotation().map(|col| col.map(f64::round)),
+        );
+        assert_eq!(output, target);
+    }
+
+    #[test]
+    fn variant() {
+        use super::*;
+
+        let calibration = Calibration {
+            f: 10.0,
+            k3: 0.3,
+            ..Default::default()
+        };
+
+        let target = CameraVariant::FullOpenCv {
+            focal_length_x: 10.0,
+            focal_length_y: 10.0,
+            k1: 0.0,
+            k2: 0.0,
+            p1: 0.0,
+            p2: 0.0,
+            k3: 0.3,
+            k4: 0.0,
+            k5: 0.0,
+            k6: 0.0,
+        };
+        let output = calibration.variant("frame").unwrap();
+        assert_eq!(output, target);
+
+        let target = CameraVariant::Pinhole {
+            focal_length_x: 10.0,
+            focal_length_y: 10.0,
+        };
+        let output = Calibration {
+            f: 10.0,
+            ..Default::default()
+        }
+        .variant("frame")
+        .unwrap();
+        assert_eq!(output, target);
+
+        let target = true;
+        let output = matches!(
+            calibration.variant("spherical").unwrap_err(),
+            Error::UnsupportedKind(kind) if kind == "spherical"
+        );
+        assert_eq!(output, target);
+    }
+
+    #[test]
+    fn variant_on_unsupported_coefficients() {
+        use super::*;
+
+        [
+            (
+                "frame",
+                "k4",
+                Calibration {
+                    k4: 0.4,
+                    ..Default::default()
+                },
+            ),
+            (
+                "frame",
+                "b2",
+                Calibration {
+                    b2: 0.1,
+                    ..Default::default()
+                },
+            ),
+            (
+                "fisheye",
+                "b2",
+                Calibration {
+                    b2: 0.1,
+                    ..Default::default()
+                },
+            ),
+            (
+                "fisheye",
+                "p1",
+                Calibration {
+                    p1: 0.001,
+                    ..Default::default()
+                },
+            ),
+            (
+                "fisheye",
+                "p2",
+                Calibration {
+                    p2: 0.002,
+                    ..Default::default()
+                },
+            ),
+        ]
+        .into_iter()
+        .for_each(|(kind, name, calibration)| {
+            let calibration = Calibration {
+                f: 10.0,
+                ..calibration
+            };
+
+            let target = true;
+            let output = matches!(
+                calibration.variant(kind).unwrap_err(),
+                Error::UnsupportedKind(output) if output == format!("{kind}: {name}")
+            );
+            assert_eq!(output, target);
+        });
+    }
+
+    #[test]
+    fn decode_on_invalid_xml() {
+        use super::*;
+
+        let target = true;
+        let output = matches!(
+            Document::decode(&mut &b"<document><chunk></document>"[..]).unwrap_err(),
+            Error::Xml(_)
+        );
+        assert_eq!(output, target);
+    }
+}
@@ -0,0 +1,197 @@
+//! Metashape source.
+//!
+//! It supports the camera XML format exported by Agisoft Metashape.
+//!
+//! For more information, see the Metashape
+//! [manual](https://www.agisoft.com/pdf/metashape-pro_2_1_en.pdf).
+
+pub mod document;
+
+pub use super::colmap::{Camera, CameraVariant, Cameras, ColmapSource, Image, Images};
+pub use super::file::*;
+pub use crate::function::Decoder;
+pub use document::*;
+
+use std::{
+    ffi::CString,
+    fmt, fs,
+    path::{Path, PathBuf},
+};
+
+/// Metashape source.
+///
+/// It is converted to the cameras and images of [`ColmapSource`].
+#[derive(Clone, PartialEq)]
+pub struct MetashapeSource<S> {
+    /// Cameras.
+    pub cameras: Cameras,
+    /// Images.
+    pub images: Images,
+    /// Images' file.
+    pub images_file: Files<S>,
+}
+
+impl MetashapeSource<fs::File> {
+    /// Resolve the image file path of the label in the `directory`.
+    ///
+    /// The extension `png`, `jpg`, `jpeg` or `tif` is appended if the label omits it.
+    fn resolve_file_path(
+        directory: &Path,
+        label: &str,
+    ) -> Result<PathBuf, Error> {
+        let path = directory.join(label);
+        if path.is_file() {
+            return Ok(path);
+        }
+        ["png", "jpg", "jpeg", "tif", "PNG", "JPG", "JPEG", "TIF"]
+            .into_iter()
+            .map(|extension| directory.join(format!("{label}.{extension}")))
+            .find(|path| path.is_file())
+            .ok_or(Error::MissingFile(path))
+    }
+}
+
+impl Opener for MetashapeSource<fs::File> {
+    /// Opening the Metashape scene from the camera XML file,
+    /// e.g., `cameras.xml`.
+    ///
+    /// It reads the first chunk.
+    /// The image files are in the `images` directory next to the XML file,
+    /// and they are named by the camera labels.
+    /// The cameras not aligned are skipped.
+    ///
+    /// The files are opened in read-only mode.
+    ///
+    /// # Errors
+    ///
+    /// It returns [`Error::MissingFile`] with the path of any missing file.
+    ///
+    /// It returns [`Error::MissingSymbol`] if there is no chunk.
+    fn open(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        if !path.is_file() {
+            return Err(Error::MissingFile(path.to_owned()));
+        }
+        let directory = path.parent().unwrap_or(Path::new(".")).join("images");
+        let document = Document::decode(&mut fs::File::open(path)?)?;
+        let chunk = document
+            .chunks
+            .first()
+            .ok_or_else(|| Error::MissingSymbol("chunk".into()))?;
+
+        let cameras = chunk.cameras()?;
+        let mut images = chunk.images()?;
+        let mut images_file = Files::default();
+
+        for image in images.values_mut() {
+            let label = image.file_name.to_str().unwrap_or_default();
+            let path = Self::resolve_file_path(&directory, label)?;
+            let file_name = path
+                .file_name()
+                .and_then(|file_name| file_name.to_str())
+                .ok_or_else(|| Error::InvalidUtf8(path.to_string_lossy().into()))?;
+            image.file_name = CString::new(file_name)
+                .map_err(|err| Error::InvalidUtf8(format!("{:?}", err.into_vec())))?;
+
+            let inner = fs::File::open(&path)?;
+            images_file.insert(path.to_owned(), File { inner, path });
+        }
+
+        #[cfg(all(debug_assertions, not(test)))]
+        log::debug!(target: "gausplat-loader::metashape", "MetashapeSource::open");
+
+        Ok(Self {
+            cameras,
+            images,
+            images_file,
+        })
+    }
+}
+
+impl<S> From<MetashapeSource<S>> for ColmapSource<S> {
+    /// The points are empty.
+    #[inline]
+    fn from(source: MetashapeSource<S>) -> Self {
+        Self {
+            cameras: source.cameras,
+            images: source.images,
+            images_file: source.images_file,
+            points: Default::default(),
+        }
+    }
+}
+
+impl<S> fmt::Debug for MetashapeSource<S> {
+    #[inline]
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        f.debug_struct("MetashapeSource")
+            .field("cameras.len()", &self.cameras.len())
+            .field("images.len()", &self.images.len())
+            .field("images_file.len()", &self.images_file.len())
+            .finish()
+    }
+}
+
+impl<S: Default> Default for MetashapeSource<S> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            cameras: Default::default(),
+            images: Default::default(),
+            images_file: Default::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn open() {
+        use super::*;
+
+        let source =
+            MetashapeSource::open("examples/data/metashape/0/cameras.xml").unwrap();
+
+        let target = [c"001.png", c"003.png"];
+        let output = source
+            .images
+            .values()
+            .map(|image| image.file_name.as_c_str())
+            .collect::<Vec<_>>();
+        assert_eq!(output, target);
+
+        let target = true;
+        let output = source.images.values().all(|image| {
+            source
+                .images_file
+                .keys()
+                .any(|path| image.matches_file_path(path))
+        });
+        assert_eq!(output, target);
+
+        let target = (2, 2, 2, 0);
+        let output = ColmapSource::from(source);
+        let output = (
+            output.cameras.len(),
+            output.images.len(),
+            output.images_file.len(),
+            output.points.len(),
+        );
+        assert_eq!(output, target);
+    }
+
+    #[test]
+    fn open_on_missing_files() {
+        use super::*;
+
+        let target = true;
+        let output = matches!(
+            MetashapeSource::open("examples/data/metashape/0/cameras.txt").unwrap_err(),
+            Error::MissingFile(path) if path.ends_with("cameras.txt")
+        );
+        assert_eq!(output, target);
+    }
+}
@@ -2,7 +2,14 @@
 //!
 //! It defines the loadable assets.
 
+pub mod bundler;
 pub mod colmap;
 pub mod file;
 pub mod image;
+pub mod llff;
+pub mod meshroom;
+pub mod metashape;
+pub mod nerf;
+pub mod npy;
+pub mod openmvg;
 pub mod polygon;
@@ -0,0 +1,367 @@
+//! NeRF source.
+//!
+//! It supports the `transforms.json` format of NeRF synthetic datasets,
+//! Instant-NGP and Nerfstudio.
+//!
+//! For more information, see the Instant-NGP
+//! [documentation](https://github.com/NVlabs/instant-ngp/blob/master/docs/nerf_dataset_tips.md)
+//! and the Nerfstudio
+//! [documentation](https://docs.nerf.studio/quickstart/data_conventions.html).
+
+pub mod transforms;
+
+pub use super::colmap::{Camera, CameraVariant, Cameras, ColmapSource, Image, Images};
+pub use super::file::*;
+pub use crate::{
+    collection::IndexMap,
+    function::{Decoder, Encoder},
+};
+pub use transforms::*;
+
+use std::{
+    ffi::CString,
+    fmt, fs,
+    io::{BufReader, BufWriter, Read, Write},
+    path::{Component, Path, PathBuf},
+};
+
+/// NeRF source.
+///
+/// It is converted to the cameras and images of [`ColmapSource`].
+#[derive(Clone, PartialEq)]
+pub struct NerfSource<S> {
+    /// Cameras.
+    pub cameras: Cameras,
+    /// Images.
+    pub images: Images,
+    /// Images' file.
+    pub images_file: Files<S>,
+    /// Masks' file mapped by [image ID](Image::image_id).
+    pub masks_file: IndexMap<u32, File<S>>,
+    /// Depths' file mapped by [image ID](Image::image_id).
+    pub depths_file: IndexMap<u32, File<S>>,
+}
+
+impl NerfSource<fs::File> {
+    /// Resolve the file path of the frame in the `directory`.
+    ///
+    /// It returns the path relative to the `directory`.
+    /// The `.png` extension is appended if the frame omits the extension.
+    fn resolve_file_path(
+        directory: &Path,
+        file_path: &str,
+    ) -> Result<PathBuf, Error> {
+        let path = Path::new(file_path)
+            .components()
+            .filter(|component| component != &Component::CurDir)
+            .collect::<PathBuf>();
+        if directory.join(&path).is_file() {
+            return Ok(path);
+        }
+
+        let path_with_extension = path.with_extension("png");
+        if path.extension().is_none() && directory.join(&path_with_extension).is_file() {
+            return Ok(path_with_extension);
+        }
+
+        Err(Error::MissingFile(directory.join(path)))
+    }
+}
+
+impl Opener for NerfSource<fs::File> {
+    /// Opening the NeRF scene from the transforms file,
+    /// e.g., `transforms_train.json`.
+    ///
+    /// The file paths are relative to the directory of the transforms file.
+    /// The intrinsics of each frame override the shared ones.
+    /// If the image dimensions are absent, they are read from the image file.
+    /// The frames with the same intrinsics share a camera.
+    ///
+    /// The files are opened in read-only mode.
+    ///
+    /// # Errors
+    ///
+    /// It returns [`Error::MissingFile`] with the path of any missing file.
+    ///
+    /// It returns [`Error::UnsupportedKind`] if the camera model is unsupported.
+    fn open(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        if !path.is_file() {
+            return Err(Error::MissingFile(path.to_owned()));
+        }
+        let directory = path.parent().unwrap_or(Path::new("."));
+        let transforms = Transforms::decode(&mut fs::File::open(path)?)?;
+        let open_file = |path: PathBuf| -> Result<_, Error> {
+            let path = directory.join(path);
+            let inner = fs::File::open(&path)?;
+            Ok(File { inner, path })
+        };
+
+        let mut cameras = Cameras::default();
+        let mut images = Images::default();
+        let mut images_file = Files::default();
+        let mut masks_file = IndexMap::default();
+        let mut depths_file = IndexMap::default();
+
+        for (index, frame) in transforms.frames.iter().enumerate() {
+            let image_id = index as u32 + 1;
+            let file_path = Self::resolve_file_path(directory, &frame.file_path)?;
+
+            let intrinsics = frame.intrinsics.or(&transforms.intrinsics);
+            let dimensions = match (intrinsics.w, intrinsics.h) {
+                (Some(width), Some(height)) => (width, height),
+                _ => {
+                    let (width, height) =
+                        ::image::ImageReader::open(directory.join(&file_path))?
+                            .into_dimensions()?;
+                    (width as u64, height as u64)
+                },
+            };
+            let mut camera = intrinsics.camera(cameras.len() as u32 + 1, dimensions)?;
+            match cameras.values().find(|other| {
+                Camera {
+                    camera_id: camera.camera_id,
+                    ..**other
+                } == camera
+            }) {
+                Some(other) => camera.camera_id = other.camera_id,
+                None => {
+                    cameras.insert(camera.camera_id, camera);
+                },
+            }
+
+            let file_name = file_path
+                .to_str()
+                .ok_or_else(|| Error::InvalidUtf8(file_path.to_string_lossy().into()))?;
+            let file_name = CString::new(file_name)
+                .map_err(|err| Error::InvalidUtf8(format!("{:?}", err.into_vec())))?;
+            images.insert(image_id, frame.image(image_id, camera.camera_id, file_name));
+
+            if let Some(mask_path) = &frame.mask_path {
+                let mask_path = Self::resolve_file_path(directory, mask_path)?;
+                masks_file.insert(image_id, open_file(mask_path)?);
+            }
+            if let Some(depth_file_path) = &frame.depth_file_path {
+                let depth_file_path =
+                    Self::resolve_file_path(directory, depth_file_path)?;
+                depths_file.insert(image_id, open_file(depth_file_path)?);
+            }
+
+            let file = open_file(file_path)?;
+            images_file.insert(file.path.to_owned(), file);
+        }
+
+        #[cfg(all(debug_assertions, not(test)))]
+        log::debug!(target: "gausplat-loader::nerf", "NerfSource::open");
+
+        Ok(Self {
+            cameras,
+            images,
+            images_file,
+            masks_file,
+            depths_file,
+        })
+    }
+}
+
+impl<S> From<NerfSource<S>> for ColmapSource<S> {
+    /// The points are empty. The masks and depths are dropped.
+    #[inline]
+    fn from(source: NerfSource<S>) -> Self {
+        Self {
+            cameras: source.cameras,
+            images: source.images,
+            images_file: source.images_file,
+            points: Default::default(),
+        }
+    }
+}
+
+impl<S> fmt::Debug for NerfSource<S> {
+    #[inline]
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        f.debug_struct("NerfSource")
+            .field("cameras.len()", &self.cameras.len())
+            .field("images.len()", &self.images.len())
+            .field("images_file.len()", &self.images_file.len())
+            .field("masks_file.len()", &self.masks_file.len())
+            .field("depths_file.len()", &self.depths_file.len())
+            .finish()
+    }
+}
+
+impl<S: Default> Default for NerfSource<S> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            cameras: Default::default(),
+            images: Default::default(),
+            images_file: Default::default(),
+            masks_file: Default::default(),
+            depths_file: Default::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn open_on_synthetic() {
+        use super::*;
+
+        let source =
+            NerfSource::open("examples/data/nerf/0/transforms_train.json").unwrap();
+
+        let target = (4, 4, 2.0, 2.0);
+        let camera = source.cameras[&1];
+        let output = (
+            camera.width,
+            camera.height,
+            camera.principal_point_x,
+            camera.principal_point_y,
+        );
+        assert_eq!(output, target);
+
+        let target = [c"train/r_0.png", c"train/r_1.png"];
+        let output = source
+            .images
+            .values()
+            .map(|image| image.file_name.as_c_str())
+            .collect::<Vec<_>>();
+        assert_eq!(output, target);
+
+        let target = true;
+        let output = source
+            .images
+            .values()
+            .zip(source.images_file.keys())
+            .all(|(image, path)| image.matches_file_path(path));
+        assert_eq!(output, target);
+
+        let target = [0.0, 0.0, 4.0];
+        let image = &source.images[&2];
+        let output = image.position(&image.rotation());
+        assert_eq!(output, target);
+
+        let target = [0.0, 1.0, 0.0, 0.0];
+        let output = image.quaternion;
+        assert_eq!(output, target);
+
+        let target = (1, 2, 2, 0);
+        let output = ColmapSource::from(source);
+        let output = (
+            output.cameras.len(),
+            output.images.len(),
+            output.images_file.len(),
+            output.points.len(),
+        );
+        assert_eq!(output, target);
+    }
+
+    #[test]
+    fn open_on_instant_ngp() {
+        use super::*;
+
+        let source = NerfSource::open("examples/data/nerf/1/transforms.json").unwrap();
+
+        let target = Camera {
+            camera_id: 1,
+            width: 8,
+            height: 8,
+            principal_point_x: 4.0,
+            principal_point_y: 4.5,
+            variant: CameraVariant::Pinhole {
+                focal_length_x: 8.0,
+                focal_length_y: 9.0,
+            },
+        };
+        let output = source.cameras[&1];
+        assert_eq!(output, target);
+
+        let target = [1.0, 2.0, 3.0];
+        let image = &source.images[&1];
+        let output = image.position(&image.rotation());
+        assert_eq!(output, target);
+
+        let target = true;
+        let output = format!("{:?}", source).starts_with("NerfSource");
+        assert_eq!(output, target);
+    }
+
+    #[test]
+    fn open_on_nerfstudio() {
+        use super::*;
+
+        let source = NerfSource::open("examples/data/nerf/2/transforms.json").unwrap();
+
+        let target = vec![
+            Camera {
+                camera_id: 1,
+                width: 8,
+                height: 8,
+                principal_point_x: 4.0,
+                principal_point_y: 4.0,
+                variant: CameraVariant::OpenCv {
+                    focal_length_x: 8.0,
+                    focal_length_y: 8.0,
+                    k1: 0.1,
+                    k2: 0.01,
+                    p1: 0.0,
+                    p2: 0.0,
+                },
+            },
+            Camera {
+                camera_id: 2,
+                width: 8,
+                height: 8,
+                principal_point_x: 4.0,
+                principal_point_y: 4.0,
+                variant: CameraVariant::OpenCvFisheye {
+                    focal_length_x: 6.0,
+                    focal_length_y: 6.0,
+                    k1: 0.1,
+                    k2: 0.01,
+                    k3: 0.001,
+                    k4: 0.0001,
+                },
+            },
+        ];
+        let output = source.cameras.values().copied().collect::<Vec<_>>();
+        assert_eq!(output, target);
+
+        let target = vec![1, 1, 2];
+        let output = source
+            .images
+            .values()
+            .map(|image| image.camera_id)
+            .collect::<Vec<_>>();
+        assert_eq!(output, target);
+
+        let target = (vec![1], vec![1]);
+        let output = (
+            source.masks_file.keys().copied().collect::<Vec<_>>(),
+            source.depths_file.keys().copied().collect::<Vec<_>>(),
+        );
+        assert_eq!(output, target);
+
+        let target = true;
+        let output = source.masks_file[&1].path.ends_with("masks/001.png")
+            && source.depths_file[&1].path.ends_with("depths/001.png");
+        assert_eq!(output, target);
+    }
+
+    #[test]
+    fn open_on_missing_files() {
+        use super::*;
+
+        let target = true;
+        let output = matches!(
+            NerfSource::open("examples/data/nerf/0/transforms_test.json").unwrap_err(),
+            Error::MissingFile(path) if path.ends_with("transforms_test.json")
+        );
+        assert_eq!(output, target);
+    }
+}
@@ -0,0 +1,527 @@
+//! NeRF transforms.
+//!
+//! It is generally stored in the `transforms.json` file.
+
+pub use super::*;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// NeRF transforms.
+///
+/// The unknown fields are preserved in [`Self::extra`].
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct Transforms {
+    /// Camera intrinsics shared by all frames.
+    #[serde(flatten)]
+    pub intrinsics: Intrinsics,
+    /// Frames.
+    pub frames: Vec<Frame>,
+    /// Other fields.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// A frame in [`Transforms`].
+///
+/// The unknown fields are preserved in [`Self::extra`].
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct Frame {
+    /// Image file path relative to the transforms file.
+    ///
+    /// The extension may be omitted, e.g., `./train/r_0`.
+    pub file_path: String,
+    /// The camera-to-world transformation in OpenGL convention.
+    ///
+    /// It is in **row-major order**, i.e., `M[row][col]`.
+    pub transform_matrix: [[f64; 4]; 4],
+    /// Mask file path relative to the transforms file.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mask_path: Option<String>,
+    /// Depth file path relative to the transforms file.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub depth_file_path: Option<String>,
+    /// Camera intrinsics overriding [`Transforms::intrinsics`].
+    #[serde(flatten)]
+    pub intrinsics: Intrinsics,
+    /// Other fields.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// Camera intrinsics in [`Transforms`] or [`Frame`].
+///
+/// All fields are optional.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct Intrinsics {
+    /// Camera model name of Nerfstudio,
+    /// e.g., `OPENCV`, `OPENCV_FISHEYE` or `EQUIRECTANGULAR`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub camera_model: Option<String>,
+    /// Horizontal field of view in radians.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub camera_angle_x: Option<f64>,
+    /// Vertical field of view in radians.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub camera_angle_y: Option<f64>,
+    /// Focal length x value.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fl_x: Option<f64>,
+    /// Focal length y value.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fl_y: Option<f64>,
+    /// Principal point x value.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cx: Option<f64>,
+    /// Principal point y value.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cy: Option<f64>,
+    /// Image width.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub w: Option<u64>,
+    /// Image height.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub h: Option<u64>,
+    /// Radial distortion coefficient 1.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub k1: Option<f64>,
+    /// Radial distortion coefficient 2.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub k2: Option<f64>,
+    /// Radial distortion coefficient 3.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub k3: Option<f64>,
+    /// Radial distortion coefficient 4.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub k4: Option<f64>,
+    /// Tangential distortion coefficient 1.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub p1: Option<f64>,
+    /// Tangential distortion coefficient 2.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub p2: Option<f64>,
+}
+
+impl Intrinsics {
+    /// Return the intrinsics with the absent fields taken from the `base`.
+    pub fn or(
+        &self,
+        base: &Self,
+    ) -> Self {
+        Self {
+            camera_model: self
+                .camera_model
+                .to_owned()
+                .or(base.camera_model.to_owned()),
+            camera_angle_x: self.camera_angle_x.or(base.camera_angle_x),
+            camera_angle_y: self.camera_angle_y.or(base.camera_angle_y),
+            fl_x: self.fl_x.or(base.fl_x),
+            fl_y: self.fl_y.or(base.fl_y),
+            cx: self.cx.or(base.cx),
+            cy: self.cy.or(base.cy),
+            w: self.w.or(base.w),
+            h: self.h.or(base.h),
+            k1: self.k1.or(base.k1),
+            k2: self.k2.or(base.k2),
+            k3: self.k3.or(base.k3),
+            k4: self.k4.or(base.k4),
+            p1: self.p1.or(base.p1),
+            p2: self.p2.or(base.p2),
+        }
+    }
+
+    /// Return the camera with the image dimensions `(width, height)`.
+    ///
+    /// [`Self::w`] and [`Self::h`] take precedence over the `dimensions`.
+    ///
+    /// The camera model is mapped as follows:
+    /// - `OPENCV` to [`CameraVariant::OpenCv`],
+    ///   or [`CameraVariant::FullOpenCv`] if `k3` is non-zero.
+    /// - `OPENCV_FISHEYE` to [`CameraVariant::OpenCvFisheye`].
+    /// - `PINHOLE` to [`CameraVariant::Pinhole`].
+    /// - Absent to [`CameraVariant::OpenCv`] if any distortion coefficient is non-zero,
+    ///   or [`CameraVariant::Pinhole`] otherwise.
+    ///
+    /// # Errors
+    ///
+    /// It returns [`Error::MissingSymbol`] if neither
+    /// [`Self::fl_x`] nor [`Self::camera_angle_x`] exists.
+    ///
+    /// It returns [`Error::UnsupportedKind`] for other camera models,
+    /// e.g., `EQUIRECTANGULAR`, which COLMAP cannot represent.
+    /// It also returns the error if `k4` is non-zero for `OPENCV`,
+    /// since the `r^8` term has no counterpart in COLMAP.
+    pub fn camera(
+        &self,
+        camera_id: u32,
+        dimensions: (u64, u64),
+    ) -> Result<Camera, Error> {
+        use CameraVariant::*;
+
+        let width = self.w.unwrap_or(dimensions.0);
+        let height = self.h.unwrap_or(dimensions.1);
+        let focal_length =
+            |angle: f64, size: u64| size as f64 / 2.0 / (angle / 2.0).tan();
+
+        let focal_length_x = self
+            .fl_x
+            .or_else(|| Some(focal_length(self.camera_angle_x?, width)))
+            .ok_or_else(|| Error::MissingSymbol("camera_angle_x".into()))?;
+        let focal_length_y = self
+            .fl_y
+            .or_else(|| Some(focal_length(self.camera_angle_y?, height)))
+            .unwrap_or(focal_length_x);
+        let [k1, k2, k3, k4, p1, p2] =
+            [self.k1, self.k2, self.k3, self.k4, self.p1, self.p2]
+                .map(Option::unwrap_or_default);
+        let is_distorted = [k1, k2, k3, k4, p1, p2].iter().any(|k| *k != 0.0);
+
+        let variant = match self.camera_model.as_deref() {
+            // NOTE: The k4 of FULL_OPENCV is in the rational denominator,
+            // while the one of Nerfstudio is the r^8 term in the numerator.
+            Some("OPENCV") | None if k4 != 0.0 => {
+                return Err(Error::UnsupportedKind("OPENCV with k4".into()))
+            },
+            Some("OPENCV") | None if k3 != 0.0 => FullOpenCv {
+                focal_length_x,
+                focal_length_y,
+                k1,
+                k2,
+                p1,
+                p2,
+                k3,
+                k4: 0.0,
+                k5: 0.0,
+                k6: 0.0,
+            },
+            Some("OPENCV") => OpenCv {
+                focal_length_x,
+                focal_length_y,
+                k1,
+                k2,
+                p1,
+                p2,
+            },
+            None if is_distorted => OpenCv {
+                focal_length_x,
+                focal_length_y,
+                k1,
+                k2,
+                p1,
+                p2,
+            },
+            Some("OPENCV_FISHEYE") => OpenCvFisheye {
+                focal_length_x,
+                focal_length_y,
+                k1,
+                k2,
+                k3,
+                k4,
+            },
+            Some("PINHOLE") | None => Pinhole {
+                focal_length_x,
+                focal_length_y,
+            },
+            Some(camera_model) => {
+                return Err(Error::UnsupportedKind(camera_model.into()))
+            },
+        };
+
+        Ok(Camera {
+            camera_id,
+            width,
+            height,
+            principal_point_x: self.cx.unwrap_or(width as f64 / 2.0),
+            principal_point_y: self.cy.unwrap_or(height as f64 / 2.0),
+            variant,
+        })
+    }
+}
+
+impl Frame {
+    /// Return the COLMAP image with the pose of the frame.
+    ///
+    /// The camera-to-world transformation in OpenGL convention is converted to
+    /// the world-to-view transformation in COLMAP convention,
+    /// which flips the y and z axes of the view space.
+    pub fn image(
+        &self,
+        image_id: u32,
+        camera_id: u32,
+        file_name: CString,
+    ) -> Image {
+        let m = &self.transform_matrix;
+        // NOTE: The rows of the camera-to-world rotation are
+        // the columns of the world-to-view rotation.
+        let rotation = [
+            [m[0][0], -m[0][1], -m[0][2]],
+            [m[1][0], -m[1][1], -m[1][2]],
+            [m[2][0], -m[2][1], -m[2][2]],
+        ];
+        let position = [m[0][3], m[1][3], m[2][3]];
+
+        let mut image = Image {
+            image_id,
+            camera_id,
+            file_name,
+            ..Default::default()
+        };
+        image
+            .set_rotation(&rotation)
+            .set_position(&position, &rotation);
+        image
+    }
+}
+
+impl Decoder for Transforms {
+    type Err = Error;
+
+    #[inline]
+    fn decode(reader: &mut impl Read) -> Result<Self, Self::Err> {
+        let transforms = serde_json::from_reader(BufReader::new(reader))?;
+
+        #[cfg(all(debug_assertions, not(test)))]
+        log::debug!(target: "gausplat-loader::nerf::transforms", "Transforms::decode");
+
+        Ok(transforms)
+    }
+}
+
+impl Encoder for Transforms {
+    type Err = Error;
+
+    #[inline]
+    fn encode(
+        &self,
+        writer: &mut impl Write,
+    ) -> Result<(), Self::Err> {
+        let writer = &mut BufWriter::new(writer);
+        serde_json::to_writer_pretty(&mut *writer, self)?;
+        writer.flush()?;
+
+        #[cfg(all(debug_assertions, not(test)))]
+        log::debug!(target: "gausplat-loader::nerf::transforms", "Transforms::encode");
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn decode_and_encode() {
+        use super::*;
+        use std::io::Cursor;
+
+        let source = &include_bytes!("../../../examples/data/nerf/1/transforms.json")[..];
+
+        let transforms = Transforms::decode(&mut Cursor::new(source)).unwrap();
+
+        let target = (Some(8.0), Some(9.0), Some(8), Some(8), 2);
+        let output = (
+            transforms.intrinsics.fl_x,
+            transforms.intrinsics.fl_y,
+            transforms.intrinsics.w,
+            transforms.intrinsics.h,
+            transforms.frames.len(),
+        );
+        assert_eq!(output, target);
+
+        let target = Some(&Value::from(16));
+        let output = transforms.extra.get("aabb_scale");
+        assert_eq!(output, target);
+
+        let target = transforms;
+        let mut writer = Cursor::new(vec![]);
+        target.encode(&mut writer).unwrap();
+        let output = Transforms::decode(&mut Cursor::new(writer.into_inner())).unwrap();
+        assert_eq!(output, target);
+    }
+
+    #[test]
+    fn decode_on_invalid_json() {
+        use super::*;
+        use std::io::Cursor;
+
+        let source = &b"{\"frames\": [{\"file_path\": 1}]}"[..];
+
+        let target = true;
+        let output = matches!(
+            Transforms::decode(&mut Cursor::new(source)).unwrap_err(),
+            Error::Json(_)
+        );
+        assert_eq!(output, target);
+    }
+
+    #[test]
+    fn camera() {
+        use super::*;
+
+        let intrinsics = Intrinsics {
+            camera_angle_x: Some(std::f64::consts::FRAC_PI_2),
+            ..Default::default()
+        };
+
+        let target = Camera {
+            camera_id: 1,
+            width: 800,
+            height: 600,
+            principal_point_x: 400.0,
+            principal_point_y: 300.0,
+            variant: CameraVariant::Pinhole {
+                focal_length_x: 400.00000000000006,
+                focal_length_y: 400.00000000000006,
+            },
+        };
+        let output = intrinsics.camera(1, (800, 600)).unwrap();
+        assert_eq!(output, target);
+
+        let target = true;
+        let output = matches!(
+            Intrinsics::default().camera(1, (800, 600)).unwrap_err(),
+            Error::MissingSymbol(name) if name == "camera_angle_x"
+        );
+        assert_eq!(output, target);
+    }
+
+    #[test]
+    fn camera_on_distortion() {
+        use super::*;
+
+        let intrinsics = Intrinsics {
+            fl_x: Some(8.0),
+            k1: Some(0.1),
+            p2: Some(0.2),
+            ..Default::default()
+        };
+
+        let target = CameraVariant::OpenCv {
+            focal_length_x: 8.0,
+            focal_length_y: 8.0,
+            k1: 0.1,
+            k2: 0.0,
+            p1: 0.0,
+            p2: 0.2,
+        };
+        let output = intrinsics.camera(1, (8, 8)).unwrap().variant;
+        assert_eq!(output, target);
+
+        let target = CameraVariant::FullOpenCv {
+            focal_length_x: 8.0,
+            focal_length_y: 8.0,
+            k1: 0.1,
+            k2: 0.0,
+            p1: 0.0,
+            p2: 0.2,
+            k3: 0.3,
+            k4: 0.0,
+            k5: 0.0,
+            k6: 0.0,
+        };
+        let output = Intrinsics {
+            camera_model: Some("OPENCV".into()),
+            k3: Some(0.3),
+            ..intrinsics.to_owned()
+        }
+        .camera(1, (8, 8))
+        .unwrap()
+        .variant;
+        assert_eq!(output, target);
+
+        let target = true;
+        let output = matches!(
+            Intrinsics {
+                k3: Some(0.3),
+                k4: Some(0.4),
+                ..intrinsics.to_owned()
+            }
+            .camera(1, (8, 8))
+            .unwrap_err(),
+            Error::UnsupportedKind(kind) if kind == "OPENCV with k4"
+        );
+        assert_eq!(output, target);
+
+        let target = CameraVariant::OpenCvFisheye {
+            focal_length_x: 8.0,
+            focal_length_y: 8.0,
+            k1: 0.1,
+            k2: 0.0,
+            k3: 0.0,
+            k4: 0.4,
+        };
+        let output = Intrinsics {
+            camera_model: Some("OPENCV_FISHEYE".into()),
+            k4: Some(0.4),
+            ..intrinsics.to_owned()
+        }
+        .camera(1, (8, 8))
+        .unwrap()
+        .variant;
+        assert_eq!(output, target);
+
+        let target = true;
+        let output = matches!(
+            Intrinsics {
+                camera_model: Some("EQUIRECTANGULAR".into()),
+                ..intrinsics.to_owned()
+            }
+            .camera(1, (8, 8))
+            .unwrap_err(),
+            Error::UnsupportedKind(kind) if kind == "EQUIRECTANGULAR"
+        );
+        assert_eq!(output, target);
+    }
+
+    #[test]
+    fn intrinsics_or() {
+        use super::*;
+
+        let base = Intrinsics {
+            camera_model: Some("OPENCV".into()),
+            fl_x: Some(8.0),
+            k1: Some(0.1),
+            ..Default::default()
+        };
+        let intrinsics = Intrinsics {
+            fl_x: Some(6.0),
+            ..Default::default()
+        };
+
+        let target = Intrinsics {
+            camera_model: Some("OPENCV".into()),
+            fl_x: Some(6.0),
+            k1: Some(0.1),
+            ..Default::default()
+        };
+        let output = intrinsics.or(&base);
+        assert_eq!(output, target);
+    }
+
+    #[test]
+    fn image() {
+        use super::*;
+
+        let frame = Frame {
+            transform_matrix: [
+                [0.0, 0.0, 1.0, 4.0],
+                [1.0, 0.0, 0.0, 5.0],
+                [0.0, 1.0, 0.0, 6.0],
+                [0.0, 0.0, 0.0, 1.0],
+            ],
+            ..Default::default()
+        };
+        let image = frame.image(1, 1, c"r_0.png".into());
+
+        let target = [4.0, 5.0, 6.0];
+        let output = image.position(&image.rotation());
+        assert_eq!(output, target);
+
+        // NOTE: The view space is flipped in y and z axes from OpenGL convention.
+        let target = [[0.0, 1.0, 0.0], [0.0, 0.0, -1.0], [-1.0, 0.0, 0.0]];
+        let rotation = image.rotation();
+        let output = [0, 1, 2].map(|row| {
+            [0, 1, 2].map(|col| (rotation[col][row] * 1e12).round() / 1e12 + 0.0)
+        });
+        assert_eq!(output, target);
+    }
+}
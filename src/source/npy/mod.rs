@@ -0,0 +1,335 @@
+//! NumPy array source.
+//!
+//! It supports the little-endian floating-point arrays in C order
+//! of the format versions 1.0, 2.0 and 3.0.
+//!
+//! For more information, see the NumPy
+//! [format](https://numpy.org/doc/stable/reference/generated/numpy.lib.format.html).
+
+pub use crate::{
+    error::Error,
+    function::{Decoder, Encoder},
+};
+
+use crate::function::{read_bytes_const, read_bytes_untrusted};
+use std::io::{Read, Write};
+
+/// The magic bytes of the NumPy array file.
+pub const MAGIC: &[u8; 6] = b"\x93NUMPY";
+
+/// NumPy array in `.npy` format.
+///
+/// The values are converted to `f64` in C order.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct NpyArray {
+    /// The dimensions.
+    pub shape: Vec<usize>,
+    /// The values in **row-major order**.
+    pub data: Vec<f64>,
+}
+
+impl NpyArray {
+    /// Return the row of the 2-D array.
+    ///
+    /// ## Panics
+    ///
+    /// It panics if the array is not 2-D or the `index` is out of bounds.
+    #[inline]
+    pub fn row(
+        &self,
+        index: usize,
+    ) -> &[f64] {
+        let column_count = self.shape[1];
+        &self.data[index * column_count..(index + 1) * column_count]
+    }
+
+    /// Obtain the value of the key in the header dictionary.
+    ///
+    /// The value is a Python literal, e.g., `'<f8'`, `False` or `(20, 17)`.
+    fn header_value<'h>(
+        header: &'h str,
+        key: &str,
+    ) -> Result<&'h str, Error> {
+        let value = header
+            .split_once(&format!("'{key}':"))
+            .ok_or_else(|| Error::MissingSymbol(key.into()))?
+            .1
+            .trim_start();
+        let end = if value.starts_with('(') {
+            value.find(')').map(|end| end + 1)
+        } else {
+            value.find([',', '}'])
+        };
+        Ok(value[..end.unwrap_or(value.len())].trim())
+    }
+}
+
+impl Decoder for NpyArray {
+    type Err = Error;
+
+    fn decode(reader: &mut impl Read) -> Result<Self, Self::Err> {
+        if &read_bytes_const(reader)? != MAGIC {
+            return Err(Error::MissingSymbol(String::from_utf8_lossy(MAGIC).into()));
+        }
+
+        let [major, minor] = read_bytes_const(reader)?;
+        let header_size = match major {
+            1 => u16::from_le_bytes(read_bytes_const(reader)?) as usize,
+            2 | 3 => u32::from_le_bytes(read_bytes_const(reader)?) as usize,
+            _ => return Err(Error::UnsupportedKind(format!("version: {major}.{minor}"))),
+        };
+        let header = read_bytes_untrusted(reader, header_size)?;
+        let header = String::from_utf8(header)
+            .map_err(|err| Error::InvalidUtf8(format!("{:?}", err.into_bytes())))?;
+
+        let descr = Self::header_value(&header, "descr")?.trim_matches(['\'', '"']);
+        let fortran_order = Self::header_value(&header, "fortran_order")?;
+        if fortran_order != "False" {
+            return Err(Error::UnsupportedKind(format!(
+                "fortran_order: {fortran_order}"
+            )));
+        }
+        let shape_value = Self::header_value(&header, "shape")?;
+        let shape = shape_value
+            .trim_matches(['(', ')'])
+            .split(',')
+            .map(str::trim)
+            .filter(|dimension| !dimension.is_empty())
+            .map(str::parse)
+            .collect::<Result<Vec<usize>, _>>()?;
+
+        // NOTE: The shape is untrusted, so its product must not overflow.
+        let count = shape
+            .iter()
+            .try_fold(1_usize, |count, &dimension| count.checked_mul(dimension));
+        let size = |stride: usize| {
+            count
+                .and_then(|count| count.checked_mul(stride))
+                .ok_or_else(|| Error::UnsupportedKind(format!("shape: {shape_value}")))
+        };
+        let data = match descr {
+            "<f8" => read_bytes_untrusted(reader, size(8)?)?
+                .chunks_exact(8)
+                .map(|bytes| f64::from_le_bytes(bytes.try_into().unwrap()))
+                .collect(),
+            "<f4" => read_bytes_untrusted(reader, size(4)?)?
+                .chunks_exact(4)
+                .map(|bytes| f32::from_le_bytes(bytes.try_into().unwrap()) as f64)
+                .collect(),
+            _ => return Err(Error::UnsupportedKind(descr.into())),
+        };
+
+        #[cfg(all(debug_assertions, not(test)))]
+        log::debug!(target: "gausplat-loader::npy", "NpyArray::decode");
+
+        Ok(Self { shape, data })
+    }
+}
+
+impl Encoder for NpyArray {
+    type Err = Error;
+
+    /// The values are encoded as `<f8` in version 1.0.
+    fn encode(
+        &self,
+        writer: &mut impl Write,
+    ) -> Result<(), Self::Err> {
+        // The alignment of the header.
+        const ALIGNMENT: usize = 64;
+
+        let shape = match self.shape.as_slice() {
+            [dimension] => format!("({dimension},)"),
+            shape => format!(
+                "({})",
+                shape
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        };
+        let mut header =
+            format!("{{'descr': '<f8', 'fortran_order': False, 'shape': {shape}, }}");
+        // NOTE: The prefix has 10 bytes, and the header ends with a newline.
+        let padding = (ALIGNMENT - (10 + header.len() + 1) % ALIGNMENT) % ALIGNMENT;
+        header.push_str(&" ".repeat(padding));
+        header.push('\n');
+
+        writer.write_all(MAGIC)?;
+        writer.write_all(&[1, 0])?;
+        writer.write_all(&(header.len() as u16).to_le_bytes())?;
+        writer.write_all(header.as_bytes())?;
+        self.data
+            .iter()
+            .try_for_each(|value| writer.write_all(&value.to_le_bytes()))?;
+
+        #[cfg(all(debug_assertions, not(test)))]
+        log::debug!(target: "gausplat-loader::npy", "NpyArray::encode");
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn decode_and_encode() {
+        use super::*;
+        use std::io::Cursor;
+
+        let source = NpyArray {
+            shape: vec![2, 3],
+            data: vec![0.0, 1.0, 2.0, 3.0, 4.5, -5.25],
+        };
+        let mut writer = vec![];
+        source.encode(&mut writer).unwrap();
+
+        let target = 0;
+        let output = (writer.len() - 6 * 8) % 64;
+        assert_eq!(output, target);
+
+        let target = source;
+        let output = NpyArray::decode(&mut Cursor::new(writer)).unwrap();
+        assert_eq!(output, target);
+
+        let target = &[3.0, 4.5, -5.25];
+        let output = output.row(1);
+        assert_eq!(output, target);
+    }
+
+    #[test]
+    fn decode_on_float32() {
+        use super::*;
+        use std::io::Cursor;
+
+        let header = "{'descr': '<f4', 'fortran_order': False, 'shape': (3,), }\n";
+        let mut source = vec![];
+        source.extend(MAGIC);
+        source.extend([1, 0]);
+        source.extend((header.len() as u16).to_le_bytes());
+        source.extend(header.as_bytes());
+        [1.0_f32, 0.5, -2.0]
+            .iter()
+            .for_each(|value| source.extend(value.to_le_bytes()));
+
+        let target = NpyArray {
+            shape: vec![3],
+            data: vec![1.0, 0.5, -2.0],
+        };
+        let output = NpyArray::decode(&mut Cursor::new(source)).unwrap();
+        assert_eq!(output, target);
+    }
+
+    #[test]
+    fn decode_on_unsupported_kinds() {
+        use super::*;
+        use std::io::Cursor;
+
+        let target = true;
+        let output = matches!(
+            NpyArray::decode(&mut Cursor::new(b"\x93NUMPX")).unwrap_err(),
+            Error::MissingSymbol(..)
+        );
+        assert_eq!(output, target);
+
+        [
+            (
+                "{'descr': '<i4', 'fortran_order': False, 'shape': (1,), }",
+                "<i4",
+            ),
+            (
+                "{'descr': '<f8', 'fortran_order': True, 'shape': (1,), }",
+                "fortran_order: True",
+            ),
+        ]
+        .into_iter()
+        .for_each(|(header, kind)| {
+            let mut source = vec![];
+            source.extend(MAGIC);
+            source.extend([1, 0]);
+            source.extend((header.len() as u16).to_le_bytes());
+            source.extend(header.as_bytes());
+            source.extend([0; 8]);
+
+            let target = true;
+            let output = matches!(
+                NpyArray::decode(&mut Cursor::new(source)).unwrap_err(),
+                Error::UnsupportedKind(output) if output == kind
+            );
+            assert_eq!(output, target);
+        });
+    }
+    #[test]
+    fn decode_on_invalid_shape() {
+        use super::*;
+        use std::io::Cursor;
+
+        [
+            ("(2305843009213693952,)", true),
+            ("(4294967296, 4294967296)", true),
+            ("(1000000000, 17)", false),
+        ]
+        .into_iter()
+        .for_each(|(shape, is_overflowed)| {
+            let header = format!(
+                "{{'descr': '<f8', 'fortran_order': False, 'shape': {shape}, }}\n"
+            );
+            let mut source = vec![];
+            source.extend(MAGIC);
+            source.extend([1, 0]);
+            source.extend((header.len() as u16).to_le_bytes());
+            source.extend(header.as_bytes());
+            source.extend([0; 8]);
+
+            let target = true;
+            let output = match NpyArray::decode(&mut Cursor::new(source)).unwrap_err() {
+                Error::UnsupportedKind(output) => {
+                    is_overflowed && output == format!("shape: {shape}")
+                },
+                Error::Io(err) => {
+                    !is_overflowed && err.kind() == std::io::ErrorKind::UnexpectedEof
+                },
+                _ => false,
+            };
+            assert_eq!(output, target);
+        });
+    }
+
+    #[test]
+    fn decode_on_unsupported_version() {
+        use super::*;
+        use std::io::Cursor;
+
+        let header = "{'descr': '<f8', 'fortran_order': False, 'shape': (1,), }\n";
+        [2, 3].into_iter().for_each(|major| {
+            let mut source = vec![];
+            source.extend(MAGIC);
+            source.extend([major, 0]);
+            source.extend((header.len() as u32).to_le_bytes());
+            source.extend(header.as_bytes());
+            source.extend(1.5_f64.to_le_bytes());
+
+            let target = NpyArray {
+                shape: vec![1],
+                data: vec![1.5],
+            };
+            let output = NpyArray::decode(&mut Cursor::new(source)).unwrap();
+            assert_eq!(output, target);
+        });
+
+        [0, 4].into_iter().for_each(|major| {
+            let mut source = vec![];
+            source.extend(MAGIC);
+            source.extend([major, 0]);
+            source.extend((header.len() as u32).to_le_bytes());
+            source.extend(header.as_bytes());
+
+            let target = true;
+            let output = matches!(
+                NpyArray::decode(&mut Cursor::new(source)).unwrap_err(),
+                Error::UnsupportedKind(output) if output == format!("version: {major}.0")
+            );
+            assert_eq!(output, target);
+        });
+    }
+}
@@ -0,0 +1,168 @@
+//! OpenMVG source.
+//!
+//! It supports the `sfm_data.json` format of OpenMVG.
+//!
+//! For more information, see the OpenMVG
+//! [documentation](https://openmvg.readthedocs.io/en/latest/openMVG/sfm/sfm/#sfm-data).
+
+pub mod sfm_data;
+
+pub use super::colmap::{
+    Camera, CameraVariant, Cameras, ColmapSource, Image, ImagePoint, Images, Point,
+    Points, TrackElement,
+};
+pub use super::file::*;
+pub use crate::function::Decoder;
+pub use sfm_data::*;
+
+use std::{ffi::CString, fmt, fs, path::Path};
+
+/// OpenMVG source.
+///
+/// It is converted to the cameras, images and points of [`ColmapSource`].
+///
+/// OpenMVG exports no point colors, so the points are black.
+#[derive(Clone, PartialEq)]
+pub struct OpenMvgSource<S> {
+    /// Cameras.
+    pub cameras: Cameras,
+    /// Images.
+    pub images: Images,
+    /// Images' file.
+    pub images_file: Files<S>,
+    /// Points.
+    pub points: Points,
+}
+
+impl Opener for OpenMvgSource<fs::File> {
+    /// Opening the OpenMVG scene from the scene file,
+    /// e.g., `sfm_data.json`.
+    ///
+    /// The relative [root path](SfmData::root_path) is relative to
+    /// the directory of the scene file.
+    /// The image files of the views without poses are not opened.
+    ///
+    /// The files are opened in read-only mode.
+    ///
+    /// # Errors
+    ///
+    /// It returns [`Error::MissingFile`] with the path of any missing file.
+    fn open(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        if !path.is_file() {
+            return Err(Error::MissingFile(path.to_owned()));
+        }
+        let directory = path.parent().unwrap_or(Path::new("."));
+        let sfm_data = SfmData::decode(&mut fs::File::open(path)?)?;
+
+        let cameras = sfm_data.cameras()?;
+        let (images, points) = sfm_data.images_and_points()?;
+
+        let images_file = sfm_data
+            .views()?
+            .filter(|view| images.contains_key(&view.id_view))
+            .map(|view| {
+                let path = directory
+                    .join(&sfm_data.root_path)
+                    .join(&view.local_path)
+                    .join(&view.filename);
+                if !path.is_file() {
+                    return Err(Error::MissingFile(path));
+                }
+                let inner = fs::File::open(&path)?;
+                Ok((path.to_owned(), File { inner, path }))
+            })
+            .collect::<Result<_, Error>>()?;
+
+        #[cfg(all(debug_assertions, not(test)))]
+        log::debug!(target: "gausplat-loader::openmvg", "OpenMvgSource::open");
+
+        Ok(Self {
+            cameras,
+            images,
+            images_file,
+            points,
+        })
+    }
+}
+
+impl<S> From<OpenMvgSource<S>> for ColmapSource<S> {
+    #[inline]
+    fn from(source: OpenMvgSource<S>) -> Self {
+        Self {
+            cameras: source.cameras,
+            images: source.images,
+            images_file: source.images_file,
+            points: source.points,
+        }
+    }
+}
+
+impl<S> fmt::Debug for OpenMvgSource<S> {
+    #[inline]
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        f.debug_struct("OpenMvgSource")
+            .field("cameras.len()", &self.cameras.len())
+            .field("images.len()", &self.images.len())
+            .field("images_file.len()", &self.images_file.len())
+            .field("points.len()", &self.points.len())
+            .finish()
+    }
+}
+
+impl<S: Default> Default for OpenMvgSource<S> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            cameras: Default::default(),
+            images: Default::default(),
+            images_file: Default::default(),
+            points: Default::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn open() {
+        use super::*;
+
+        let source =
+            OpenMvgSource::open("examples/data/openmvg/0/sfm_data.json").unwrap();
+
+        let target = true;
+        let output = source.images.values().all(|image| {
+            source
+                .images_file
+                .keys()
+                .any(|path| image.matches_file_path(path))
+        });
+        assert_eq!(output, target);
+
+        let target = (3, 2, 2, 1);
+        let output = ColmapSource::from(source);
+        let output = (
+            output.cameras.len(),
+            output.images.len(),
+            output.images_file.len(),
+            output.points.len(),
+        );
+        assert_eq!(output, target);
+    }
+
+    #[test]
+    fn open_on_missing_files() {
+        use super::*;
+
+        let target = true;
+        let output = matches!(
+            OpenMvgSource::open("examples/data/openmvg/0/sfm_data.bin").unwrap_err(),
+            Error::MissingFile(path) if path.ends_with("sfm_data.bin")
+        );
+        assert_eq!(output, target);
+    }
+}
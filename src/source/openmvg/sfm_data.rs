@@ -0,0 +1,505 @@
+//! OpenMVG scene module.
+
+pub use super::*;
+
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    io::{BufReader, Read},
+};
+
+/// The mask of the polymorphic ID declared with the name.
+pub const POLYMORPHIC_ID_MASK: u32 = 0x7FFF_FFFF;
+
+/// OpenMVG scene in `sfm_data.json`.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+pub struct SfmData {
+    /// The version, e.g., `0.3`.
+    pub sfm_data_version: String,
+    /// The root directory of the image files.
+    #[serde(default)]
+    pub root_path: String,
+    /// Views mapped by the view ID.
+    #[serde(default)]
+    pub views: Vec<Entry<Polymorphic<View>>>,
+    /// Intrinsics mapped by the intrinsic ID.
+    #[serde(default)]
+    pub intrinsics: Vec<Entry<Polymorphic<Intrinsic>>>,
+    /// Poses mapped by the pose ID.
+    #[serde(default)]
+    pub extrinsics: Vec<Entry<Pose>>,
+    /// Landmarks mapped by the landmark ID.
+    #[serde(default)]
+    pub structure: Vec<Entry<Landmark>>,
+}
+
+/// A key-value entry in [`SfmData`].
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+pub struct Entry<T> {
+    /// Key.
+    pub key: u32,
+    /// Value.
+    pub value: T,
+}
+
+/// A polymorphic value in [`SfmData`].
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+pub struct Polymorphic<T> {
+    /// Polymorphic ID.
+    ///
+    /// The name is declared in the first value of each type.
+    #[serde(default)]
+    pub polymorphic_id: u32,
+    /// Polymorphic name, e.g., `pinhole_radial_k3`.
+    #[serde(default)]
+    pub polymorphic_name: Option<String>,
+    /// Pointer wrapper.
+    pub ptr_wrapper: PtrWrapper<T>,
+}
+
+/// A pointer wrapper in [`Polymorphic`].
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+pub struct PtrWrapper<T> {
+    /// Pointer ID.
+    pub id: u32,
+    /// Data.
+    ///
+    /// It is `None` if the pointer refers to the previous data.
+    pub data: Option<T>,
+}
+
+/// A view in [`SfmData`].
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+pub struct View {
+    /// Local directory of the image file relative to [`SfmData::root_path`].
+    #[serde(default)]
+    pub local_path: String,
+    /// Image file name.
+    pub filename: String,
+    /// Image width.
+    pub width: u64,
+    /// Image height.
+    pub height: u64,
+    /// View ID.
+    pub id_view: u32,
+    /// Intrinsic ID.
+    pub id_intrinsic: u32,
+    /// Pose ID.
+    pub id_pose: u32,
+}
+
+/// An intrinsic in [`SfmData`].
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+pub struct Intrinsic {
+    /// Image width.
+    pub width: u64,
+    /// Image height.
+    pub height: u64,
+    /// Focal length.
+    pub focal_length: f64,
+    /// Principal point.
+    pub principal_point: [f64; 2],
+    /// Distortion coefficients of `pinhole_radial_k1`.
+    #[serde(default)]
+    pub disto_k1: Option<[f64; 1]>,
+    /// Distortion coefficients of `pinhole_radial_k3`.
+    #[serde(default)]
+    pub disto_k3: Option<[f64; 3]>,
+    /// Distortion coefficients of `fisheye`.
+    #[serde(default)]
+    pub fisheye: Option<[f64; 4]>,
+    /// Distortion coefficients of `pinhole_brown_t2`,
+    /// i.e., `[k1, k2, k3, t1, t2]`.
+    #[serde(default)]
+    pub disto_t2: Option<[f64; 5]>,
+}
+
+/// A pose in [`SfmData`].
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+pub struct Pose {
+    /// The 3D rotation from world space to view space.
+    ///
+    /// It is in **row-major order**, i.e., `M[row][col]`.
+    pub rotation: [[f64; 3]; 3],
+    /// The 3D position in world space.
+    pub center: [f64; 3],
+}
+
+/// A landmark in [`SfmData`].
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+pub struct Landmark {
+    /// Position in world space.
+    #[serde(rename = "X")]
+    pub position: [f64; 3],
+    /// Observations mapped by the view ID.
+    #[serde(default)]
+    pub observations: Vec<Entry<Observation>>,
+}
+
+/// An observation in [`Landmark`].
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+pub struct Observation {
+    /// Feature ID.
+    pub id_feat: u32,
+    /// Position in image space.
+    #[serde(rename = "x")]
+    pub position: [f64; 2],
+}
+
+impl Intrinsic {
+    /// Return the COLMAP camera of the intrinsic type.
+    ///
+    /// The intrinsic type is mapped as follows:
+    /// - `pinhole` to [`CameraVariant::SimplePinhole`].
+    /// - `pinhole_radial_k1` to [`CameraVariant::SimpleRadial`].
+    /// - `pinhole_radial_k3` and `pinhole_brown_t2` to [`CameraVariant::FullOpenCv`].
+    /// - `fisheye` to [`CameraVariant::OpenCvFisheye`].
+    ///
+    /// # Errors
+    ///
+    /// It returns [`Error::UnsupportedKind`] for other intrinsic types,
+    /// e.g., `spherical`.
+    ///
+    /// It returns [`Error::MissingSymbol`] if the distortion coefficients are absent.
+    pub fn camera(
+        &self,
+        camera_id: u32,
+        kind: &str,
+    ) -> Result<Camera, Error> {
+        use CameraVariant::*;
+
+        let focal_length = self.focal_length;
+        let full_open_cv = |[k1, k2, k3, p1, p2]: [f64; 5]| FullOpenCv {
+            focal_length_x: focal_length,
+            focal_length_y: focal_length,
+            k1,
+            k2,
+            p1,
+            p2,
+            k3,
+            k4: 0.0,
+            k5: 0.0,
+            k6: 0.0,
+        };
+        let missing = |name: &str| Error::MissingSymbol(name.into());
+
+        let variant = match kind {
+            "pinhole" => SimplePinhole { focal_length },
+            "pinhole_radial_k1" => SimpleRadial {
+                focal_length,
+                k: self.disto_k1.ok_or_else(|| missing("disto_k1"))?[0],
+            },
+            "pinhole_radial_k3" => {
+                let [k1, k2, k3] = self.disto_k3.ok_or_else(|| missing("disto_k3"))?;
+                full_open_cv([k1, k2, k3, 0.0, 0.0])
+            },
+            "pinhole_brown_t2" => {
+                full_open_cv(self.disto_t2.ok_or_else(|| missing("disto_t2"))?)
+            },
+            "fisheye" => {
+                let [k1, k2, k3, k4] = self.fisheye.ok_or_else(|| missing("fisheye"))?;
+                OpenCvFisheye {
+                    focal_length_x: focal_length,
+                    focal_length_y: focal_length,
+                    k1,
+                    k2,
+                    k3,
+                    k4,
+                }
+            },
+            _ => return Err(Error::UnsupportedKind(kind.into())),
+        };
+
+        Ok(Camera {
+            camera_id,
+            width: self.width,
+            height: self.height,
+            principal_point_x: self.principal_point[0],
+            principal_point_y: self.principal_point[1],
+            variant,
+        })
+    }
+}
+
+impl SfmData {
+    /// Return the COLMAP cameras.
+    ///
+    /// The [camera ID](Camera::camera_id) is the intrinsic ID.
+    pub fn cameras(&self) -> Result<Cameras, Error> {
+        let mut names = HashMap::<u32, &str>::new();
+        self.intrinsics
+            .iter()
+            .map(|entry| {
+                let polymorphic = &entry.value;
+                let polymorphic_id = polymorphic.polymorphic_id & POLYMORPHIC_ID_MASK;
+                if let Some(name) = &polymorphic.polymorphic_name {
+                    names.insert(polymorphic_id, name);
+                }
+                let kind = names
+                    .get(&polymorphic_id)
+                    .ok_or_else(|| Error::MissingSymbol("polymorphic_name".into()))?;
+                let intrinsic = polymorphic
+                    .ptr_wrapper
+                    .data
+                    .as_ref()
+                    .ok_or_else(|| Error::MissingSymbol("data".into()))?;
+                Ok((entry.key, intrinsic.camera(entry.key, kind)?))
+            })
+            .collect()
+    }
+
+    /// Return the COLMAP images and points.
+    ///
+    /// The [image ID](Image::image_id) is the view ID,
+    /// and the [point ID](Point::point_id) is the landmark ID.
+    /// The views without poses are skipped.
+    pub fn images_and_points(&self) -> Result<(Images, Points), Error> {
+        let poses = self
+            .extrinsics
+            .iter()
+            .map(|entry| (entry.key, &entry.value))
+            .collect::<HashMap<_, _>>();
+
+        let mut images = self
+            .views()?
+            .filter_map(|view| {
+                let pose = poses.get(&view.id_pose)?;
+                Some(CString::new(view.filename.as_str()).map(|file_name| {
+                    // NOTE: The rotation is transposed to column-major order.
+                    let rotation = [0, 1, 2].map(|col| pose.rotation.map(|row| row[col]));
+                    let mut image = Image {
+                        image_id: view.id_view,
+                        camera_id: view.id_intrinsic,
+                        file_name,
+                        points: Some(vec![]),
+                        ..Default::default()
+                    };
+                    image
+                        .set_rotation(&rotation)
+                        .set_position(&pose.center, &rotation);
+                    (image.image_id, image)
+                }))
+            })
+            .collect::<Result<Images, _>>()
+            .map_err(|err| Error::InvalidUtf8(format!("{:?}", err.into_vec())))?;
+
+        let points = self
+            .structure
+            .iter()
+            .map(|entry| {
+                let point_id = entry.key as u64;
+                let track = entry
+                    .value
+                    .observations
+                    .iter()
+                    .filter_map(|observation| {
+                        let image_id = observation.key;
+                        let points = images.get_mut(&image_id)?.points.as_mut()?;
+                        points.push(ImagePoint {
+                            position: observation.value.position,
+                            point_id,
+                        });
+                        Some(TrackElement {
+                            image_id,
+                            point_index: points.len() as u32 - 1,
+                        })
+                    })
+                    .collect();
+                // NOTE: OpenMVG exports no color and no re-projection error.
+                let point = Point {
+                    point_id,
+                    position: entry.value.position,
+                    color_rgb: [0; 3],
+                    error: -1.0,
+                    track,
+                };
+                (point_id, point)
+            })
+            .collect();
+
+        Ok((images, points))
+    }
+
+    /// Return the views.
+    ///
+    /// # Errors
+    ///
+    /// It returns [`Error::MissingSymbol`] if the view data is absent.
+    pub fn views(&self) -> Result<impl Iterator<Item = &View>, Error> {
+        let views = self
+            .views
+            .iter()
+            .map(|entry| {
+                entry
+                    .value
+                    .ptr_wrapper
+                    .data
+                    .as_ref()
+                    .ok_or_else(|| Error::MissingSymbol("data".into()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(views.into_iter())
+    }
+}
+
+impl Decoder for SfmData {
+    type Err = Error;
+
+    #[inline]
+    fn decode(reader: &mut impl Read) -> Result<Self, Self::Err> {
+        let sfm_data = serde_json::from_reader(BufReader::new(reader))?;
+
+        #[cfg(all(debug_assertions, not(test)))]
+        log::debug!(target: "gausplat-loader::openmvg::sfm_data", "SfmData::decode");
+
+        Ok(sfm_data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn cameras() {
+        use super::*;
+
+        let source =
+            &mut std::fs::File::open("examples/data/openmvg/0/sfm_data.json").unwrap();
+        let sfm_data = SfmData::decode(source).unwrap();
+
+        let target = vec![
+            Camera {
+                camera_id: 0,
+                width: 8,
+                height: 8,
+                principal_point_x: 4.0,
+                principal_point_y: 4.0,
+                variant: CameraVariant::FullOpenCv {
+                    focal_length_x: 10.0,
+                    focal_length_y: 10.0,
+                    k1: 0.1,
+                    k2: 0.01,
+                    p1: 0.0,
+                    p2: 0.0,
+                    k3: 0.001,
+                    k4: 0.0,
+                    k5: 0.0,
+                    k6: 0.0,
+                },
+            },
+            Camera {
+                camera_id: 1,
+                width: 8,
+                height: 8,
+                principal_point_x: 3.5,
+                principal_point_y: 4.5,
+                variant: CameraVariant::FullOpenCv {
+                    focal_length_x: 12.0,
+                    focal_length_y: 12.0,
+                    k1: 0.2,
+                    k2: 0.02,
+                    p1: 0.003,
+                    p2: 0.004,
+                    k3: 0.002,
+                    k4: 0.0,
+                    k5: 0.0,
+                    k6: 0.0,
+                },
+            },
+            Camera {
+                camera_id: 2,
+                width: 8,
+                height: 8,
+                principal_point_x: 4.0,
+                principal_point_y: 4.0,
+                variant: CameraVariant::FullOpenCv {
+                    focal_length_x: 8.0,
+                    focal_length_y: 8.0,
+                    k1: 0.0,
+                    k2: 0.0,
+                    p1: 0.0,
+                    p2: 0.0,
+                    k3: 0.0,
+                    k4: 0.0,
+                    k5: 0.0,
+                    k6: 0.0,
+                },
+            },
+        ];
+        let output = sfm_data
+            .cameras()
+            .unwrap()
+            .into_values()
+            .collect::<Vec<_>>();
+        assert_eq!(output, target);
+    }
+
+    #[test]
+    fn cameras_on_unsupported_kind() {
+        use super::*;
+
+        let intrinsic = Intrinsic::default();
+
+        let target = true;
+        let output = matches!(
+            intrinsic.camera(0, "spherical").unwrap_err(),
+            Error::UnsupportedKind(kind) if kind == "spherical"
+        );
+        assert_eq!(output, target);
+
+        let target = true;
+        let output = matches!(
+            intrinsic.camera(0, "fisheye").unwrap_err(),
+            Error::MissingSymbol(name) if name == "fisheye"
+        );
+        assert_eq!(output, target);
+    }
+
+    #[test]
+    fn images_and_points() {
+        use super::*;
+
+        let source =
+            &mut std::fs::File::open("examples/data/openmvg/0/sfm_data.json").unwrap();
+        let sfm_data = SfmData::decode(source).unwrap();
+        let (images, points) = sfm_data.images_and_points().unwrap();
+
+        let target = vec![0, 2];
+        let output = images.keys().copied().collect::<Vec<_>>();
+        assert_eq!(output, target);
+
+        let target = ([0.0, 0.0, 5.0], [0.0, 1.0, 0.0, 0.0], [0.0, 0.0, 5.0]);
+        let image = &images[&0];
+        let output = (
+            image.translation,
+            image.quaternion,
+            image.position(&image.rotation()),
+        );
+        assert_eq!(output, target);
+
+        let target = Point {
+            point_id: 7,
+            position: [1.0, 2.0, 3.0],
+            color_rgb: [0; 3],
+            error: -1.0,
+            track: vec![
+                TrackElement {
+                    image_id: 0,
+                    point_index: 0,
+                },
+                TrackElement {
+                    image_id: 2,
+                    point_index: 0,
+                },
+            ],
+        };
+        let output = points[&7].to_owned();
+        assert_eq!(output, target);
+
+        let target = vec![ImagePoint {
+            position: [4.5, 5.0],
+            point_id: 7,
+        }];
+        let output = images[&0].points.to_owned().unwrap();
+        assert_eq!(output, target);
+    }
+}
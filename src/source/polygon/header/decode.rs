@@ -78,15 +78,15 @@ impl Decoder for Header {
                     let value = string_from_vec_ascii(value)?;
 
                     let kind = if value != "list" {
-                        ScalarPropertyKind { value }.into()
+                        value.parse::<ScalarPropertyKind>()?.into()
                     } else {
                         let mut kind = vec![read_byte_after(reader, is_space)?];
                         kind.extend(read_bytes_before(reader, is_space, 8)?);
-                        let count = string_from_vec_ascii(kind)?.into();
+                        let count = string_from_vec_ascii(kind)?.parse()?;
 
                         let mut kind = vec![read_byte_after(reader, is_space)?];
                         kind.extend(read_bytes_before(reader, is_space, 8)?);
-                        let value = string_from_vec_ascii(kind)?.into();
+                        let value = string_from_vec_ascii(kind)?.parse()?;
 
                         ListPropertyKind { count, value }.into()
                     };
@@ -516,6 +516,107 @@ mod tests {
         Header::decode(source).unwrap_err();
     }
 
+    #[test]
+    fn decode_on_property_kind_aliases() {
+        use super::*;
+
+        let source = &mut Cursor::new(
+            &b"ply\nformat ascii 1.0\nelement vertex 0\n\
+            property float32 x\nproperty list uint8 int32 y\nend_header\n"[..],
+        );
+        let header = Header::decode(source).unwrap();
+
+        let target = ScalarPropertyVariant::Float32;
+        let output = header["vertex"]["x"]
+            .kind
+            .try_unwrap_scalar_ref()
+            .unwrap()
+            .variant;
+        assert_eq!(output, target);
+
+        let target = &PropertyKind::Scalar("float".into());
+        let output = &header["vertex"]["x"].kind;
+        assert_eq!(output, target);
+
+        let target = &PropertyKind::List(("uchar", "int").into());
+        let output = &header["vertex"]["y"].kind;
+        assert_eq!(output, target);
+
+        let target = "property float32 x\nproperty list uint8 int32 y\n";
+        let output = header["vertex"].properties.to_string();
+        assert_eq!(output, target);
+
+        [
+            ("char", ["int8", "byte"].as_slice()),
+            ("uchar", &["uint8", "ubyte"]),
+            ("short", &["int16"]),
+            ("ushort", &["uint16"]),
+            ("int", &["int32"]),
+            ("uint", &["uint32"]),
+            ("long", &["int64"]),
+            ("ulong", &["uint64"]),
+            ("half", &["float16"]),
+            ("float", &["float32"]),
+            ("double", &["float64"]),
+        ]
+        .into_iter()
+        .for_each(|(name, aliases)| {
+            let target = ScalarPropertyKind::from(name);
+            aliases.iter().for_each(|alias| {
+                let output = ScalarPropertyKind::from(*alias);
+                assert_eq!(output, target);
+                assert_eq!(output.to_string(), *alias);
+            });
+        });
+    }
+
+    #[test]
+    fn decode_on_invalid_property_kind() {
+        use super::*;
+
+        let source = &mut Cursor::new(
+            &b"ply\nformat ascii 1.0\nelement vertex 0\n\
+            property duoble x\nend_header\n"[..],
+        );
+        let target = true;
+        let output = matches!(
+            Header::decode(source).unwrap_err(),
+            InvalidKind(kind) if kind == "duoble",
+        );
+        assert_eq!(output, target);
+
+        let source = &mut Cursor::new(
+            &b"ply\nformat ascii 1.0\nelement vertex 0\n\
+            property list uchar in32 y\nend_header\n"[..],
+        );
+        let target = true;
+        let output = matches!(
+            Header::decode(source).unwrap_err(),
+            InvalidKind(kind) if kind == "in32",
+        );
+        assert_eq!(output, target);
+
+        let kind = ScalarPropertyKind::register("vec2f", 8);
+        let source = &mut Cursor::new(
+            &b"ply\nformat ascii 1.0\nelement vertex 0\n\
+            property vec2f uv\nend_header\n"[..],
+        );
+        let header = Header::decode(source).unwrap();
+
+        let target = &PropertyKind::Scalar(kind);
+        let output = &header["vertex"]["uv"].kind;
+        assert_eq!(output, target);
+
+        let target = Some(8);
+        let output = header["vertex"].property_sizes().next().unwrap().ok();
+        assert_eq!(output, target);
+
+        let target = ScalarPropertyKind::from("double");
+        let output = ScalarPropertyKind::register("float64", 2);
+        assert_eq!(output, target);
+        assert_eq!(output.size(), Some(8));
+    }
+
     #[test]
     fn decode_on_no_element() {
         use super::*;
@@ -108,6 +108,29 @@ mod tests {
         }
     }
 
+    #[test]
+    fn encode_on_property_kind_aliases() {
+        use super::*;
+
+        let source = &include_bytes!(
+            "../../../../examples/data/polygon/supported-data-types-special.ply"
+        )[..];
+        let header = Header::decode(&mut Cursor::new(source)).unwrap();
+        let writer = &mut vec![];
+        header.encode(writer).unwrap();
+
+        // NOTE: The comments are not kept in the header.
+        let target = source
+            .split_inclusive(|byte| *byte == b'\n')
+            .take_while(|line| *line != b"end_header\n")
+            .filter(|line| !line.starts_with(b"comment "))
+            .chain([&b"end_header\n"[..]])
+            .collect::<Vec<_>>()
+            .concat();
+        let output = writer.to_owned();
+        assert_eq!(output, target);
+    }
+
     #[test]
     fn encode_on_empty_slice() {
         use super::*;
@@ -2,7 +2,11 @@
 
 pub use super::*;
 
-use std::sync::{LazyLock, RwLock};
+use burn_tensor::f16;
+use std::{
+    hash::{Hash, Hasher},
+    sync::{LazyLock, RwLock},
+};
 
 /// List property kind.
 #[derive(
@@ -89,25 +93,53 @@ pub struct Properties {
 }
 
 /// Scalar property kind.
-#[derive(
-    AsRef,
-    Clone,
-    Constructor,
-    Debug,
-    Default,
-    Deref,
-    DerefMut,
-    Display,
-    Eq,
-    Hash,
-    From,
-    PartialEq,
-)]
-#[display("{value}")]
-#[from(String, &str)]
+///
+/// The aliases of a standard kind are mapped to the same variant,
+/// e.g., both `float` and `float32` are [`ScalarPropertyVariant::Float32`].
+/// The other kinds are custom,
+/// and their sizes can be registered by [`ScalarPropertyKind::register`].
+///
+/// The kind keeps its name in the header, which is displayed and encoded,
+/// so decoding and re-encoding a header preserves the spelling, e.g., `float32`.
+/// Two kinds are equal if they have the same variant,
+/// or the same name for custom kinds.
+#[derive(Clone, Debug, Default, Display)]
+#[display("{name}")]
 pub struct ScalarPropertyKind {
-    /// Kind representation.
-    pub value: String,
+    /// The variant of the kind.
+    pub variant: ScalarPropertyVariant,
+    /// The name of the kind in the header.
+    pub name: String,
+}
+
+/// Scalar property variants.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, IsVariant, PartialEq)]
+pub enum ScalarPropertyVariant {
+    /// `char`, `int8` or `byte`.
+    Int8,
+    /// `uchar`, `uint8` or `ubyte`.
+    UInt8,
+    /// `short` or `int16`.
+    Int16,
+    /// `ushort` or `uint16`.
+    UInt16,
+    /// `int` or `int32`.
+    Int32,
+    /// `uint` or `uint32`.
+    UInt32,
+    /// `long` or `int64`.
+    Int64,
+    /// `ulong` or `uint64`.
+    UInt64,
+    /// `half` or `float16`.
+    Float16,
+    /// `float` or `float32`.
+    Float32,
+    /// `double` or `float64`.
+    Float64,
+    /// Custom kind, which is named by [`ScalarPropertyKind::name`].
+    #[default]
+    Custom,
 }
 
 impl Properties {
@@ -123,26 +155,263 @@ impl Properties {
     }
 
     /// Return an iterator of property sizes.
+    ///
+    /// The size of a list property is the size of its value.
     #[inline]
     pub fn property_sizes(&self) -> impl '_ + Iterator<Item = Result<usize, Error>> {
         self.values().map(|prop| {
-            prop.try_unwrap_scalar_ref()
-                .map_err(|err| InvalidKind(err.input.to_string()))?
-                .size()
-                .ok_or_else(|| InvalidKind(prop.kind.to_string()))
+            match &prop.kind {
+                PropertyKind::List(list) => &list.value,
+                PropertyKind::Scalar(scalar) => scalar,
+            }
+            .size()
+            .ok_or_else(|| InvalidKind(prop.kind.to_string()))
+        })
+    }
+}
+
+impl ListPropertyKind {
+    /// Convert the bytes of the list count into a number.
+    ///
+    /// The bytes are in native endianness.
+    ///
+    /// # Errors
+    ///
+    /// It returns [`Error::TryFromIntError`] if the count is negative or too large.
+    ///
+    /// It returns [`Error::UnsupportedKind`] if the count kind is not an integer,
+    /// or [`Error::InvalidKind`] if the count kind is unknown.
+    pub fn count_from_bytes(
+        &self,
+        datum: &[u8],
+    ) -> Result<usize, Error> {
+        use ScalarPropertyVariant::*;
+
+        Ok(match self.count.variant {
+            Int8 => read_datum::<i8>(datum)?.try_into()?,
+            UInt8 => read_datum::<u8>(datum)?.into(),
+            Int16 => read_datum::<i16>(datum)?.try_into()?,
+            UInt16 => read_datum::<u16>(datum)?.into(),
+            Int32 => read_datum::<i32>(datum)?.try_into()?,
+            UInt32 => read_datum::<u32>(datum)?.try_into()?,
+            Int64 => read_datum::<i64>(datum)?.try_into()?,
+            UInt64 => read_datum::<u64>(datum)?.try_into()?,
+            _ => return Err(self.count.unsupported_or_invalid()),
+        })
+    }
+
+    /// Convert the number into the bytes of the list count.
+    ///
+    /// The bytes are in native endianness.
+    ///
+    /// # Errors
+    ///
+    /// It returns [`Error::TryFromIntError`] if the count overflows the count kind.
+    ///
+    /// It returns [`Error::UnsupportedKind`] if the count kind is not an integer,
+    /// or [`Error::InvalidKind`] if the count kind is unknown.
+    pub fn count_to_bytes(
+        &self,
+        count: usize,
+    ) -> Result<Vec<u8>, Error> {
+        use ScalarPropertyVariant::*;
+
+        Ok(match self.count.variant {
+            Int8 => i8::try_from(count)?.to_ne_bytes().into(),
+            UInt8 => u8::try_from(count)?.to_ne_bytes().into(),
+            Int16 => i16::try_from(count)?.to_ne_bytes().into(),
+            UInt16 => u16::try_from(count)?.to_ne_bytes().into(),
+            Int32 => i32::try_from(count)?.to_ne_bytes().into(),
+            UInt32 => u32::try_from(count)?.to_ne_bytes().into(),
+            Int64 => i64::try_from(count)?.to_ne_bytes().into(),
+            UInt64 => u64::try_from(count)?.to_ne_bytes().into(),
+            _ => return Err(self.count.unsupported_or_invalid()),
         })
     }
 }
 
 impl ScalarPropertyKind {
+    /// Parse the ASCII token as the scalar property.
+    ///
+    /// It returns the bytes in native endianness,
+    /// which have the same layout as the binary format.
+    ///
+    /// # Errors
+    ///
+    /// It returns [`Error::ParseIntError`] or [`Error::ParseFloatError`]
+    /// if the token is malformed for the kind.
+    ///
+    /// It returns [`Error::UnsupportedKind`] if the kind has a size
+    /// but no ASCII representation, or [`Error::InvalidKind`] if the kind is unknown.
+    pub fn parse_ascii(
+        &self,
+        token: &str,
+    ) -> Result<Vec<u8>, Error> {
+        use ScalarPropertyVariant::*;
+
+        Ok(match self.variant {
+            Int8 => token.parse::<i8>()?.to_ne_bytes().into(),
+            UInt8 => token.parse::<u8>()?.to_ne_bytes().into(),
+            Int16 => token.parse::<i16>()?.to_ne_bytes().into(),
+            UInt16 => token.parse::<u16>()?.to_ne_bytes().into(),
+            Int32 => token.parse::<i32>()?.to_ne_bytes().into(),
+            UInt32 => token.parse::<u32>()?.to_ne_bytes().into(),
+            Int64 => token.parse::<i64>()?.to_ne_bytes().into(),
+            UInt64 => token.parse::<u64>()?.to_ne_bytes().into(),
+            Float16 => f16::from_f64(token.parse()?).to_ne_bytes().into(),
+            Float32 => token.parse::<f32>()?.to_ne_bytes().into(),
+            Float64 => token.parse::<f64>()?.to_ne_bytes().into(),
+            _ => return Err(self.unsupported_or_invalid()),
+        })
+    }
+
+    /// Format the bytes of the scalar property as an ASCII token.
+    ///
+    /// The bytes are in native endianness.
+    /// The floating-point numbers are formatted in the shortest representation
+    /// that is parsed back to the identical value by [`Self::parse_ascii`].
+    ///
+    /// # Errors
+    ///
+    /// It returns [`Error::Bytemuck`] if the bytes have a mismatched size.
+    ///
+    /// It returns [`Error::UnsupportedKind`] if the kind has a size
+    /// but no ASCII representation, or [`Error::InvalidKind`] if the kind is unknown.
+    pub fn format_ascii(
+        &self,
+        datum: &[u8],
+    ) -> Result<String, Error> {
+        use ScalarPropertyVariant::*;
+
+        Ok(match self.variant {
+            Int8 => read_datum::<i8>(datum)?.to_string(),
+            UInt8 => read_datum::<u8>(datum)?.to_string(),
+            Int16 => read_datum::<i16>(datum)?.to_string(),
+            UInt16 => read_datum::<u16>(datum)?.to_string(),
+            Int32 => read_datum::<i32>(datum)?.to_string(),
+            UInt32 => read_datum::<u32>(datum)?.to_string(),
+            Int64 => read_datum::<i64>(datum)?.to_string(),
+            UInt64 => read_datum::<u64>(datum)?.to_string(),
+            // NOTE: The debug format of floating-point numbers is the shortest
+            // round-trip representation, and it switches to the exponential notation
+            // for very large or small magnitudes.
+            Float16 => format!("{:?}", f16::from_bits(read_datum(datum)?)),
+            Float32 => format!("{:?}", read_datum::<f32>(datum)?),
+            Float64 => format!("{:?}", read_datum::<f64>(datum)?),
+            _ => return Err(self.unsupported_or_invalid()),
+        })
+    }
+
     /// Query the size of the scalar property.
+    ///
+    /// The size of a custom kind is [registered](Self::register).
     #[inline]
     pub fn size(&self) -> Option<usize> {
-        SCALAR_PROPERTY_SIZES
-            .read()
-            .unwrap()
-            .get(&self.value)
-            .copied()
+        use ScalarPropertyVariant::*;
+
+        Some(match self.variant {
+            Int8 | UInt8 => 1,
+            Int16 | UInt16 | Float16 => 2,
+            Int32 | UInt32 | Float32 => 4,
+            Int64 | UInt64 | Float64 => 8,
+            Custom => {
+                return CUSTOM_SCALAR_PROPERTY_SIZES
+                    .read()
+                    .unwrap()
+                    .get(&self.name)
+                    .copied()
+            },
+        })
+    }
+
+    /// Register the size of a custom kind.
+    ///
+    /// The size of a standard kind is fixed, so it is not registered.
+    pub fn register(
+        name: impl Into<String>,
+        size: usize,
+    ) -> Self {
+        let kind = Self::from(name.into());
+        if kind.variant.is_custom() {
+            CUSTOM_SCALAR_PROPERTY_SIZES
+                .write()
+                .unwrap()
+                .insert(kind.name.to_owned(), size);
+        }
+        kind
+    }
+
+    /// Return [`Error::UnsupportedKind`] if the kind has a size,
+    /// or [`Error::InvalidKind`] otherwise.
+    #[inline]
+    fn unsupported_or_invalid(&self) -> Error {
+        match self.size() {
+            Some(_) => UnsupportedKind(self.to_string()),
+            None => InvalidKind(self.to_string()),
+        }
+    }
+}
+
+impl Eq for ScalarPropertyKind {}
+
+impl From<&str> for ScalarPropertyKind {
+    #[inline]
+    fn from(name: &str) -> Self {
+        name.to_owned().into()
+    }
+}
+
+impl From<String> for ScalarPropertyKind {
+    #[inline]
+    fn from(name: String) -> Self {
+        Self {
+            variant: get_standard_variant(&name).unwrap_or_default(),
+            name,
+        }
+    }
+}
+
+impl FromStr for ScalarPropertyKind {
+    type Err = Error;
+
+    /// Parsing the kind name in the header.
+    ///
+    /// # Errors
+    ///
+    /// It returns [`Error::InvalidKind`] if the kind is neither standard nor registered.
+    #[inline]
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        let kind = Self::from(name);
+        match kind.size() {
+            Some(_) => Ok(kind),
+            None => Err(InvalidKind(name.into())),
+        }
+    }
+}
+
+impl Hash for ScalarPropertyKind {
+    #[inline]
+    fn hash<H: Hasher>(
+        &self,
+        state: &mut H,
+    ) {
+        self.variant.hash(state);
+        if self.variant.is_custom() {
+            self.name.hash(state);
+        }
+    }
+}
+
+impl PartialEq for ScalarPropertyKind {
+    /// The names of standard kinds are not compared,
+    /// since they can be any of the aliases.
+    #[inline]
+    fn eq(
+        &self,
+        other: &Self,
+    ) -> bool {
+        self.variant == other.variant
+            && (!self.variant.is_custom() || self.name == other.name)
     }
 }
 
@@ -163,39 +432,36 @@ impl fmt::Display for Properties {
     }
 }
 
-/// A map of scalar property kind to its size.
-pub static SCALAR_PROPERTY_SIZES: LazyLock<RwLock<IndexMap<String, usize>>> =
-    LazyLock::new(|| {
-        [
-            // Common scalar types
-            ("char".into(), 1),
-            ("uchar".into(), 1),
-            ("short".into(), 2),
-            ("ushort".into(), 2),
-            ("int".into(), 4),
-            ("uint".into(), 4),
-            ("float".into(), 4),
-            ("double".into(), 8),
-            // General scalar types
-            ("int8".into(), 1),
-            ("uint8".into(), 1),
-            ("int16".into(), 2),
-            ("uint16".into(), 2),
-            ("int32".into(), 4),
-            ("uint32".into(), 4),
-            ("float32".into(), 4),
-            ("float64".into(), 8),
-            // Special scalar types
-            ("byte".into(), 1),
-            ("ubyte".into(), 1),
-            ("half".into(), 2),
-            ("long".into(), 8),
-            ("ulong".into(), 8),
-            ("float16".into(), 2),
-            ("int64".into(), 8),
-            ("uint64".into(), 8),
-        ]
-        .into_iter()
-        .collect::<IndexMap<_, _>>()
-        .into()
-    });
+/// Reading a scalar datum from the bytes in native endianness.
+#[inline]
+fn read_datum<T: bytemuck::Pod>(datum: &[u8]) -> Result<T, Error> {
+    if datum.len() != size_of::<T>() {
+        return Err(bytemuck::PodCastError::SizeMismatch.into());
+    }
+    Ok(bytemuck::pod_read_unaligned(datum))
+}
+
+/// Get the standard variant by any of its names.
+#[inline]
+fn get_standard_variant(name: &str) -> Option<ScalarPropertyVariant> {
+    use ScalarPropertyVariant::*;
+
+    Some(match name {
+        "char" | "int8" | "byte" => Int8,
+        "uchar" | "uint8" | "ubyte" => UInt8,
+        "short" | "int16" => Int16,
+        "ushort" | "uint16" => UInt16,
+        "int" | "int32" => Int32,
+        "uint" | "uint32" => UInt32,
+        "long" | "int64" => Int64,
+        "ulong" | "uint64" => UInt64,
+        "half" | "float16" => Float16,
+        "float" | "float32" => Float32,
+        "double" | "float64" => Float64,
+        _ => return None,
+    })
+}
+
+/// A map of custom scalar property kind to its size.
+static CUSTOM_SCALAR_PROPERTY_SIZES: LazyLock<RwLock<IndexMap<String, usize>>> =
+    LazyLock::new(Default::default);
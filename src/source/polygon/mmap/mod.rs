@@ -0,0 +1,370 @@
+//! Polygon memory-mapped module.
+//!
+//! It maps a PLY file into memory without decoding the payload up front.
+
+pub mod view;
+
+pub use super::*;
+pub use crate::function::Opener;
+pub use object::*;
+pub use view::*;
+
+use memmap2::Mmap;
+use payload::decode::decode_element_rows;
+use std::{fmt, fs, io::Cursor, path::Path};
+
+/// Memory-mapped polygon object.
+///
+/// The properties of a scalar-only element in binary native-endian format are
+/// strided views into the mapped file.
+/// The other elements are decoded into owned data at opening.
+pub struct MappedObject {
+    /// Header.
+    pub header: Header,
+    elements: Vec<ElementStorage>,
+    mmap: Mmap,
+}
+
+/// The storage of an element.
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum ElementStorage {
+    /// The data are in the mapped file.
+    Mapped {
+        /// The start of each property in the mapped file.
+        starts: Vec<usize>,
+        /// The size of a row.
+        stride: usize,
+    },
+    /// The data are decoded.
+    Owned {
+        /// The data of each property.
+        data: Vec<Vec<u8>>,
+        /// The offsets of each property.
+        offsets: Vec<Vec<usize>>,
+    },
+}
+
+impl MappedObject {
+    /// Create the object from the `mmap` of a PLY file.
+    ///
+    /// # Errors
+    ///
+    /// It returns the errors of decoding the header,
+    /// and the errors of decoding the elements not mapped.
+    ///
+    /// It returns [`Error::OutOfBounds`] if the file is too short for the mapped elements.
+    pub fn new(mmap: Mmap) -> Result<Self, Error> {
+        let reader = &mut Cursor::new(&mmap[..]);
+        let header = Header::decode(reader)?;
+        let mut start = reader.position() as usize;
+
+        let elements = header
+            .values()
+            .map(|elem| {
+                let is_mapped = header.format.is_binary_native_endian()
+                    && !elem.values().any(|prop| prop.is_list());
+                if is_mapped {
+                    let (storage, end) = get_mapped_storage(elem, start)?;
+                    start = end;
+                    if start > mmap.len() {
+                        return Err(Error::OutOfBounds(
+                            start,
+                            mmap.len(),
+                            format!("element {}", elem.name),
+                        ));
+                    }
+                    return Ok(storage);
+                }
+
+                let reader = &mut Cursor::new(&mmap[start..]);
+                let selections = vec![true; elem.len()];
+                let (data, offsets) = decode_element_rows(
+                    reader,
+                    elem,
+                    &header.format,
+                    elem.count,
+                    &selections,
+                )?;
+                start += reader.position() as usize;
+                Ok(ElementStorage::Owned { data, offsets })
+            })
+            .collect::<Result<_, Error>>()?;
+
+        #[cfg(all(debug_assertions, not(test)))]
+        log::debug!(target: "gausplat-loader::polygon::mmap", "MappedObject::new");
+
+        Ok(Self {
+            header,
+            elements,
+            mmap,
+        })
+    }
+
+    /// Get the view of a property by the names of element and property.
+    pub fn elem_prop<Q: AsRef<str>>(
+        &self,
+        elem_name: Q,
+        prop_name: Q,
+    ) -> Option<PropertyView<'_>> {
+        let (elem_index, _, elem) = self.header.get_full(elem_name.as_ref())?;
+        let (prop_index, _, meta) = elem.get_full(prop_name.as_ref())?;
+        self.get_property_view(elem_index, prop_index, meta)
+    }
+
+    /// Return an iterator over the views of the properties of an element.
+    pub fn props<Q: AsRef<str>>(
+        &self,
+        elem_name: Q,
+    ) -> Option<impl '_ + Iterator<Item = PropertyView<'_>>> {
+        let (elem_index, _, elem) = self.header.get_full(elem_name.as_ref())?;
+        Some(elem.values().enumerate().map(move |(prop_index, meta)| {
+            // NOTE: The indices are guaranteed to be valid.
+            self.get_property_view(elem_index, prop_index, meta)
+                .unwrap()
+        }))
+    }
+
+    /// Check if the element is mapped rather than owned.
+    pub fn is_mapped<Q: AsRef<str>>(
+        &self,
+        elem_name: Q,
+    ) -> Option<bool> {
+        let elem_index = self.header.get_index_of(elem_name.as_ref())?;
+        Some(matches!(
+            self.elements.get(elem_index)?,
+            ElementStorage::Mapped { .. }
+        ))
+    }
+
+    /// Get the view of a property by the indices.
+    fn get_property_view<'m>(
+        &'m self,
+        elem_index: usize,
+        prop_index: usize,
+        meta: &'m Property,
+    ) -> Option<PropertyView<'m>> {
+        let elem = self.header.get_index(elem_index)?.1;
+        let count = elem.count;
+        let size = elem.property_sizes().nth(prop_index)?.ok()?;
+        Some(match self.elements.get(elem_index)? {
+            ElementStorage::Mapped { starts, stride } => {
+                let start = *starts.get(prop_index)?;
+                let end = match count {
+                    0 => start,
+                    _ => (count - 1)
+                        .checked_mul(*stride)?
+                        .checked_add(start)?
+                        .checked_add(size)?,
+                };
+                PropertyView {
+                    meta,
+                    data: self.mmap.get(start..end)?,
+                    offsets: &[],
+                    count,
+                    size,
+                    stride: *stride,
+                }
+            },
+            ElementStorage::Owned { data, offsets } => {
+                let data = data.get(prop_index)?;
+                let offsets = offsets
+                    .get(prop_index)
+                    .map(Vec::as_slice)
+                    .unwrap_or_default();
+                // NOTE: The data of a list property are the flat values.
+                let count = match offsets.last() {
+                    Some(count) => *count,
+                    None => count,
+                };
+                PropertyView {
+                    meta,
+                    data,
+                    offsets,
+                    count,
+                    size,
+                    stride: size,
+                }
+            },
+        })
+    }
+}
+
+impl Opener for MappedObject {
+    /// Mapping the PLY file at the `path` in read-only mode.
+    ///
+    /// The file should not be modified while it is mapped.
+    fn open(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        if !path.is_file() {
+            return Err(Error::MissingFile(path.to_owned()));
+        }
+        let file = fs::File::open(path)?;
+
+        // NOTE: Mapping is unsafe since the file can be modified by other processes.
+        // The undefined behavior is the same as modifying the file while reading.
+        let mmap = unsafe { Mmap::map(&file)? };
+        Self::new(mmap)
+    }
+}
+
+impl fmt::Debug for MappedObject {
+    #[inline]
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        f.debug_struct("MappedObject")
+            .field("header", &self.header)
+            .field("mmap.len()", &self.mmap.len())
+            .finish()
+    }
+}
+
+/// Get the mapped storage of a scalar-only element and its end.
+///
+/// # Errors
+///
+/// It returns [`Error::OutOfBounds`] if the end overflows.
+fn get_mapped_storage(
+    elem: &Element,
+    start: usize,
+) -> Result<(ElementStorage, usize), Error> {
+    let sizes = elem.property_sizes().collect::<Result<Vec<_>, _>>()?;
+    let stride = sizes.iter().sum::<usize>();
+    let end = elem
+        .count
+        .checked_mul(stride)
+        .and_then(|size| size.checked_add(start))
+        .ok_or_else(|| {
+            Error::OutOfBounds(
+                elem.count,
+                (usize::MAX - start) / stride,
+                format!("element {}", elem.name),
+            )
+        })?;
+    let starts = sizes
+        .iter()
+        .scan(start, |start, size| {
+            let current = *start;
+            *start += size;
+            Some(current)
+        })
+        .collect();
+    let storage = ElementStorage::Mapped { starts, stride };
+    Ok((storage, end))
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn open_on_all_examples() {
+        use super::*;
+
+        [
+            "another-cube.greg-turk.ascii.ply",
+            "another-cube.greg-turk.binary-be.ply",
+            "another-cube.greg-turk.binary-le.ply",
+            "another-cube.greg-turk.zeros.binary-le.ply",
+            "empty-element.ascii.ply",
+            "empty-element.binary-le.ply",
+            "supported-data-types-common.ply",
+            "supported-data-types-special.ply",
+            "triangle.binary-be.ply",
+            "triangle.binary-le.ply",
+        ]
+        .into_iter()
+        .for_each(|name| {
+            let path = format!("examples/data/polygon/{name}");
+            let object = Object::decode(&mut fs::File::open(&path).unwrap()).unwrap();
+            let mapped = MappedObject::open(&path).unwrap();
+
+            let target = &object.header;
+            let output = &mapped.header;
+            assert_eq!(output, target, "{name}");
+
+            object.elems().for_each(|elem| {
+                let target = elem.props().map(|p| p.data.to_owned()).collect::<Vec<_>>();
+                let output = mapped
+                    .props(&elem.meta.name)
+                    .unwrap()
+                    .map(|p| p.to_bytes())
+                    .collect::<Vec<_>>();
+                assert_eq!(output, target, "{name}");
+            });
+        });
+    }
+
+    #[test]
+    fn open_on_example_triangle() {
+        use super::*;
+
+        let mapped =
+            MappedObject::open("examples/data/polygon/triangle.binary-le.ply").unwrap();
+
+        let target = Some(cfg!(target_endian = "little"));
+        let output = mapped.is_mapped("vertex");
+        assert_eq!(output, target);
+
+        let target = &[0.1200019046664238, 0.0, -0.23999999463558197];
+        let output = mapped.elem_prop("vertex", "x").unwrap();
+        let output = output.cast::<f32>().unwrap();
+        let output = &output.iter().map(|x| *x as f64).collect::<Vec<_>>()[..];
+        assert_eq!(output, target);
+
+        let target = None;
+        let output = mapped.elem_prop("vertex", "w");
+        assert_eq!(output, target);
+
+        let target = None;
+        let output = mapped.is_mapped("vretex");
+        assert_eq!(output, target);
+    }
+
+    #[test]
+    fn new_on_huge_element() {
+        use super::*;
+
+        let source = b"ply\nformat binary_little_endian 1.0\n\
+            element a 18446744073709551615\nproperty double x\nend_header\n";
+        let mut mmap = memmap2::MmapMut::map_anon(source.len()).unwrap();
+        mmap.copy_from_slice(source);
+
+        let target = true;
+        let output = MappedObject::new(mmap.make_read_only().unwrap());
+        let output = if cfg!(target_endian = "little") {
+            matches!(
+                output.unwrap_err(),
+                Error::OutOfBounds(_, _, elem) if elem == "element a",
+            )
+        } else {
+            output.is_err()
+        };
+        assert_eq!(output, target);
+    }
+
+    #[test]
+    fn new_on_truncated_payload() {
+        use super::*;
+
+        let path = "examples/data/polygon/triangle.binary-le.ply";
+        let source = fs::read(path).unwrap();
+        let mut mmap = memmap2::MmapMut::map_anon(source.len() - 1).unwrap();
+        mmap.copy_from_slice(&source[..source.len() - 1]);
+
+        let target = true;
+        let output = MappedObject::new(mmap.make_read_only().unwrap());
+        let output = if cfg!(target_endian = "little") {
+            matches!(output.unwrap_err(), Error::OutOfBounds(..))
+        } else {
+            matches!(output.unwrap_err(), Error::Io(_))
+        };
+        assert_eq!(output, target);
+
+        let target = true;
+        let output = matches!(
+            MappedObject::open("examples/data/polygon/missing.ply").unwrap_err(),
+            Error::MissingFile(_),
+        );
+        assert_eq!(output, target);
+    }
+}
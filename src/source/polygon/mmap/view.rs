@@ -0,0 +1,163 @@
+//! Polygon memory-mapped view implementation.
+
+pub use super::*;
+pub use bytemuck::Pod;
+
+use bytemuck::{pod_read_unaligned, try_cast_slice, PodCastError};
+use std::borrow::Cow;
+
+/// Property view.
+///
+/// It is a strided view into the mapped file or the owned data.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct PropertyView<'m> {
+    /// Property metadata.
+    pub meta: &'m Property,
+    /// Property data from the first value to the end of the last value.
+    pub data: &'m [u8],
+    /// Property offsets if it is a list property.
+    ///
+    /// See [`ListPayload::offsets`](crate::source::polygon::payload::ListPayload::offsets).
+    pub offsets: &'m [usize],
+    /// The number of values.
+    ///
+    /// It is the number of flat values if it is a list property.
+    pub count: usize,
+    /// The size of a value.
+    pub size: usize,
+    /// The distance between the starts of two adjacent values.
+    pub stride: usize,
+}
+
+impl<'m> PropertyView<'m> {
+    /// Returns the number of values.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    /// Check if there is no value.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Check if the values are adjacent.
+    #[inline]
+    pub fn is_contiguous(&self) -> bool {
+        self.stride == self.size
+    }
+
+    /// Get the bytes of a value in native endianness.
+    #[inline]
+    pub fn get(
+        &self,
+        index: usize,
+    ) -> Option<&'m [u8]> {
+        if index >= self.count {
+            return None;
+        }
+        let start = index * self.stride;
+        self.data.get(start..start + self.size)
+    }
+
+    /// Return an iterator over the bytes of values in native endianness.
+    #[inline]
+    pub fn iter(&self) -> impl 'm + Iterator<Item = &'m [u8]> {
+        let view = *self;
+        (0..self.count).filter_map(move |index| view.get(index))
+    }
+
+    /// Cast the property data to a slice of the kind.
+    ///
+    /// It borrows the data if they are contiguous and aligned,
+    /// or it copies them otherwise.
+    ///
+    /// # Errors
+    ///
+    /// It returns [`Error::Bytemuck`] if the size of the kind mismatches.
+    pub fn cast<T: Pod>(&self) -> Result<Cow<'m, [T]>, Error> {
+        if size_of::<T>() != self.size {
+            return Err(PodCastError::SizeMismatch.into());
+        }
+        if self.is_contiguous() {
+            if let Ok(values) = try_cast_slice(self.data) {
+                return Ok(Cow::Borrowed(values));
+            }
+        }
+        Ok(Cow::Owned(self.iter().map(pod_read_unaligned).collect()))
+    }
+
+    /// Return an iterator over the values of the kind.
+    ///
+    /// # Errors
+    ///
+    /// It returns [`Error::Bytemuck`] if the size of the kind mismatches.
+    pub fn iter_of_kind<T: Pod>(&self) -> Result<impl 'm + Iterator<Item = T>, Error> {
+        if size_of::<T>() != self.size {
+            return Err(PodCastError::SizeMismatch.into());
+        }
+        Ok(self.iter().map(pod_read_unaligned))
+    }
+
+    /// Copy the values into contiguous bytes in native endianness.
+    #[inline]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        if self.is_contiguous() {
+            return self.data.to_owned();
+        }
+        self.iter().flatten().copied().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn cast_and_iter_on_strided_data() {
+        use super::*;
+
+        let meta = &Property {
+            kind: PropertyKind::Scalar("ushort".into()),
+            name: "y".into(),
+        };
+        let data = &[1_u8, 2, 0, 3, 4, 0, 5, 6, 0];
+        let view = PropertyView {
+            meta,
+            data: &data[..8],
+            offsets: &[],
+            count: 3,
+            size: 2,
+            stride: 3,
+        };
+
+        let target = vec![&[1, 2][..], &[3, 4], &[5, 6]];
+        let output = view.iter().collect::<Vec<_>>();
+        assert_eq!(output, target);
+
+        let target = [
+            u16::from_ne_bytes([1, 2]),
+            u16::from_ne_bytes([3, 4]),
+            u16::from_ne_bytes([5, 6]),
+        ];
+        let output = view.cast::<u16>().unwrap();
+        assert_eq!(*output, target);
+
+        let output = view.iter_of_kind::<u16>().unwrap().collect::<Vec<_>>();
+        assert_eq!(output, target);
+
+        let target = None;
+        let output = view.get(3);
+        assert_eq!(output, target);
+
+        let target = vec![1, 2, 3, 4, 5, 6];
+        let output = view.to_bytes();
+        assert_eq!(output, target);
+
+        let target = true;
+        let output = matches!(
+            view.cast::<u32>().unwrap_err(),
+            Error::Bytemuck(PodCastError::SizeMismatch),
+        );
+        assert_eq!(output, target);
+    }
+}
@@ -60,13 +60,19 @@
 #![doc = include_str!("LICENSE.md")]
 
 pub mod header;
+pub mod mmap;
 pub mod object;
 pub mod payload;
+pub mod splat;
+pub mod stream;
 
 pub use crate::{
     error::Error,
     function::{Decoder, DecoderWith, Encoder},
 };
 pub use header::{Format, Header};
+pub use mmap::{MappedObject, PropertyView};
 pub use object::Object;
 pub use payload::Payload;
+pub use splat::{GaussianSplat, GaussianSplatBuilder, GaussianSplatTensors};
+pub use stream::{ElementChunk, PlyReader, PlyWriter};
@@ -12,6 +12,10 @@ pub struct ElementEntry<'e> {
     pub meta: &'e Element,
     /// Element data.
     pub data: &'e Vec<Vec<u8>>,
+    /// Element offsets of list properties.
+    ///
+    /// It is empty if the payload is [scalar](Payload::Scalar).
+    pub offsets: &'e [Vec<usize>],
 }
 
 /// Element entry (mutable).
@@ -30,6 +34,10 @@ pub struct PropertyEntry<'p> {
     pub meta: &'p Property,
     /// Property data.
     pub data: &'p Vec<u8>,
+    /// Property offsets if it is a list property.
+    ///
+    /// See [`ListPayload::offsets`](crate::source::polygon::payload::ListPayload::offsets).
+    pub offsets: &'p [usize],
 }
 
 /// Property entry (mutable).
@@ -148,6 +156,23 @@ impl<'p> PropertyEntry<'p> {
     pub fn cast<T: Pod>(&'p self) -> Result<&'p [T], Error> {
         self.as_kind()
     }
+
+    /// Get the list values of an element as a slice of the kind.
+    #[doc(alias = "get_list_of_kind")]
+    #[inline]
+    pub fn list<T: Pod>(
+        &'p self,
+        index: usize,
+    ) -> Result<&'p [T], Error> {
+        self.get_list_of_kind(index)
+    }
+
+    /// Return an iterator over the list values of elements as slices of the kind.
+    #[doc(alias = "iter_lists_of_kind")]
+    #[inline]
+    pub fn lists<T: Pod>(&'p self) -> Result<impl Iterator<Item = &'p [T]>, Error> {
+        self.iter_lists_of_kind()
+    }
 }
 
 /// Short named mutators
@@ -169,14 +194,13 @@ impl Object {
         name: Q,
     ) -> Option<ElementEntry<'_>> {
         let (index, _, meta) = self.header.get_full(name.as_ref())?;
-        // NOTE: Currently, there is only scalar payload implemented.
-        let data = self
-            .payload
-            .try_unwrap_scalar_ref()
-            .unwrap()
-            .data
-            .get(index)?;
-        Some(ElementEntry { meta, data })
+        let data = self.payload.data().get(index)?;
+        let offsets = self.get_offsets(index);
+        Some(ElementEntry {
+            meta,
+            data,
+            offsets,
+        })
     }
 
     /// Get a mutable element by name.
@@ -186,13 +210,7 @@ impl Object {
         name: Q,
     ) -> Option<ElementEntryMut<'_>> {
         let (index, _, meta) = self.header.get_full_mut(name.as_ref())?;
-        // NOTE: Currently, there is only scalar payload implemented.
-        let data = self
-            .payload
-            .try_unwrap_scalar_mut()
-            .unwrap()
-            .data
-            .get_mut(index)?;
+        let data = self.payload.data_mut().get_mut(index)?;
         Some(ElementEntryMut { meta, data })
     }
 
@@ -204,16 +222,16 @@ impl Object {
         property_name: Q,
     ) -> Option<PropertyEntry<'p>> {
         let (index, _, meta) = self.header.get_full(element_name.as_ref())?;
-        // NOTE: Currently, there is only scalar payload implemented.
-        let data = self
-            .payload
-            .try_unwrap_scalar_ref()
-            .unwrap()
-            .data
-            .get(index)?;
+        let data = self.payload.data().get(index)?;
+        let offsets = self.get_offsets(index);
         let (index, _, meta) = meta.get_full(property_name.as_ref())?;
         let data = data.get(index)?;
-        Some(PropertyEntry { meta, data })
+        let offsets = offsets.get(index).map(Vec::as_slice).unwrap_or_default();
+        Some(PropertyEntry {
+            meta,
+            data,
+            offsets,
+        })
     }
 
     /// Get a mutable property of an element.
@@ -224,13 +242,7 @@ impl Object {
         property_name: Q,
     ) -> Option<PropertyEntryMut<'p>> {
         let (index, _, meta) = self.header.get_full_mut(element_name.as_ref())?;
-        // NOTE: Currently, there is only scalar payload implemented.
-        let data = self
-            .payload
-            .try_unwrap_scalar_mut()
-            .unwrap()
-            .data
-            .get_mut(index)?;
+        let data = self.payload.data_mut().get_mut(index)?;
         let (index, _, meta) = meta.get_full_mut(property_name.as_ref())?;
         let data = data.get_mut(index)?;
         Some(PropertyEntryMut { meta, data })
@@ -240,31 +252,40 @@ impl Object {
     #[doc(alias = "elems")]
     #[inline]
     pub fn iter_elements(&self) -> impl Iterator<Item = ElementEntry<'_>> {
-        // NOTE: Currently, there is only scalar payload implemented.
         self.header
             .elements
             .values()
-            .zip(self.payload.try_unwrap_scalar_ref().unwrap().data.iter())
-            .map(Into::into)
+            .zip(self.payload.data().iter().enumerate())
+            .map(|(meta, (index, data))| ElementEntry {
+                meta,
+                data,
+                offsets: self.get_offsets(index),
+            })
     }
 
     /// Return an iterator over mutable elements.
     #[doc(alias = "elems_mut")]
     #[inline]
     pub fn iter_mut_elements(&mut self) -> impl Iterator<Item = ElementEntryMut<'_>> {
-        // NOTE: Currently, there is only scalar payload implemented.
         self.header
             .elements
             .values_mut()
-            .zip(
-                self.payload
-                    .try_unwrap_scalar_mut()
-                    .unwrap()
-                    .data
-                    .iter_mut(),
-            )
+            .zip(self.payload.data_mut().iter_mut())
             .map(Into::into)
     }
+
+    /// Get the offsets of list properties of an element by index.
+    #[inline]
+    fn get_offsets(
+        &self,
+        index: usize,
+    ) -> &[Vec<usize>] {
+        self.payload
+            .offsets()
+            .and_then(|offsets| offsets.get(index))
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
 }
 
 /// Long named accessors
@@ -278,7 +299,16 @@ impl<'e, 'p: 'e> ElementEntry<'e> {
     ) -> Option<PropertyEntry<'p>> {
         let (index, _, meta) = self.meta.get_full(name.as_ref())?;
         let data = self.data.get(index)?;
-        Some(PropertyEntry { meta, data })
+        let offsets = self
+            .offsets
+            .get(index)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        Some(PropertyEntry {
+            meta,
+            data,
+            offsets,
+        })
     }
 
     /// Return an iterator over properties.
@@ -288,8 +318,16 @@ impl<'e, 'p: 'e> ElementEntry<'e> {
         self.meta
             .properties
             .values()
-            .zip(self.data.iter())
-            .map(Into::into)
+            .zip(self.data.iter().enumerate())
+            .map(|(meta, (index, data))| PropertyEntry {
+                meta,
+                data,
+                offsets: self
+                    .offsets
+                    .get(index)
+                    .map(Vec::as_slice)
+                    .unwrap_or_default(),
+            })
     }
 }
 
@@ -329,6 +367,71 @@ impl<'p> PropertyEntry<'p> {
     pub fn as_kind<T: Pod>(&'p self) -> Result<&'p [T], Error> {
         Ok(try_cast_slice(self.data)?)
     }
+
+    /// Get the list values of an element as a slice of the kind.
+    ///
+    /// # Errors
+    ///
+    /// It returns [`Error::InvalidKind`] if it is not a list property.
+    ///
+    /// It returns [`Error::OutOfBounds`] if the index or the offsets are out of bounds.
+    #[doc(alias = "list")]
+    pub fn get_list_of_kind<T: Pod>(
+        &'p self,
+        index: usize,
+    ) -> Result<&'p [T], Error> {
+        let values = self.list_values()?;
+        let (start, end) = self
+            .offsets
+            .get(index)
+            .zip(self.offsets.get(index + 1))
+            .ok_or_else(|| {
+                OutOfBounds(index, self.offsets.len().saturating_sub(1), "list".into())
+            })?;
+        values
+            .get(*start..*end)
+            .ok_or_else(|| OutOfBounds(*end, values.len(), format!("list index {index}")))
+    }
+
+    /// Return an iterator over the list values of elements as slices of the kind.
+    ///
+    /// # Errors
+    ///
+    /// It returns [`Error::InvalidKind`] if it is not a list property.
+    ///
+    /// It returns [`Error::OutOfBounds`] if the offsets are out of bounds.
+    #[doc(alias = "lists")]
+    pub fn iter_lists_of_kind<T: Pod>(
+        &'p self
+    ) -> Result<impl Iterator<Item = &'p [T]>, Error> {
+        let values = self.list_values()?;
+        if let Some(index) = self
+            .offsets
+            .windows(2)
+            .position(|range| range[0] > range[1] || range[1] > values.len())
+        {
+            let end = self.offsets[index + 1];
+            return Err(OutOfBounds(
+                end,
+                values.len(),
+                format!("list index {index}"),
+            ));
+        }
+        // NOTE: The offsets are guaranteed to be valid.
+        Ok(self
+            .offsets
+            .windows(2)
+            .map(|range| &values[range[0]..range[1]]))
+    }
+
+    /// Cast the list property data to a slice of the kind.
+    #[inline]
+    fn list_values<T: Pod>(&'p self) -> Result<&'p [T], Error> {
+        if !self.meta.is_list() {
+            return Err(InvalidKind(self.meta.kind.to_string()));
+        }
+        self.as_kind()
+    }
 }
 
 /// Long named mutators
@@ -170,34 +170,319 @@ mod tests {
     }
 
     #[test]
-    #[should_panic]
-    fn decode_on_no_implemented_ascii() {
+    fn decode_on_ascii_example_empty_element() {
         use super::*;
 
         let source = &mut Cursor::new(
             &include_bytes!(
-                "../../../../examples/data/polygon/another-cube.greg-turk.ascii.ply"
+                "../../../../examples/data/polygon/empty-element.binary-le.ply"
             )[..],
         );
+        let target = Object::decode(source).unwrap();
+
+        let source = &mut Cursor::new(
+            &include_bytes!("../../../../examples/data/polygon/empty-element.ascii.ply")
+                [..],
+        );
+        let output = Object::decode(source).unwrap();
+        assert_eq!(output.payload, target.payload);
+
+        let target = &[0, 1, 2, 3, 2, 1];
+        let output = output.elem_prop("edge", "vertex1").unwrap();
+        assert_eq!(output.cast::<u32>().unwrap(), target);
+    }
+
+    #[test]
+    fn decode_on_ascii_scalar_kinds() {
+        use super::*;
+
+        let source = &mut Cursor::new(
+            &b"ply\nformat ascii 1.0\n\
+            element point 2\n\
+            property char a\nproperty uchar b\n\
+            property short c\nproperty ushort d\n\
+            property int e\nproperty uint f\n\
+            property long g\nproperty ulong h\n\
+            property half i\nproperty float j\nproperty double k\n\
+            end_header\n\
+            -128 255 -32768 65535 -2147483648 4294967295 \
+            -9223372036854775808 18446744073709551615 -0.5 1e-3 -1.1e200\n\
+            +1 001 2\t3 4 5\r\n6 7 65504 -inf 0.1"[..],
+        );
+        let object = Object::decode(source).unwrap();
+        let point = object.elem("point").unwrap();
+
+        let target = &[-128_i8, 1];
+        let output = point.prop("a").unwrap();
+        assert_eq!(output.cast::<i8>().unwrap(), target);
+        let target = &[255_u8, 1];
+        let output = point.prop("b").unwrap();
+        assert_eq!(output.cast::<u8>().unwrap(), target);
+        let target = &[-32768_i16, 2];
+        let output = point.prop("c").unwrap();
+        assert_eq!(output.cast::<i16>().unwrap(), target);
+        let target = &[65535_u16, 3];
+        let output = point.prop("d").unwrap();
+        assert_eq!(output.cast::<u16>().unwrap(), target);
+        let target = &[-2147483648_i32, 4];
+        let output = point.prop("e").unwrap();
+        assert_eq!(output.cast::<i32>().unwrap(), target);
+        let target = &[4294967295_u32, 5];
+        let output = point.prop("f").unwrap();
+        assert_eq!(output.cast::<u32>().unwrap(), target);
+        let target = &[i64::MIN, 6];
+        let output = point.prop("g").unwrap();
+        assert_eq!(output.cast::<i64>().unwrap(), target);
+        let target = &[u64::MAX, 7];
+        let output = point.prop("h").unwrap();
+        assert_eq!(output.cast::<u64>().unwrap(), target);
+        let target = &[0xb800_u16, 0x7bff];
+        let output = point.prop("i").unwrap();
+        assert_eq!(output.cast::<u16>().unwrap(), target);
+        let target = &[1e-3_f32, f32::NEG_INFINITY];
+        let output = point.prop("j").unwrap();
+        assert_eq!(output.cast::<f32>().unwrap(), target);
+        let target = &[-1.1e200_f64, 0.1];
+        let output = point.prop("k").unwrap();
+        assert_eq!(output.cast::<f64>().unwrap(), target);
+    }
+
+    #[test]
+    fn decode_on_ascii_invalid_token() {
+        use super::*;
+        use std::io::ErrorKind::*;
+
+        let source = &mut Cursor::new(
+            &b"ply\nformat ascii 1.0\n\
+            element point 2\n\
+            property uchar x\n\
+            end_header\n\
+            255 256\n"[..],
+        );
+        let target = true;
+        let output = matches!(Object::decode(source).unwrap_err(), ParseIntError(_));
+        assert_eq!(output, target);
+
+        let source = &mut Cursor::new(
+            &b"ply\nformat ascii 1.0\n\
+            element point 1\n\
+            property float x\n\
+            end_header\n\
+            1.0.0\n"[..],
+        );
+        let target = true;
+        let output = matches!(Object::decode(source).unwrap_err(), ParseFloatError(_));
+        assert_eq!(output, target);
+
+        let source = &mut Cursor::new(
+            &b"ply\nformat ascii 1.0\n\
+            element point 2\n\
+            property int x\n\
+            end_header\n\
+            1 \n"[..],
+        );
+        let target = true;
+        let output = matches!(Object::decode(source).unwrap_err(), Io(e) if e.kind() == UnexpectedEof);
+        assert_eq!(output, target);
+
+        let source = &mut Cursor::new(
+            &b"ply\nformat ascii 1.0\n\
+            element point 1\n\
+            property int x\n\
+            end_header\n\
+            \xff\n"[..],
+        );
         Object::decode(source).unwrap_err();
     }
 
     #[test]
-    fn decode_on_non_scalar_payload() {
+    fn decode_on_example_list_payload() {
         use super::*;
 
         let source = &mut Cursor::new(
             &include_bytes!(
-                "../../../../examples/data/polygon/another-cube.greg-turk.binary-le.ply"
+                "../../../../examples/data/polygon/another-cube.greg-turk.ascii.ply"
             )[..],
         );
-        Object::decode(source).unwrap_err();
+        let target = Object::decode(source).unwrap();
 
         let source = &mut Cursor::new(
             &include_bytes!(
                 "../../../../examples/data/polygon/another-cube.greg-turk.binary-be.ply"
             )[..],
         );
-        Object::decode(source).unwrap_err();
+        let output = Object::decode(source).unwrap();
+        assert_eq!(output.payload, target.payload);
+
+        let source = &mut Cursor::new(
+            &include_bytes!(
+                "../../../../examples/data/polygon/another-cube.greg-turk.binary-le.ply"
+            )[..],
+        );
+        let output = Object::decode(source).unwrap();
+        assert_eq!(output.payload, target.payload);
+
+        let object = output;
+
+        let target = true;
+        let output = object.payload.is_list();
+        assert_eq!(output, target);
+
+        let face = object.elem_prop("face", "vertex_index").unwrap();
+
+        let target = vec![
+            &[0, 1, 2][..],
+            &[0, 2, 3],
+            &[7, 6, 5, 4],
+            &[0, 4, 5, 1],
+            &[1, 5, 6, 2],
+            &[2, 6, 7, 3],
+            &[3, 7, 4, 0],
+        ];
+        let output = face.lists::<i32>().unwrap().collect::<Vec<_>>();
+        assert_eq!(output, target);
+
+        let target = &[7, 6, 5, 4];
+        let output = face.list::<i32>(2).unwrap();
+        assert_eq!(output, target);
+
+        let target = true;
+        let output = matches!(face.list::<i32>(7).unwrap_err(), OutOfBounds(7, 7, _));
+        assert_eq!(output, target);
+
+        let target = true;
+        let output = matches!(face.lists::<i64>(), Err(OutOfBounds(_, 13, _)));
+        assert_eq!(output, target);
+
+        let target = true;
+        let output = object.elem_prop("vertex", "x").unwrap();
+        let output = matches!(output.lists::<f32>(), Err(InvalidKind(_)));
+        assert_eq!(output, target);
+
+        let target = &[1, 2, 3, 0, 0];
+        let output = object.elem_prop("edge", "vertex2").unwrap();
+        assert_eq!(output.cast::<i32>().unwrap(), target);
+
+        let target = &[0, 3, 6, 10, 14, 18, 22, 26];
+        let output = object.elem("face").unwrap();
+        let output = output.get_property("vertex_index").unwrap().offsets;
+        assert_eq!(output, target);
+    }
+
+    #[test]
+    fn decode_on_example_list_types() {
+        use super::*;
+
+        let source = &mut Cursor::new(
+            &include_bytes!(
+                "../../../../examples/data/polygon/supported-data-types-common.ply"
+            )[..],
+        );
+        let object = Object::decode(source).unwrap();
+
+        let target = vec![&[2, 1, 0][..]];
+        let output = object.elem_prop("primary_list_type", "L_U8_I32").unwrap();
+        let output = output.lists::<i32>().unwrap().collect::<Vec<_>>();
+        assert_eq!(output, target);
+
+        let target = vec![&[1, 0][..]];
+        let output = object
+            .elem_prop("secondary_list_type", "L_U32_I32")
+            .unwrap();
+        let output = output.lists::<i32>().unwrap().collect::<Vec<_>>();
+        assert_eq!(output, target);
+
+        let source = &mut Cursor::new(
+            &include_bytes!(
+                "../../../../examples/data/polygon/supported-data-types-special.ply"
+            )[..],
+        );
+        let object = Object::decode(source).unwrap();
+
+        let target = vec![&[0, -1][..]];
+        let output = object.elem_prop("extra_list_type", "L_U64_I64").unwrap();
+        let output = output.lists::<i64>().unwrap().collect::<Vec<_>>();
+        assert_eq!(output, target);
+    }
+
+    #[test]
+    fn decode_on_invalid_list_count() {
+        use super::*;
+
+        let source = &mut Cursor::new(
+            &b"ply\nformat ascii 1.0\n\
+            element face 1\n\
+            property list char int vertex_index\n\
+            end_header\n\
+            -1\n"[..],
+        );
+        let target = true;
+        let output = matches!(Object::decode(source).unwrap_err(), TryFromIntError(_));
+        assert_eq!(output, target);
+
+        let source = &mut Cursor::new(
+            &b"ply\nformat binary_little_endian 1.0\n\
+            element face 1\n\
+            property list float int vertex_index\n\
+            end_header\n\
+            \0\0\x80\x3f\0\0\0\0"[..],
+        );
+        let target = true;
+        let output = matches!(Object::decode(source).unwrap_err(), UnsupportedKind(k) if k == "float");
+        assert_eq!(output, target);
+
+        let source = &mut Cursor::new(
+            &b"ply\nformat binary_little_endian 1.0\n\
+            element face 1\n\
+            property list uint64 int vertex_index\n\
+            end_header\n\
+            \xff\xff\xff\xff\xff\xff\xff\x0f\0\0\0\0"[..],
+        );
+        let target = true;
+        let output = matches!(Object::decode(source).unwrap_err(), Io(_));
+        assert_eq!(output, target);
+    }
+
+    #[test]
+    fn decode_on_binary_multiple_properties() {
+        use super::*;
+
+        let mut source = b"ply\nformat ascii 1.0\n\
+            element point 1000\n\
+            property uchar a\n\
+            property short b\n\
+            property float c\n\
+            property double d\n\
+            element empty 2\n\
+            end_header\n"
+            .to_vec();
+        (0..1000).for_each(|index| {
+            let line = format!(
+                "{} {} {} {}\n",
+                index % 256,
+                -index,
+                index as f32 / 3.0,
+                index as f64 * 1e9
+            );
+            source.extend(line.as_bytes());
+        });
+        let mut object = Object::decode(&mut Cursor::new(source)).unwrap();
+
+        let target = (0..1000).map(|index| -index as i16).collect::<Vec<_>>();
+        let output = object.elem_prop("point", "b").unwrap();
+        let output = output.cast::<i16>().unwrap();
+        assert_eq!(output, target);
+
+        [Format::BinaryBigEndian, Format::BinaryLittleEndian]
+            .into_iter()
+            .for_each(|format| {
+                object.header.format = format;
+                let source = &mut vec![];
+                object.encode(source).unwrap();
+
+                let target = &object;
+                let output = &Object::decode(&mut Cursor::new(source)).unwrap();
+                assert_eq!(output, target);
+            });
     }
 }
@@ -14,53 +14,9 @@ impl Encoder for Object {
         let writer = &mut BufWriter::new(writer);
 
         self.header.encode(writer)?;
-        assert!(
-            !self.header.format.is_ascii(),
-            "Unimplemented: ASCII format encoding"
-        );
-
-        let should_reverse_datum = !self.header.format.is_binary_native_endian();
-
-        self.iter_elements()
-            .try_for_each(|ElementEntry { meta: elem, data }| {
-                let prop_count = elem.len();
-                let prop_sizes = elem.property_sizes().collect::<Result<Vec<_>, _>>()?;
-                (0..elem.count)
-                    .try_fold(vec![0; prop_count], |mut prop_offsets, elem_index| {
-                        prop_offsets
-                            .iter_mut()
-                            .zip(prop_sizes.iter().zip(data.iter()))
-                            .try_for_each(|(offset, (size, data))| {
-                                let start = *offset;
-                                let end = start + size;
-                                *offset = end;
-
-                                let datum = data.get(start..end).ok_or_else(|| {
-                                    OutOfBounds(
-                                        end,
-                                        elem.count * size,
-                                        format!("element index {elem_index}"),
-                                    )
-                                })?;
-                                let result = if should_reverse_datum {
-                                    let datum = &mut datum.to_owned();
-                                    datum.reverse();
-                                    writer.write_all(datum)
-                                } else {
-                                    writer.write_all(datum)
-                                };
-
-                                #[cfg(test)]
-                                result.unwrap();
-                                #[cfg(not(test))]
-                                result?;
-
-                                Ok::<_, Self::Err>(())
-                            })?;
-                        Ok::<_, Self::Err>(prop_offsets)
-                    })
-                    .map(drop)
-            })?;
+        self.iter_elements().try_for_each(|elem| {
+            encode_element_rows(writer, &elem, &self.header.format)
+        })?;
 
         #[cfg(all(debug_assertions, not(test)))]
         log::debug!(target: "gausplat-loader::polygon::object", "Object::encode");
@@ -69,6 +25,151 @@ impl Encoder for Object {
     }
 }
 
+/// Encoding the rows of the element in the `format`.
+///
+/// The row count is [`Element::count`] of the element metadata.
+pub fn encode_element_rows(
+    writer: &mut impl Write,
+    elem: &ElementEntry<'_>,
+    format: &Format,
+) -> Result<(), Error> {
+    if format.is_ascii() {
+        encode_ascii_rows(writer, elem)
+    } else {
+        encode_binary_rows(writer, elem, !format.is_binary_native_endian())
+    }
+}
+
+/// Encoding the rows in ASCII format.
+///
+/// Each row is a line of space-separated tokens formatted by
+/// [`ScalarPropertyKind::format_ascii`].
+/// A list starts with the token of its count.
+fn encode_ascii_rows(
+    writer: &mut impl Write,
+    elem: &ElementEntry<'_>,
+) -> Result<(), Error> {
+    let prop_sizes = elem.meta.property_sizes().collect::<Result<Vec<_>, _>>()?;
+    (0..elem.meta.count).try_for_each(|elem_index| {
+        let tokens = elem
+            .meta
+            .values()
+            .zip(prop_sizes.iter())
+            .enumerate()
+            .map(|(prop_index, (prop, size))| {
+                let datum = get_datum(elem, prop_index, *size, elem_index)?;
+                Ok(match &prop.kind {
+                    PropertyKind::List(kind) => {
+                        let count = kind.count_to_bytes(datum.len() / size)?;
+                        std::iter::once(kind.count.format_ascii(&count))
+                            .chain(
+                                datum
+                                    .chunks_exact(*size)
+                                    .map(|value| kind.value.format_ascii(value)),
+                            )
+                            .collect::<Result<Vec<_>, _>>()?
+                            .join(" ")
+                    },
+                    PropertyKind::Scalar(kind) => kind.format_ascii(datum)?,
+                })
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        Ok::<_, Error>(writeln!(writer, "{}", tokens.join(" "))?)
+    })
+}
+
+/// Encoding the rows in binary format.
+///
+/// A list starts with its count.
+fn encode_binary_rows(
+    writer: &mut impl Write,
+    elem: &ElementEntry<'_>,
+    should_reverse_datum: bool,
+) -> Result<(), Error> {
+    let mut write_datum = |datum: &[u8], size: usize| {
+        if should_reverse_datum {
+            datum.chunks_exact(size).try_for_each(|value| {
+                let value = &mut value.to_owned();
+                value.reverse();
+                writer.write_all(value)
+            })
+        } else {
+            writer.write_all(datum)
+        }
+    };
+
+    let prop_sizes = elem.meta.property_sizes().collect::<Result<Vec<_>, _>>()?;
+    (0..elem.meta.count).try_for_each(|elem_index| {
+        elem.meta
+            .values()
+            .zip(prop_sizes.iter())
+            .enumerate()
+            .try_for_each(|(prop_index, (prop, size))| {
+                let datum = get_datum(elem, prop_index, *size, elem_index)?;
+                if let PropertyKind::List(kind) = &prop.kind {
+                    let count = kind.count_to_bytes(datum.len() / size)?;
+                    write_datum(&count, count.len())?;
+                }
+                Ok::<_, Error>(write_datum(datum, *size)?)
+            })
+    })
+}
+
+/// Get the datum of a property of an element.
+///
+/// The datum of a list property contains all the values of the list.
+///
+/// # Errors
+///
+/// It returns [`Error::OutOfBounds`] if the data or the offsets are out of bounds.
+fn get_datum<'e>(
+    elem: &ElementEntry<'e>,
+    prop_index: usize,
+    size: usize,
+    elem_index: usize,
+) -> Result<&'e [u8], Error> {
+    let ElementEntry {
+        meta: elem_meta,
+        data,
+        offsets,
+    } = *elem;
+    let data = data.get(prop_index).map(Vec::as_slice).unwrap_or_default();
+    let is_list = elem_meta
+        .get_index(prop_index)
+        .is_some_and(|(_, prop)| prop.is_list());
+
+    if is_list {
+        let offsets = offsets
+            .get(prop_index)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        let (start, end) = offsets
+            .get(elem_index)
+            .zip(offsets.get(elem_index + 1))
+            .ok_or_else(|| {
+                OutOfBounds(
+                    elem_index + 1,
+                    offsets.len(),
+                    format!("offsets of element index {elem_index}"),
+                )
+            })?;
+        let (start, end) = (start * size, end * size);
+        return data.get(start..end).ok_or_else(|| {
+            OutOfBounds(end, data.len(), format!("element index {elem_index}"))
+        });
+    }
+
+    let start = elem_index * size;
+    let end = start + size;
+    data.get(start..end).ok_or_else(|| {
+        OutOfBounds(
+            end,
+            elem_meta.count * size,
+            format!("element index {elem_index}"),
+        )
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use std::io::Cursor;
@@ -196,14 +297,176 @@ mod tests {
     }
 
     #[test]
-    #[should_panic]
-    fn encode_on_unimplemented_ascii() {
+    fn encode_on_ascii_example_and_decode() {
         use super::*;
 
-        let mut object = Object::default();
-        object.header.format = Format::Ascii;
+        assert(
+            &include_bytes!(
+                "../../../../examples/data/polygon/another-cube.greg-turk.ascii.ply"
+            )[..],
+        );
+        assert(
+            &include_bytes!(
+                "../../../../examples/data/polygon/another-cube.greg-turk.binary-be.ply"
+            )[..],
+        );
+        assert(
+            &include_bytes!(
+                "../../../../examples/data/polygon/another-cube.greg-turk.binary-le.ply"
+            )[..],
+        );
+        assert(
+            &include_bytes!(
+                "../../../../examples/data/polygon/another-cube.greg-turk.zeros.binary-le.ply"
+            )[..],
+        );
+        assert(
+            &include_bytes!("../../../../examples/data/polygon/empty-element.ascii.ply")
+                [..],
+        );
+        assert(
+            &include_bytes!(
+                "../../../../examples/data/polygon/empty-element.binary-le.ply"
+            )[..],
+        );
+        assert(
+            &include_bytes!("../../../../examples/data/polygon/empty-head.ascii.ply")[..],
+        );
+        assert(
+            &include_bytes!(
+                "../../../../examples/data/polygon/supported-data-types-common.ply"
+            )[..],
+        );
+        assert(
+            &include_bytes!(
+                "../../../../examples/data/polygon/supported-data-types-special.ply"
+            )[..],
+        );
+        assert(
+            &include_bytes!("../../../../examples/data/polygon/triangle.binary-be.ply")[..],
+        );
+        assert(
+            &include_bytes!("../../../../examples/data/polygon/triangle.binary-le.ply")[..],
+        );
+        assert(
+            &include_bytes!("../../../../examples/data/polygon/valid-keyword.ascii.ply")
+                [..],
+        );
+
+        fn assert(source: &[u8]) {
+            let mut target = Object::decode(&mut Cursor::new(source)).unwrap();
+            target.header.format = Format::Ascii;
+
+            let writer = &mut vec![];
+            target.encode(writer).unwrap();
+            let output_1 = writer.to_owned();
+
+            let output = Object::decode(&mut Cursor::new(writer)).unwrap();
+            assert_eq!(output, target);
+
+            let writer = &mut vec![];
+            output.encode(writer).unwrap();
+            let output_2 = writer.to_owned();
+            assert_eq!(output_1, output_2);
+
+            let target = true;
+            let output = output_1.is_ascii();
+            assert_eq!(output, target);
+        }
+    }
+
+    #[test]
+    fn encode_on_binary_list_payload() {
+        use super::*;
+
+        let source_be = &include_bytes!(
+            "../../../../examples/data/polygon/another-cube.greg-turk.binary-be.ply"
+        )[..];
+        let source_le = &include_bytes!(
+            "../../../../examples/data/polygon/another-cube.greg-turk.binary-le.ply"
+        )[..];
+
+        let target = &source_le[source_le.len() - 286..];
+        let mut object = Object::decode(&mut Cursor::new(source_be)).unwrap();
+        object.header.format = Format::BinaryLittleEndian;
         let output = &mut vec![];
-        object.encode(output).unwrap_err();
+        object.encode(output).unwrap();
+        let output = output.split_at(object.header.to_string().len()).1;
+        assert_eq!(output, target);
+
+        object.payload.try_unwrap_list_mut().unwrap().offsets[1][0]
+            .pop()
+            .unwrap();
+        let target = true;
+        let output = matches!(
+            object.encode(&mut vec![]).unwrap_err(),
+            OutOfBounds(7, 7, _)
+        );
+        assert_eq!(output, target);
+    }
+
+    #[test]
+    fn encode_on_ascii_scalar_kinds() {
+        use super::*;
+
+        let source = &mut Cursor::new(
+            &b"ply\nformat ascii 1.0\n\
+            element point 2\n\
+            property char a\nproperty uchar b\n\
+            property short c\nproperty ushort d\n\
+            property int e\nproperty uint f\n\
+            property long g\nproperty ulong h\n\
+            property half i\nproperty float j\nproperty double k\n\
+            end_header\n\
+            -128 255 -32768 65535 -2147483648 4294967295 \
+            -9223372036854775808 18446744073709551615 -0.5 1e-3 -1.1e200\n\
+            +1 001 2\t3 4 5\r\n6 7 65504 -inf 0.1"[..],
+        );
+        let object = Object::decode(source).unwrap();
+
+        let target = &b"\
+            -128 255 -32768 65535 -2147483648 4294967295 \
+            -9223372036854775808 18446744073709551615 -0.5 0.001 -1.1e200\n\
+            1 1 2 3 4 5 6 7 65504.0 -inf 0.1\n"[..];
+        let output = &mut vec![];
+        object.encode(output).unwrap();
+        let output = output.split_at(object.header.to_string().len()).1;
+        assert_eq!(output, target);
+    }
+
+    #[test]
+    fn encode_on_ascii_invalid_kind() {
+        use super::*;
+
+        let mut object = Object::decode(&mut Cursor::new(
+            &b"ply\nformat ascii 1.0\n\
+            element point 1\n\
+            property double x\n\
+            end_header\n\
+            0.5\n"[..],
+        ))
+        .unwrap();
+
+        object.elem_prop_mut("point", "x").unwrap().meta.kind =
+            PropertyKind::Scalar("duoble".into());
+        let target = true;
+        let output = matches!(object.encode(&mut vec![]).unwrap_err(), InvalidKind(k) if k == "duoble");
+        assert_eq!(output, target);
+
+        object.elem_prop_mut("point", "x").unwrap().meta.kind =
+            PropertyKind::Scalar("double".into());
+        object
+            .elem_prop_mut("point", "x")
+            .unwrap()
+            .data
+            .pop()
+            .unwrap();
+        let target = true;
+        let output = matches!(
+            object.encode(&mut vec![]).unwrap_err(),
+            OutOfBounds(8, 8, _)
+        );
+        assert_eq!(output, target);
     }
 
     #[test]
@@ -2,9 +2,22 @@
 
 pub use super::*;
 
-use crate::function::read_bytes;
+use crate::function::{
+    advance, read_byte_after, read_bytes, read_bytes_untrusted, string_from_vec_ascii,
+};
+use header::{Element, PropertyKind};
+use rayon::iter::{
+    IndexedParallelIterator, IntoParallelRefIterator, IntoParallelRefMutIterator,
+    ParallelIterator,
+};
 use std::io::Read;
 
+/// The maximum size of a block of rows read at once, which is 64 MiB.
+const BLOCK_SIZE_MAX: usize = 1 << 26;
+
+/// The data and offsets of an element.
+pub type ElementData = (Vec<Vec<u8>>, Vec<Vec<usize>>);
+
 impl DecoderWith<&Header> for Payload {
     type Err = Error;
 
@@ -12,46 +25,26 @@ impl DecoderWith<&Header> for Payload {
         reader: &mut impl Read,
         init: &Header,
     ) -> Result<Self, Self::Err> {
-        assert!(
-            !init.format.is_ascii(),
-            "Unimplemented: ASCII format decoding"
-        );
-
-        let should_reverse_datum = !init.format.is_binary_native_endian();
-
-        let data = init
+        let (data, offsets) = init
             .elements
             .values()
             .map(|elem| {
-                let prop_count = elem.len();
-                let prop_sizes = elem.property_sizes().collect::<Result<Vec<_>, _>>()?;
-                let elem_size = prop_sizes.iter().sum::<usize>();
-
-                (0..elem.count).try_fold(
-                    vec![Vec::with_capacity(1 << 15); prop_count],
-                    |mut props, _| {
-                        let mut data = read_bytes(reader, elem_size)?;
-                        props.iter_mut().zip(prop_sizes.iter()).fold(
-                            0,
-                            |start, (prop, size)| {
-                                let end = start + size;
-                                // NOTE: The index is guaranteed to be valid.
-                                let datum = data.get_mut(start..end).unwrap();
-                                if should_reverse_datum {
-                                    datum.reverse();
-                                }
-                                prop.extend_from_slice(datum);
-                                end
-                            },
-                        );
-                        Ok(props)
-                    },
-                )
+                let selections = vec![true; elem.len()];
+                decode_element_rows(reader, elem, &init.format, elem.count, &selections)
             })
-            .collect::<Result<_, Self::Err>>()?;
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .unzip();
 
-        // NOTE: Currently, there is only scalar payload implemented.
-        let payload = ScalarPayload { data }.into();
+        let has_list = init
+            .elements
+            .values()
+            .any(|elem| elem.values().any(|prop| prop.is_list()));
+        let payload = if has_list {
+            ListPayload { data, offsets }.into()
+        } else {
+            ScalarPayload { data }.into()
+        };
 
         #[cfg(all(debug_assertions, not(test)))]
         log::debug!(target: "gausplat-loader::polygon::payload", "Payload::decode_with");
@@ -59,3 +52,346 @@ impl DecoderWith<&Header> for Payload {
         Ok(payload)
     }
 }
+
+/// Decoding `row_count` rows of the element in the `format`.
+///
+/// Only the data and offsets of the properties marked in `selections` are kept,
+/// and the others are read and discarded.
+/// The offsets of a list property start with zero.
+pub fn decode_element_rows(
+    reader: &mut impl Read,
+    elem: &Element,
+    format: &Format,
+    row_count: usize,
+    selections: &[bool],
+) -> Result<ElementData, Error> {
+    if format.is_ascii() {
+        decode_ascii_rows(reader, elem, row_count, selections)
+    } else {
+        let should_reverse_datum = !format.is_binary_native_endian();
+        decode_binary_rows(
+            reader,
+            elem,
+            row_count,
+            selections,
+            should_reverse_datum,
+            BLOCK_SIZE_MAX,
+        )
+    }
+}
+
+/// Decoding the rows in ASCII format.
+///
+/// The tokens are separated by any ASCII whitespace,
+/// and they are parsed by
+/// [`ScalarPropertyKind::parse_ascii`](header::ScalarPropertyKind::parse_ascii).
+fn decode_ascii_rows(
+    reader: &mut impl Read,
+    elem: &Element,
+    row_count: usize,
+    selections: &[bool],
+) -> Result<ElementData, Error> {
+    elem.property_sizes().try_for_each(|size| size.map(drop))?;
+
+    let selected_count = selections.iter().filter(|s| **s).count();
+    let mut data = vec![Vec::with_capacity(1 << 15); selected_count];
+    let mut offsets = new_offsets(elem, selections);
+
+    for _ in 0..row_count {
+        let mut data = data.iter_mut();
+        let mut offsets = offsets.iter_mut();
+        for (prop, is_selected) in elem.values().zip(selections) {
+            let mut datum = if *is_selected { data.next() } else { None };
+            let offsets = if *is_selected { offsets.next() } else { None };
+            match &prop.kind {
+                PropertyKind::List(kind) => {
+                    let count = kind.count.parse_ascii(&read_ascii_token(reader)?)?;
+                    let count = kind.count_from_bytes(&count)?;
+                    for _ in 0..count {
+                        let value = kind.value.parse_ascii(&read_ascii_token(reader)?)?;
+                        if let Some(datum) = datum.as_deref_mut() {
+                            datum.extend(value);
+                        }
+                    }
+                    if let Some(offsets) = offsets {
+                        push_offset(offsets, count);
+                    }
+                },
+                PropertyKind::Scalar(kind) => {
+                    let value = kind.parse_ascii(&read_ascii_token(reader)?)?;
+                    if let Some(datum) = datum {
+                        datum.extend(value);
+                    }
+                },
+            }
+        }
+    }
+
+    Ok((data, offsets))
+}
+
+/// Decoding the rows in binary format.
+///
+/// The rows of a scalar-only element are read in blocks of at most
+/// `block_size_max` bytes, or one row if it is larger,
+/// and the selected properties are de-interleaved in parallel.
+fn decode_binary_rows(
+    reader: &mut impl Read,
+    elem: &Element,
+    row_count: usize,
+    selections: &[bool],
+    should_reverse_datum: bool,
+    block_size_max: usize,
+) -> Result<ElementData, Error> {
+    let prop_sizes = elem.property_sizes().collect::<Result<Vec<_>, _>>()?;
+    let selected_count = selections.iter().filter(|s| **s).count();
+
+    if elem.values().any(|prop| prop.is_list()) {
+        let mut data = vec![Vec::with_capacity(1 << 15); selected_count];
+        let mut offsets = new_offsets(elem, selections);
+
+        for _ in 0..row_count {
+            let mut data = data.iter_mut();
+            let mut offsets = offsets.iter_mut();
+            for ((prop, size), is_selected) in
+                elem.values().zip(prop_sizes.iter()).zip(selections)
+            {
+                let count = match &prop.kind {
+                    PropertyKind::List(kind) => {
+                        let count_size = kind
+                            .count
+                            .size()
+                            .ok_or_else(|| Error::InvalidKind(kind.count.to_string()))?;
+                        let mut count = read_bytes(reader, count_size)?;
+                        if should_reverse_datum {
+                            count.reverse();
+                        }
+                        Some(kind.count_from_bytes(&count)?)
+                    },
+                    PropertyKind::Scalar(_) => None,
+                };
+                let values_size =
+                    count.map_or(*size, |count| count.saturating_mul(*size));
+
+                if !is_selected {
+                    advance(reader, values_size)?;
+                    continue;
+                }
+
+                let mut values = match count {
+                    Some(_) => read_bytes_untrusted(reader, values_size)?,
+                    None => read_bytes(reader, values_size)?,
+                };
+                if should_reverse_datum {
+                    values.chunks_exact_mut(*size).for_each(<[u8]>::reverse);
+                }
+                // NOTE: The data and offsets are as many as the selected properties.
+                data.next().unwrap().extend(values);
+
+                if let Some(count) = count {
+                    push_offset(offsets.next().unwrap(), count);
+                }
+            }
+        }
+
+        return Ok((data, offsets));
+    }
+
+    let elem_size = prop_sizes.iter().sum::<usize>();
+    let prop_ranges = prop_sizes
+        .iter()
+        .scan(0, |start, size| {
+            let range = *start..*start + size;
+            *start += size;
+            Some(range)
+        })
+        .zip(selections)
+        .filter(|(_, is_selected)| **is_selected)
+        .map(|(range, _)| range)
+        .collect::<Vec<_>>();
+    let mut data = vec![vec![]; selected_count];
+
+    if elem_size == 0 {
+        return Ok((data, vec![vec![]; selected_count]));
+    }
+
+    let block_row_count = (block_size_max / elem_size).max(1);
+    let mut row_index = 0;
+    while row_index < row_count {
+        let block_row_count = block_row_count.min(row_count - row_index);
+        let block = read_bytes(reader, block_row_count * elem_size)?;
+
+        data.par_iter_mut()
+            .zip(prop_ranges.par_iter())
+            .for_each(|(datum, range)| {
+                let size = range.len();
+                let start = datum.len();
+                if size == elem_size {
+                    datum.extend_from_slice(&block);
+                } else {
+                    datum.reserve(block_row_count * size);
+                    block
+                        .chunks_exact(elem_size)
+                        .for_each(|row| datum.extend_from_slice(&row[range.to_owned()]));
+                }
+                if should_reverse_datum {
+                    datum[start..]
+                        .chunks_exact_mut(size)
+                        .for_each(<[u8]>::reverse);
+                }
+            });
+
+        row_index += block_row_count;
+    }
+
+    Ok((data, vec![vec![]; selected_count]))
+}
+
+/// Creating the initial offsets of the element.
+///
+/// The offsets of a selected list property start with zero.
+#[inline]
+fn new_offsets(
+    elem: &Element,
+    selections: &[bool],
+) -> Vec<Vec<usize>> {
+    elem.values()
+        .zip(selections)
+        .filter(|(_, is_selected)| **is_selected)
+        .map(|(prop, _)| if prop.is_list() { vec![0] } else { vec![] })
+        .collect()
+}
+
+/// Pushing the next offset after `count` values.
+#[inline]
+fn push_offset(
+    offsets: &mut Vec<usize>,
+    count: usize,
+) {
+    // NOTE: The offsets of a list property are never empty.
+    let last = offsets.last().copied().unwrap_or_default();
+    offsets.push(last + count);
+}
+
+/// Reading an ASCII token after all whitespace bytes.
+///
+/// The token ends before a whitespace byte or at the end of the reader.
+fn read_ascii_token(reader: &mut impl Read) -> Result<String, Error> {
+    let mut token = vec![read_byte_after(reader, |b| b.is_ascii_whitespace())?];
+    loop {
+        let byte = &mut [0; 1];
+        if reader.read(byte)? == 0 || byte[0].is_ascii_whitespace() {
+            return string_from_vec_ascii(token);
+        }
+        token.push(byte[0]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    #[test]
+    fn decode_binary_rows_on_multiple_blocks() {
+        use super::*;
+
+        let header = Header::decode(&mut Cursor::new(
+            &b"ply\nformat binary_little_endian 1.0\n\
+            element point 1000\n\
+            property uchar a\n\
+            property short b\n\
+            property float c\n\
+            property double d\n\
+            end_header\n"[..],
+        ))
+        .unwrap();
+        let elem = &header["point"];
+        let rows = (0..1000)
+            .map(|index| {
+                (
+                    (index % 256) as u8,
+                    -index as i16,
+                    index as f32 / 3.0,
+                    index as f64 * 1e9,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let target = vec![
+            rows.iter()
+                .flat_map(|row| row.0.to_ne_bytes())
+                .collect::<Vec<_>>(),
+            rows.iter().flat_map(|row| row.1.to_ne_bytes()).collect(),
+            rows.iter().flat_map(|row| row.2.to_ne_bytes()).collect(),
+            rows.iter().flat_map(|row| row.3.to_ne_bytes()).collect(),
+        ];
+
+        [false, true].into_iter().for_each(|is_big_endian| {
+            let source = rows
+                .iter()
+                .flat_map(|row| {
+                    if is_big_endian {
+                        [
+                            &row.0.to_be_bytes()[..],
+                            &row.1.to_be_bytes(),
+                            &row.2.to_be_bytes(),
+                            &row.3.to_be_bytes(),
+                        ]
+                        .concat()
+                    } else {
+                        [
+                            &row.0.to_le_bytes()[..],
+                            &row.1.to_le_bytes(),
+                            &row.2.to_le_bytes(),
+                            &row.3.to_le_bytes(),
+                        ]
+                        .concat()
+                    }
+                })
+                .collect::<Vec<_>>();
+            let should_reverse_datum = is_big_endian == cfg!(target_endian = "little");
+
+            // NOTE: The blocks have 1, 6 and 1000 rows.
+            [1, 100, BLOCK_SIZE_MAX]
+                .into_iter()
+                .for_each(|block_size_max| {
+                    let (output, _) = decode_binary_rows(
+                        &mut Cursor::new(&source),
+                        elem,
+                        1000,
+                        &[true; 4],
+                        should_reverse_datum,
+                        block_size_max,
+                    )
+                    .unwrap();
+                    assert_eq!(output, target, "{block_size_max}");
+
+                    let (output, _) = decode_binary_rows(
+                        &mut Cursor::new(&source),
+                        elem,
+                        1000,
+                        &[false, true, false, true],
+                        should_reverse_datum,
+                        block_size_max,
+                    )
+                    .unwrap();
+                    assert_eq!(output, [&target[1][..], &target[3]], "{block_size_max}");
+                });
+
+            let target = true;
+            let output = matches!(
+                decode_binary_rows(
+                    &mut Cursor::new(&source[..source.len() - 1]),
+                    elem,
+                    1000,
+                    &[true; 4],
+                    should_reverse_datum,
+                    100,
+                )
+                .unwrap_err(),
+                Error::Io(_),
+            );
+            assert_eq!(output, target);
+        });
+    }
+}
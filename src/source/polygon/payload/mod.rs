@@ -16,7 +16,42 @@ use std::fmt;
 pub enum Payload {
     /// Scalar-only payload.
     Scalar(ScalarPayload),
-    // TODO: Non-scalar payload
+    /// Payload that contains list data.
+    List(ListPayload),
+}
+
+/// A payload that contains list data.
+///
+/// The data of a list property are the flat values of all elements,
+/// and the values of each element are delimited by the offsets.
+#[derive(
+    AsRef,
+    Clone,
+    Constructor,
+    Debug,
+    Default,
+    Deref,
+    DerefMut,
+    Eq,
+    From,
+    Hash,
+    IntoIterator,
+    PartialEq,
+)]
+pub struct ListPayload {
+    /// Inner data list.
+    #[as_ref]
+    #[deref]
+    #[deref_mut]
+    #[into_iterator(owned, ref, ref_mut)]
+    pub data: Vec<Vec<Vec<u8>>>,
+
+    /// Inner offsets list.
+    ///
+    /// The offsets of a list property have `count + 1` indices of the values,
+    /// and the values of the `i`-th element are in `offsets[i]..offsets[i + 1]`.
+    /// The offsets of a scalar property are empty.
+    pub offsets: Vec<Vec<Vec<usize>>>,
 }
 
 /// A payload that only contains scalar data.
@@ -39,6 +74,56 @@ pub struct ScalarPayload {
     pub data: Vec<Vec<Vec<u8>>>,
 }
 
+impl Payload {
+    /// Returns the inner data list.
+    #[inline]
+    pub fn data(&self) -> &Vec<Vec<Vec<u8>>> {
+        match self {
+            Self::List(payload) => &payload.data,
+            Self::Scalar(payload) => &payload.data,
+        }
+    }
+
+    /// Returns the mutable inner data list.
+    #[inline]
+    pub fn data_mut(&mut self) -> &mut Vec<Vec<Vec<u8>>> {
+        match self {
+            Self::List(payload) => &mut payload.data,
+            Self::Scalar(payload) => &mut payload.data,
+        }
+    }
+
+    /// Returns the inner offsets list if it is a list payload.
+    #[inline]
+    pub fn offsets(&self) -> Option<&Vec<Vec<Vec<usize>>>> {
+        self.try_unwrap_list_ref()
+            .ok()
+            .map(|payload| &payload.offsets)
+    }
+}
+
+impl ListPayload {
+    /// Returns the number of elements.
+    #[inline]
+    pub fn element_count(&self) -> usize {
+        self.len()
+    }
+
+    /// Returns the number of properties.
+    #[inline]
+    pub fn property_count(&self) -> usize {
+        self.iter().map(Vec::len).sum()
+    }
+
+    /// Returns the number of bytes.
+    #[inline]
+    pub fn byte_count(&self) -> usize {
+        self.iter()
+            .map(|v| v.iter().map(Vec::len).sum::<usize>())
+            .sum()
+    }
+}
+
 impl ScalarPayload {
     /// Returns the number of elements.
     #[inline]
@@ -68,6 +153,22 @@ impl Default for Payload {
     }
 }
 
+impl fmt::Display for ListPayload {
+    #[inline]
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        write!(
+            f,
+            "[{} elements, {} properties, {} bytes]",
+            self.element_count(),
+            self.property_count(),
+            self.byte_count()
+        )
+    }
+}
+
 impl fmt::Display for ScalarPayload {
     #[inline]
     fn fmt(
@@ -100,5 +201,32 @@ mod tests {
         let target = "[3 elements, 22 properties, 88 bytes]";
         let output = output.to_string();
         assert_eq!(target, output);
+
+        let output = ListPayload::new(
+            vec![vec![vec![0u8; 4]; 2]; 2],
+            vec![vec![vec![], vec![0, 3, 4]]; 2],
+        );
+        let target = "[2 elements, 4 properties, 16 bytes]";
+        let output = Payload::from(output).to_string();
+        assert_eq!(target, output);
+    }
+
+    #[test]
+    fn data_and_offsets() {
+        use super::*;
+
+        let mut output = Payload::default();
+        output.data_mut().push(vec![vec![1]]);
+        let target = &vec![vec![vec![1]]];
+        assert_eq!(output.data(), target);
+        let target = None;
+        assert_eq!(output.offsets(), target);
+
+        let mut output = Payload::from(ListPayload::default());
+        output.data_mut().push(vec![vec![1]]);
+        let target = &vec![vec![vec![1]]];
+        assert_eq!(output.data(), target);
+        let target = Some(&vec![]);
+        assert_eq!(output.offsets(), target);
     }
 }
@@ -0,0 +1,736 @@
+//! Gaussian splat module.
+//!
+//! It provides a typed view over the polygon object of a 3DGS PLY file.
+//!
+//! The canonical schema of the `vertex` element is all `float` properties:
+//!
+//! ```plaintext
+//! x y z
+//! nx ny nz
+//! f_dc_0 f_dc_1 f_dc_2
+//! f_rest_0 ... f_rest_{3 * ((D + 1)^2 - 1) - 1}
+//! opacity
+//! scale_0 scale_1 scale_2
+//! rot_0 rot_1 rot_2 rot_3
+//! ```
+//!
+//! `D` is the degree of spherical harmonics.
+//! The properties `f_rest_*` are in channel-major order.
+
+pub mod tensor;
+
+pub use super::*;
+pub use object::*;
+pub use tensor::*;
+
+use bytemuck::try_cast_slice;
+use payload::ScalarPayload;
+use std::io::Write;
+use Error::*;
+
+/// The element name of Gaussian splats.
+pub const ELEMENT_NAME: &str = "vertex";
+
+/// Gaussian splat view.
+///
+/// It borrows the data of the `vertex` element of [`Object`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct GaussianSplat<'o> {
+    /// Colors in SH DC coefficients, `f_dc_0`, `f_dc_1` and `f_dc_2`.
+    pub colors_sh_dc: [&'o [f32]; 3],
+    /// Colors in SH rest coefficients, `f_rest_*` in channel-major order.
+    pub colors_sh_rest: Vec<&'o [f32]>,
+    /// Normals, `nx`, `ny` and `nz`.
+    ///
+    /// They are optional.
+    pub normals: Option<[&'o [f32]; 3]>,
+    /// Opacities, `opacity`.
+    pub opacities: &'o [f32],
+    /// Positions, `x`, `y` and `z`.
+    pub positions: [&'o [f32]; 3],
+    /// Rotations in quaternion, `rot_0`, `rot_1`, `rot_2` and `rot_3`.
+    pub rotations: [&'o [f32]; 4],
+    /// Scalings, `scale_0`, `scale_1` and `scale_2`.
+    pub scalings: [&'o [f32]; 3],
+}
+
+/// Gaussian splat builder.
+///
+/// It builds an [`Object`] in the canonical schema.
+/// The data are in row-major order, and the row count is the point count.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GaussianSplatBuilder {
+    colors_sh: Vec<f32>,
+    normals: Option<Vec<f32>>,
+    opacities: Vec<f32>,
+    positions: Vec<f32>,
+    rotations: Vec<f32>,
+    scalings: Vec<f32>,
+    sh_degree: usize,
+}
+
+impl<'o> GaussianSplat<'o> {
+    /// Returns the number of points.
+    #[inline]
+    pub fn count(&self) -> usize {
+        self.opacities.len()
+    }
+
+    /// Returns the degree of spherical harmonics.
+    #[inline]
+    pub fn sh_degree(&self) -> usize {
+        get_sh_degree(self.colors_sh_rest.len()).unwrap_or_default()
+    }
+
+    /// Returns the number of SH coefficients per channel, that is, `(D + 1)^2`.
+    #[inline]
+    pub fn sh_coefficient_count(&self) -> usize {
+        self.colors_sh_rest.len() / 3 + 1
+    }
+
+    /// Get the SH coefficients of a point in channel-major order.
+    ///
+    /// The shape is `[3, (D + 1)^2]`.
+    pub fn get_colors_sh(
+        &self,
+        index: usize,
+    ) -> Option<Vec<f32>> {
+        let rest_count = self.sh_coefficient_count() - 1;
+        let mut colors_sh = Vec::with_capacity(3 * (rest_count + 1));
+        for channel in 0..3 {
+            colors_sh.push(*self.colors_sh_dc[channel].get(index)?);
+            for rest in &self.colors_sh_rest[channel * rest_count..][..rest_count] {
+                colors_sh.push(*rest.get(index)?);
+            }
+        }
+        Some(colors_sh)
+    }
+
+    /// Returns the SH coefficients of all points in channel-major order.
+    ///
+    /// The shape is `[N, 3 * (D + 1)^2]`.
+    pub fn colors_sh(&self) -> Vec<f32> {
+        (0..self.count())
+            .flat_map(|index| {
+                // NOTE: The index is guaranteed to be valid.
+                self.get_colors_sh(index).unwrap()
+            })
+            .collect()
+    }
+
+    /// Convert to a polygon object in the canonical schema.
+    ///
+    /// The format is binary little endian.
+    /// The normals are zeros if they are absent.
+    pub fn to_object(&self) -> Object {
+        let zeros = vec![0.0; self.count()];
+        let normals = self.normals.unwrap_or([&zeros, &zeros, &zeros]);
+        let columns = self
+            .positions
+            .iter()
+            .chain(normals.iter())
+            .chain(self.colors_sh_dc.iter())
+            .chain(self.colors_sh_rest.iter())
+            .chain([self.opacities].iter())
+            .chain(self.scalings.iter())
+            .chain(self.rotations.iter())
+            .map(|column| bytemuck::cast_slice(column).to_owned())
+            .collect();
+
+        new_object(self.count(), self.sh_degree(), columns)
+    }
+}
+
+impl<'o> TryFrom<&'o Object> for GaussianSplat<'o> {
+    type Error = Error;
+
+    /// Validating the schema and viewing the data of the `vertex` element.
+    ///
+    /// The properties may be in any order.
+    ///
+    /// # Errors
+    ///
+    /// It returns [`Error::MissingSymbol`] if any required element or property is missing.
+    ///
+    /// It returns [`Error::UnsupportedKind`] if any property is not `float`.
+    ///
+    /// It returns [`Error::MismatchedTensorShape`] if the data length of any property
+    /// mismatches the element count, or the count of `f_rest_*` mismatches
+    /// any degree of spherical harmonics.
+    fn try_from(object: &'o Object) -> Result<Self, Self::Error> {
+        let element = object
+            .header
+            .get(ELEMENT_NAME)
+            .ok_or_else(|| MissingSymbol(format!("element {ELEMENT_NAME}")))?;
+        let get = |name: &str| get_property(object, element.count, name);
+        let get_required = |name: &str| {
+            get(name)?.ok_or_else(|| MissingSymbol(format!("property {name}")))
+        };
+
+        let rest_count = (0..)
+            .take_while(|index| element.contains_key(&format!("f_rest_{index}")))
+            .count();
+        if get_sh_degree(rest_count).is_none() {
+            let sh_degree = (rest_count / 3 + 1).isqrt() - 1;
+            let rest_count_expected = 3 * ((sh_degree + 1).pow(2) - 1);
+            return Err(MismatchedTensorShape(
+                vec![rest_count],
+                vec![rest_count_expected],
+            ));
+        }
+
+        let positions = [get_required("x")?, get_required("y")?, get_required("z")?];
+        let normals = match (get("nx")?, get("ny")?, get("nz")?) {
+            (Some(nx), Some(ny), Some(nz)) => Some([nx, ny, nz]),
+            _ => None,
+        };
+        let colors_sh_dc = [
+            get_required("f_dc_0")?,
+            get_required("f_dc_1")?,
+            get_required("f_dc_2")?,
+        ];
+        let colors_sh_rest = (0..rest_count)
+            .map(|index| get_required(&format!("f_rest_{index}")))
+            .collect::<Result<_, _>>()?;
+        let opacities = get_required("opacity")?;
+        let scalings = [
+            get_required("scale_0")?,
+            get_required("scale_1")?,
+            get_required("scale_2")?,
+        ];
+        let rotations = [
+            get_required("rot_0")?,
+            get_required("rot_1")?,
+            get_required("rot_2")?,
+            get_required("rot_3")?,
+        ];
+        let splat = Self {
+            colors_sh_dc,
+            colors_sh_rest,
+            normals,
+            opacities,
+            positions,
+            rotations,
+            scalings,
+        };
+
+        #[cfg(all(debug_assertions, not(test)))]
+        log::debug!(target: "gausplat-loader::polygon::splat", "GaussianSplat::try_from");
+
+        Ok(splat)
+    }
+}
+
+impl Encoder for GaussianSplat<'_> {
+    type Err = Error;
+
+    /// Encoding the Gaussian splats in the canonical schema.
+    ///
+    /// See [`GaussianSplat::to_object`].
+    #[inline]
+    fn encode(
+        &self,
+        writer: &mut impl Write,
+    ) -> Result<(), Self::Err> {
+        self.to_object().encode(writer)
+    }
+}
+
+impl GaussianSplatBuilder {
+    /// Create a builder with empty data.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the SH coefficients in channel-major order.
+    ///
+    /// The shape is `[N, 3 * (D + 1)^2]`.
+    #[inline]
+    pub fn colors_sh(
+        mut self,
+        colors_sh: Vec<f32>,
+    ) -> Self {
+        self.colors_sh = colors_sh;
+        self
+    }
+
+    /// Set the normals.
+    ///
+    /// The shape is `[N, 3]`. They are zeros if not set.
+    #[inline]
+    pub fn normals(
+        mut self,
+        normals: Vec<f32>,
+    ) -> Self {
+        self.normals = Some(normals);
+        self
+    }
+
+    /// Set the opacities.
+    ///
+    /// The shape is `[N, 1]`.
+    #[inline]
+    pub fn opacities(
+        mut self,
+        opacities: Vec<f32>,
+    ) -> Self {
+        self.opacities = opacities;
+        self
+    }
+
+    /// Set the positions.
+    ///
+    /// The shape is `[N, 3]`.
+    #[inline]
+    pub fn positions(
+        mut self,
+        positions: Vec<f32>,
+    ) -> Self {
+        self.positions = positions;
+        self
+    }
+
+    /// Set the rotations in quaternion.
+    ///
+    /// The shape is `[N, 4]`.
+    #[inline]
+    pub fn rotations(
+        mut self,
+        rotations: Vec<f32>,
+    ) -> Self {
+        self.rotations = rotations;
+        self
+    }
+
+    /// Set the scalings.
+    ///
+    /// The shape is `[N, 3]`.
+    #[inline]
+    pub fn scalings(
+        mut self,
+        scalings: Vec<f32>,
+    ) -> Self {
+        self.scalings = scalings;
+        self
+    }
+
+    /// Set the degree of spherical harmonics.
+    ///
+    /// The default value is `0`.
+    #[inline]
+    pub fn sh_degree(
+        mut self,
+        sh_degree: usize,
+    ) -> Self {
+        self.sh_degree = sh_degree;
+        self
+    }
+
+    /// Build the polygon object in the canonical schema.
+    ///
+    /// The format is binary little endian.
+    ///
+    /// # Errors
+    ///
+    /// It returns [`Error::MismatchedTensorShape`] if the shape of any data mismatches.
+    pub fn build(self) -> Result<Object, Error> {
+        let count = self.positions.len() / 3;
+        let sh_count = (self.sh_degree + 1).pow(2);
+        let normals = self.normals.unwrap_or_else(|| vec![0.0; count * 3]);
+
+        let positions = get_columns(&self.positions, count, 3)?;
+        let normals = get_columns(&normals, count, 3)?;
+        let mut colors_sh = get_columns(&self.colors_sh, count, 3 * sh_count)?;
+        let opacities = get_columns(&self.opacities, count, 1)?;
+        let scalings = get_columns(&self.scalings, count, 3)?;
+        let rotations = get_columns(&self.rotations, count, 4)?;
+
+        // NOTE: The channel-major SH coefficients are split into
+        // the DC coefficients and the rest coefficients of each channel.
+        let colors_sh_dc = (0..3)
+            .map(|channel| std::mem::take(&mut colors_sh[channel * sh_count]))
+            .collect::<Vec<_>>();
+        let colors_sh_rest = colors_sh
+            .into_iter()
+            .enumerate()
+            .filter(|(index, _)| index % sh_count != 0)
+            .map(|(_, column)| column);
+
+        let columns = positions
+            .into_iter()
+            .chain(normals)
+            .chain(colors_sh_dc)
+            .chain(colors_sh_rest)
+            .chain(opacities)
+            .chain(scalings)
+            .chain(rotations)
+            .collect();
+
+        Ok(new_object(count, self.sh_degree, columns))
+    }
+}
+
+/// Create a polygon object in the canonical schema from the property columns.
+///
+/// The columns are in the canonical order.
+fn new_object(
+    count: usize,
+    sh_degree: usize,
+    columns: Vec<Vec<u8>>,
+) -> Object {
+    let properties = get_property_names(sh_degree)
+        .into_iter()
+        .map(|name| {
+            let property = Property {
+                kind: PropertyKind::Scalar("float".into()),
+                name: name.to_owned(),
+            };
+            (name, property)
+        })
+        .collect::<IndexMap<_, _>>();
+    let element = Element {
+        count,
+        name: ELEMENT_NAME.into(),
+        properties: properties.into(),
+    };
+
+    Object {
+        header: Header {
+            elements: [(ELEMENT_NAME.into(), element)]
+                .into_iter()
+                .collect::<IndexMap<_, _>>()
+                .into(),
+            format: Format::BinaryLittleEndian,
+            ..Default::default()
+        },
+        payload: ScalarPayload {
+            data: vec![columns],
+        }
+        .into(),
+    }
+}
+
+/// De-interleaving the row-major data into the columns of native-endian bytes.
+///
+/// # Errors
+///
+/// It returns [`Error::MismatchedTensorShape`] if the data length is not `count * width`.
+fn get_columns(
+    data: &[f32],
+    count: usize,
+    width: usize,
+) -> Result<Vec<Vec<u8>>, Error> {
+    if data.len() != count * width {
+        return Err(MismatchedTensorShape(vec![data.len()], vec![count, width]));
+    }
+    let mut columns = vec![Vec::with_capacity(count * 4); width];
+    data.chunks_exact(width).for_each(|row| {
+        columns
+            .iter_mut()
+            .zip(row)
+            .for_each(|(column, value)| column.extend(value.to_ne_bytes()))
+    });
+    Ok(columns)
+}
+
+/// Get the degree of spherical harmonics from the count of `f_rest_*`.
+///
+/// It returns `None` if the count is not `3 * ((D + 1)^2 - 1)`.
+#[inline]
+fn get_sh_degree(rest_count: usize) -> Option<usize> {
+    let sh_count = rest_count / 3 + 1;
+    let sh_degree = sh_count.isqrt() - 1;
+    (3 * ((sh_degree + 1).pow(2) - 1) == rest_count).then_some(sh_degree)
+}
+
+/// Get the property names in the canonical order.
+fn get_property_names(sh_degree: usize) -> Vec<String> {
+    let rest_count = 3 * ((sh_degree + 1).pow(2) - 1);
+    [
+        "x", "y", "z", "nx", "ny", "nz", "f_dc_0", "f_dc_1", "f_dc_2",
+    ]
+    .into_iter()
+    .map(Into::into)
+    .chain((0..rest_count).map(|index| format!("f_rest_{index}")))
+    .chain(
+        [
+            "opacity", "scale_0", "scale_1", "scale_2", "rot_0", "rot_1", "rot_2",
+            "rot_3",
+        ]
+        .into_iter()
+        .map(Into::into),
+    )
+    .collect()
+}
+
+/// Get the data of a `float` property of the `vertex` element.
+///
+/// It returns `None` if the property is missing.
+fn get_property<'o>(
+    object: &'o Object,
+    count: usize,
+    name: &str,
+) -> Result<Option<&'o [f32]>, Error> {
+    let Some(PropertyEntry { meta, data, .. }) = object.elem_prop(ELEMENT_NAME, name)
+    else {
+        return Ok(None);
+    };
+    let kind = meta
+        .try_unwrap_scalar_ref()
+        .map_err(|err| UnsupportedKind(err.input.to_string()))?;
+    if kind.variant != ScalarPropertyVariant::Float32 {
+        return Err(UnsupportedKind(kind.to_string()));
+    }
+    let data = try_cast_slice::<_, f32>(data)?;
+    if data.len() != count {
+        return Err(MismatchedTensorShape(vec![data.len()], vec![count]));
+    }
+    Ok(Some(data))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    #[test]
+    fn build_and_view() {
+        use super::*;
+
+        let colors_sh = (0..24).map(|value| value as f32).collect::<Vec<_>>();
+        let object = GaussianSplatBuilder::new()
+            .colors_sh(colors_sh.to_owned())
+            .opacities(vec![0.5, -0.5])
+            .positions(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0])
+            .rotations(vec![1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0])
+            .scalings(vec![-1.0, -2.0, -3.0, -4.0, -5.0, -6.0])
+            .sh_degree(1)
+            .build()
+            .unwrap();
+
+        let target = "x y z nx ny nz f_dc_0 f_dc_1 f_dc_2 \
+            f_rest_0 f_rest_1 f_rest_2 f_rest_3 f_rest_4 f_rest_5 f_rest_6 f_rest_7 f_rest_8 \
+            opacity scale_0 scale_1 scale_2 rot_0 rot_1 rot_2 rot_3";
+        let output = object
+            .elem(ELEMENT_NAME)
+            .unwrap()
+            .meta
+            .keys()
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join(" ");
+        assert_eq!(output, target);
+
+        let splat = GaussianSplat::try_from(&object).unwrap();
+
+        let target = (2, 1, 4);
+        let output = (
+            splat.count(),
+            splat.sh_degree(),
+            splat.sh_coefficient_count(),
+        );
+        assert_eq!(output, target);
+
+        let target = [&[1.0, 4.0][..], &[2.0, 5.0], &[3.0, 6.0]];
+        let output = splat.positions;
+        assert_eq!(output, target);
+
+        let target = Some([&[0.0, 0.0][..]; 3]);
+        let output = splat.normals;
+        assert_eq!(output, target);
+
+        let target = [&[0.0, 12.0][..], &[4.0, 16.0], &[8.0, 20.0]];
+        let output = splat.colors_sh_dc;
+        assert_eq!(output, target);
+
+        let target = [&[1.0, 13.0][..], &[2.0, 14.0], &[3.0, 15.0], &[5.0, 17.0]];
+        let output = &splat.colors_sh_rest[..4];
+        assert_eq!(output, target);
+
+        let target = colors_sh[12..].to_vec();
+        let output = splat.get_colors_sh(1).unwrap();
+        assert_eq!(output, target);
+
+        let target = None;
+        let output = splat.get_colors_sh(2);
+        assert_eq!(output, target);
+
+        let target = colors_sh;
+        let output = splat.colors_sh();
+        assert_eq!(output, target);
+
+        let target = [&[1.0, 0.0][..], &[0.0, 1.0], &[0.0, 0.0], &[0.0, 0.0]];
+        let output = splat.rotations;
+        assert_eq!(output, target);
+    }
+
+    #[test]
+    fn build_on_mismatched_shape() {
+        use super::*;
+
+        let target = true;
+        let output = GaussianSplatBuilder::new()
+            .positions(vec![0.0; 6])
+            .opacities(vec![0.0; 3])
+            .build()
+            .unwrap_err();
+        let output = matches!(
+            output,
+            MismatchedTensorShape(output, target)
+                if output == [0] && target == [2, 3]
+        );
+        assert_eq!(output, target);
+
+        let target = true;
+        let output = GaussianSplatBuilder::new()
+            .positions(vec![0.0; 3])
+            .colors_sh(vec![0.0; 3])
+            .opacities(vec![0.0; 1])
+            .scalings(vec![0.0; 3])
+            .rotations(vec![0.0; 4])
+            .sh_degree(2)
+            .build()
+            .unwrap_err();
+        let output = matches!(
+            output,
+            MismatchedTensorShape(output, target)
+                if output == [3] && target == [1, 27]
+        );
+        assert_eq!(output, target);
+
+        GaussianSplatBuilder::new()
+            .positions(vec![0.0; 3])
+            .colors_sh(vec![0.0; 3])
+            .opacities(vec![0.0; 1])
+            .scalings(vec![0.0; 3])
+            .rotations(vec![0.0; 4])
+            .build()
+            .unwrap();
+    }
+
+    #[test]
+    fn encode_and_decode() {
+        use super::*;
+
+        let source = &mut Cursor::new(
+            &b"ply\nformat ascii 1.0\n\
+            element vertex 2\n\
+            property float rot_0\nproperty float rot_1\n\
+            property float rot_2\nproperty float rot_3\n\
+            property float opacity\n\
+            property float scale_0\nproperty float scale_1\nproperty float scale_2\n\
+            property float f_dc_0\nproperty float f_dc_1\nproperty float f_dc_2\n\
+            property float x\nproperty float y\nproperty float z\n\
+            element camera 1\n\
+            property uchar id\n\
+            end_header\n\
+            1 0 0 0 0.25 -1 -2 -3 0.1 0.2 0.3 1 2 3\n\
+            0 1 0 0 0.75 -4 -5 -6 0.4 0.5 0.6 4 5 6\n\
+            0\n"[..],
+        );
+        let object = Object::decode(source).unwrap();
+        let splat = GaussianSplat::try_from(&object).unwrap();
+
+        let target = (2, 0, None);
+        let output = (splat.count(), splat.sh_degree(), splat.normals);
+        assert_eq!(output, target);
+
+        let target = vec![0.1, 0.2, 0.3, 0.4, 0.5, 0.6];
+        let output = splat.colors_sh();
+        assert_eq!(output, target);
+
+        let writer = &mut vec![];
+        splat.encode(writer).unwrap();
+        let object = Object::decode(&mut Cursor::new(writer)).unwrap();
+        let output = GaussianSplat::try_from(&object).unwrap();
+
+        let target = 17;
+        assert_eq!(output.to_object().header.elements[0].len(), target);
+        assert_eq!(object.header.elements[0].len(), target);
+
+        let target = Some([&[0.0, 0.0][..]; 3]);
+        assert_eq!(output.normals, target);
+
+        let target = GaussianSplat {
+            normals: output.normals,
+            ..splat
+        };
+        assert_eq!(output, target);
+    }
+
+    #[test]
+    fn try_from_on_invalid_schema() {
+        use super::*;
+
+        let target = true;
+        let output = matches!(
+            GaussianSplat::try_from(&Object::default()).unwrap_err(),
+            MissingSymbol(symbol) if symbol == "element vertex"
+        );
+        assert_eq!(output, target);
+
+        let object = Object::decode(&mut Cursor::new(
+            &b"ply\nformat ascii 1.0\n\
+            element vertex 1\n\
+            property float x\nproperty float y\nproperty double z\n\
+            end_header\n\
+            0 0 0\n"[..],
+        ))
+        .unwrap();
+        let target = true;
+        let output = matches!(
+            GaussianSplat::try_from(&object).unwrap_err(),
+            UnsupportedKind(kind) if kind == "double"
+        );
+        assert_eq!(output, target);
+
+        let object = Object::decode(&mut Cursor::new(
+            &b"ply\nformat ascii 1.0\n\
+            element vertex 1\n\
+            property float x\nproperty float y\nproperty float z\n\
+            end_header\n\
+            0 0 0\n"[..],
+        ))
+        .unwrap();
+        let target = true;
+        let output = matches!(
+            GaussianSplat::try_from(&object).unwrap_err(),
+            MissingSymbol(symbol) if symbol == "property f_dc_0"
+        );
+        assert_eq!(output, target);
+
+        let object = Object::decode(&mut Cursor::new(
+            &b"ply\nformat ascii 1.0\n\
+            element vertex 1\n\
+            property float f_rest_0\nproperty float f_rest_1\n\
+            property float f_rest_2\nproperty float f_rest_3\n\
+            end_header\n\
+            0 0 0 0\n"[..],
+        ))
+        .unwrap();
+        let target = true;
+        let output = matches!(
+            GaussianSplat::try_from(&object).unwrap_err(),
+            MismatchedTensorShape(output, target)
+                if output == [4] && target == [0]
+        );
+        assert_eq!(output, target);
+
+        let mut object = GaussianSplatBuilder::new()
+            .positions(vec![0.0; 3])
+            .colors_sh(vec![0.0; 3])
+            .opacities(vec![0.0; 1])
+            .scalings(vec![0.0; 3])
+            .rotations(vec![0.0; 4])
+            .build()
+            .unwrap();
+        object.header.elements[0].count = 2;
+        let target = true;
+        let output = matches!(
+            GaussianSplat::try_from(&object).unwrap_err(),
+            MismatchedTensorShape(output, target)
+                if output == [1] && target == [2]
+        );
+        assert_eq!(output, target);
+    }
+}
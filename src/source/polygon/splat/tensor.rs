@@ -0,0 +1,235 @@
+//! Gaussian splat tensor module.
+
+pub use super::*;
+pub use burn_tensor::{backend::Backend, Tensor};
+
+use burn_tensor::TensorData;
+
+/// Gaussian splat tensors.
+///
+/// The row count of each tensor is the point count `N`.
+#[derive(Clone, Debug)]
+pub struct GaussianSplatTensors<B: Backend> {
+    /// Colors in SH coefficients with shape of `[N, 3 * (D + 1)^2]`
+    /// in channel-major order.
+    pub colors_sh: Tensor<B, 2>,
+    /// Opacities with shape of `[N, 1]`.
+    pub opacities: Tensor<B, 2>,
+    /// Positions with shape of `[N, 3]`.
+    pub positions: Tensor<B, 2>,
+    /// Rotations in quaternion with shape of `[N, 4]`.
+    pub rotations: Tensor<B, 2>,
+    /// Scalings with shape of `[N, 3]`.
+    pub scalings: Tensor<B, 2>,
+}
+
+/// Interoperability with [`Tensor`].
+impl GaussianSplat<'_> {
+    /// Obtaining the [`GaussianSplatTensors`] from the view.
+    pub fn get_tensors<B: Backend>(
+        &self,
+        device: &B::Device,
+    ) -> GaussianSplatTensors<B> {
+        let count = self.count();
+        let get_tensor = |values: Vec<f32>, width: usize| {
+            Tensor::from_data(TensorData::new(values, [count, width]), device)
+        };
+
+        GaussianSplatTensors {
+            colors_sh: get_tensor(self.colors_sh(), 3 * self.sh_coefficient_count()),
+            opacities: get_tensor(self.opacities.to_owned(), 1),
+            positions: get_tensor(get_rows(&self.positions), 3),
+            rotations: get_tensor(get_rows(&self.rotations), 4),
+            scalings: get_tensor(get_rows(&self.scalings), 3),
+        }
+    }
+
+    /// Obtaining the [`GaussianSplatTensors`] from a polygon object.
+    ///
+    /// # Errors
+    ///
+    /// It returns the errors of [`GaussianSplat::try_from`].
+    #[inline]
+    pub fn get_tensors_from_object<B: Backend>(
+        object: &Object,
+        device: &B::Device,
+    ) -> Result<GaussianSplatTensors<B>, Error> {
+        Ok(GaussianSplat::try_from(object)?.get_tensors(device))
+    }
+
+    /// Obtaining a polygon object in the canonical schema
+    /// from the [`GaussianSplatTensors`].
+    ///
+    /// The format is binary little endian, and the normals are zeros.
+    ///
+    /// # Errors
+    ///
+    /// It returns [`Error::MismatchedTensorShape`] if the shape of any tensor mismatches.
+    /// The row count of [`GaussianSplatTensors::positions`] is the expected point count.
+    pub fn get_object_from_tensors<B: Backend>(
+        tensors: GaussianSplatTensors<B>
+    ) -> Result<Object, Error> {
+        let [count, _] = tensors.positions.dims();
+
+        let [_, sh_width] = tensors.colors_sh.dims();
+        let sh_degree = sh_width
+            .checked_sub(3)
+            .filter(|rest_count| rest_count % 3 == 0)
+            .and_then(get_sh_degree);
+        // NOTE: The expected width is the nearest valid one below.
+        let sh_degree_expected =
+            sh_degree.unwrap_or_else(|| (sh_width / 3).isqrt().saturating_sub(1));
+        let sh_width_expected = 3 * (sh_degree_expected + 1).pow(2);
+
+        let positions = get_values_from_tensor(tensors.positions, count, 3)?;
+        let colors_sh =
+            get_values_from_tensor(tensors.colors_sh, count, sh_width_expected)?;
+        let opacities = get_values_from_tensor(tensors.opacities, count, 1)?;
+        let scalings = get_values_from_tensor(tensors.scalings, count, 3)?;
+        let rotations = get_values_from_tensor(tensors.rotations, count, 4)?;
+
+        GaussianSplatBuilder::new()
+            .colors_sh(colors_sh)
+            .opacities(opacities)
+            .positions(positions)
+            .rotations(rotations)
+            .scalings(scalings)
+            .sh_degree(sh_degree_expected)
+            .build()
+    }
+}
+
+/// Interleaving the columns into the row-major data.
+fn get_rows(columns: &[&[f32]]) -> Vec<f32> {
+    let count = columns
+        .first()
+        .map(|column| column.len())
+        .unwrap_or_default();
+    (0..count)
+        .flat_map(|index| columns.iter().map(move |column| column[index]))
+        .collect()
+}
+
+/// Obtaining the row-major values of a [`Tensor`] with shape of `[count, width]`.
+///
+/// # Errors
+///
+/// It returns [`Error::MismatchedTensorShape`] if the shape mismatches.
+fn get_values_from_tensor<B: Backend>(
+    tensor: Tensor<B, 2>,
+    count: usize,
+    width: usize,
+) -> Result<Vec<f32>, Error> {
+    let dims = tensor.dims();
+    if dims != [count, width] {
+        return Err(MismatchedTensorShape(dims.into(), vec![count, width]));
+    }
+    // NOTE: The data type is converted.
+    Ok(tensor.into_data().convert::<f32>().into_vec().unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn get_tensors_and_object() {
+        use super::*;
+        use burn_ndarray::NdArray;
+
+        let colors_sh = (0..24).map(|value| value as f32).collect::<Vec<_>>();
+        let object = GaussianSplatBuilder::new()
+            .colors_sh(colors_sh.to_owned())
+            .opacities(vec![0.5, -0.5])
+            .positions(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0])
+            .rotations(vec![1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0])
+            .scalings(vec![-1.0, -2.0, -3.0, -4.0, -5.0, -6.0])
+            .sh_degree(1)
+            .build()
+            .unwrap();
+
+        let tensors = GaussianSplat::get_tensors_from_object::<NdArray>(
+            &object,
+            &Default::default(),
+        )
+        .unwrap();
+
+        let target = ([2, 12], [2, 1], [2, 3], [2, 4], [2, 3]);
+        let output = (
+            tensors.colors_sh.dims(),
+            tensors.opacities.dims(),
+            tensors.positions.dims(),
+            tensors.rotations.dims(),
+            tensors.scalings.dims(),
+        );
+        assert_eq!(output, target);
+
+        let target = vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+        let output = tensors.positions.to_data().to_vec::<f32>().unwrap();
+        assert_eq!(output, target);
+
+        let target = colors_sh;
+        let output = tensors.colors_sh.to_data().to_vec::<f32>().unwrap();
+        assert_eq!(output, target);
+
+        let target = object;
+        let output = GaussianSplat::get_object_from_tensors(tensors).unwrap();
+        assert_eq!(output, target);
+    }
+
+    #[test]
+    fn get_object_from_tensors_on_mismatched_shape() {
+        use super::*;
+        use burn_ndarray::NdArray;
+
+        let device = Default::default();
+        let tensors = GaussianSplatTensors::<NdArray> {
+            colors_sh: Tensor::zeros([2, 3], &device),
+            opacities: Tensor::zeros([2, 1], &device),
+            positions: Tensor::zeros([2, 3], &device),
+            rotations: Tensor::zeros([2, 4], &device),
+            scalings: Tensor::zeros([2, 3], &device),
+        };
+
+        let target = true;
+        let output = GaussianSplat::get_object_from_tensors(tensors.to_owned()).is_ok();
+        assert_eq!(output, target);
+
+        let target = (vec![2, 10], vec![2, 3]);
+        let output = matches!(
+            GaussianSplat::get_object_from_tensors(GaussianSplatTensors {
+                colors_sh: Tensor::zeros([2, 10], &device),
+                ..tensors.to_owned()
+            })
+            .unwrap_err(),
+            Error::MismatchedTensorShape(output_0, output_1)
+            if output_0 == target.0 && output_1 == target.1,
+        );
+        let target = true;
+        assert_eq!(output, target);
+
+        let target = (vec![3, 1], vec![2, 1]);
+        let output = matches!(
+            GaussianSplat::get_object_from_tensors(GaussianSplatTensors {
+                opacities: Tensor::zeros([3, 1], &device),
+                ..tensors.to_owned()
+            })
+            .unwrap_err(),
+            Error::MismatchedTensorShape(output_0, output_1)
+            if output_0 == target.0 && output_1 == target.1,
+        );
+        let target = true;
+        assert_eq!(output, target);
+
+        let target = (vec![2, 5], vec![2, 4]);
+        let output = matches!(
+            GaussianSplat::get_object_from_tensors(GaussianSplatTensors {
+                rotations: Tensor::zeros([2, 5], &device),
+                ..tensors
+            })
+            .unwrap_err(),
+            Error::MismatchedTensorShape(output_0, output_1)
+            if output_0 == target.0 && output_1 == target.1,
+        );
+        let target = true;
+        assert_eq!(output, target);
+    }
+}
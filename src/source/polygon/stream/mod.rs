@@ -0,0 +1,11 @@
+//! Polygon streaming module.
+//!
+//! It reads and writes the payload incrementally,
+//! so the whole payload is never materialized in memory.
+
+pub mod reader;
+pub mod writer;
+
+pub use super::*;
+pub use reader::*;
+pub use writer::*;
@@ -0,0 +1,504 @@
+//! Polygon streaming reader implementation.
+
+pub use super::*;
+pub use object::*;
+
+use crate::function::advance;
+use payload::decode::decode_element_rows;
+use std::io::{BufReader, Read, Seek};
+use Error::*;
+
+/// Polygon streaming reader.
+///
+/// It decodes the header up front,
+/// and then reads the payload in chunks of element rows.
+///
+/// The elements and properties not selected are skipped without being kept.
+/// All of them are selected if none is selected explicitly.
+pub struct PlyReader<R> {
+    /// Header.
+    pub header: Header,
+    elem_index: usize,
+    reader: BufReader<R>,
+    row_index: usize,
+    selections: Vec<Vec<bool>>,
+    should_select_all: bool,
+    skip: fn(&mut BufReader<R>, usize) -> Result<(), Error>,
+}
+
+/// A chunk of element rows.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ElementChunk {
+    /// Element metadata.
+    ///
+    /// It contains only the selected properties,
+    /// and its count is the number of rows in the chunk.
+    pub meta: Element,
+    /// Element data of the selected properties.
+    pub data: Vec<Vec<u8>>,
+    /// Element offsets of the selected properties.
+    ///
+    /// The offsets of a list property start with zero in each chunk.
+    pub offsets: Vec<Vec<usize>>,
+    /// The index of the first row in the element.
+    pub start: usize,
+}
+
+impl<R: Read> PlyReader<R> {
+    /// Decoding the header from the `reader`.
+    ///
+    /// The skipped data are discarded by [`advance`].
+    pub fn new(reader: R) -> Result<Self, Error> {
+        Self::new_with_skip(reader, advance)
+    }
+
+    /// Decoding the header from the `reader` with the `skip` function.
+    fn new_with_skip(
+        reader: R,
+        skip: fn(&mut BufReader<R>, usize) -> Result<(), Error>,
+    ) -> Result<Self, Error> {
+        let mut reader = BufReader::new(reader);
+        let header = Header::decode(&mut reader)?;
+        let selections = header
+            .elements
+            .values()
+            .map(|elem| vec![false; elem.len()])
+            .collect();
+
+        #[cfg(all(debug_assertions, not(test)))]
+        log::debug!(target: "gausplat-loader::polygon::stream", "PlyReader::new");
+
+        Ok(Self {
+            header,
+            elem_index: 0,
+            reader,
+            row_index: 0,
+            selections,
+            should_select_all: true,
+            skip,
+        })
+    }
+
+    /// Selecting all properties of the element.
+    ///
+    /// It should be called before reading.
+    ///
+    /// # Errors
+    ///
+    /// It returns [`Error::MissingSymbol`] if the element is missing.
+    pub fn select_element<Q: AsRef<str>>(
+        &mut self,
+        name: Q,
+    ) -> Result<&mut Self, Error> {
+        self.get_selections_mut(name)?.fill(true);
+        Ok(self)
+    }
+
+    /// Selecting the properties of the element.
+    ///
+    /// It should be called before reading.
+    ///
+    /// # Errors
+    ///
+    /// It returns [`Error::MissingSymbol`] if the element or any property is missing.
+    pub fn select_properties<Q: AsRef<str>>(
+        &mut self,
+        name: Q,
+        property_names: impl IntoIterator<Item = Q>,
+    ) -> Result<&mut Self, Error> {
+        let elem = self
+            .header
+            .elements
+            .get(name.as_ref())
+            .ok_or_else(|| MissingSymbol(format!("element {}", name.as_ref())))?;
+        let indices = property_names
+            .into_iter()
+            .map(|property_name| {
+                elem.get_index_of(property_name.as_ref()).ok_or_else(|| {
+                    MissingSymbol(format!("property {}", property_name.as_ref()))
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let selections = self.get_selections_mut(name)?;
+        indices
+            .into_iter()
+            .for_each(|index| selections[index] = true);
+        Ok(self)
+    }
+
+    /// Reading the next chunk of at most `row_count` rows.
+    ///
+    /// A chunk never spans two elements,
+    /// and the elements without rows or selected properties are skipped.
+    /// It reads one row per chunk if `row_count` is zero.
+    ///
+    /// It returns `None` after the last element.
+    ///
+    /// # Errors
+    ///
+    /// It returns [`Error::OutOfBounds`] if the size of a skipped element overflows.
+    pub fn read_chunk(
+        &mut self,
+        row_count: usize,
+    ) -> Result<Option<ElementChunk>, Error> {
+        let result = self.read_chunk_inner(row_count.max(1));
+        if result.is_err() {
+            // NOTE: The reader is unusable after an error.
+            self.elem_index = self.header.elements.len();
+        }
+        result
+    }
+
+    /// Return an iterator over the chunks of at most `row_count` rows.
+    ///
+    /// See [`PlyReader::read_chunk`].
+    #[inline]
+    pub fn chunks(
+        &mut self,
+        row_count: usize,
+    ) -> impl '_ + Iterator<Item = Result<ElementChunk, Error>> {
+        std::iter::from_fn(move || self.read_chunk(row_count).transpose())
+    }
+
+    /// Get the mutable selections of the element.
+    ///
+    /// The other elements are no longer selected implicitly after the first call.
+    fn get_selections_mut<Q: AsRef<str>>(
+        &mut self,
+        name: Q,
+    ) -> Result<&mut Vec<bool>, Error> {
+        let name = name.as_ref();
+        let index = self
+            .header
+            .elements
+            .get_index_of(name)
+            .ok_or_else(|| MissingSymbol(format!("element {name}")))?;
+        self.should_select_all = false;
+        Ok(&mut self.selections[index])
+    }
+
+    /// Reading the next chunk of at most `row_count` rows,
+    /// where `row_count` is positive.
+    fn read_chunk_inner(
+        &mut self,
+        row_count: usize,
+    ) -> Result<Option<ElementChunk>, Error> {
+        while let Some(elem) = self.header.elements.get_index(self.elem_index) {
+            let elem = elem.1;
+            let selections = match self.should_select_all {
+                true => vec![true; elem.len()],
+                false => self.selections[self.elem_index].to_owned(),
+            };
+            let row_count_remaining = elem.count - self.row_index;
+
+            if row_count_remaining == 0 {
+                self.elem_index += 1;
+                self.row_index = 0;
+                continue;
+            }
+
+            if !selections.contains(&true) {
+                let is_fixed_size =
+                    !self.header.format.is_ascii() && !elem.values().any(|p| p.is_list());
+                if is_fixed_size {
+                    let elem_size = elem.property_sizes().sum::<Result<usize, _>>()?;
+                    let size =
+                        row_count_remaining.checked_mul(elem_size).ok_or_else(|| {
+                            OutOfBounds(
+                                row_count_remaining,
+                                usize::MAX / elem_size,
+                                format!("element {}", elem.name),
+                            )
+                        })?;
+                    (self.skip)(&mut self.reader, size)?;
+                } else {
+                    decode_element_rows(
+                        &mut self.reader,
+                        elem,
+                        &self.header.format,
+                        row_count_remaining,
+                        &selections,
+                    )?;
+                }
+                self.row_index = elem.count;
+                continue;
+            }
+
+            let row_count = row_count.min(row_count_remaining);
+            let (data, offsets) = decode_element_rows(
+                &mut self.reader,
+                elem,
+                &self.header.format,
+                row_count,
+                &selections,
+            )?;
+            let properties = elem
+                .iter()
+                .zip(&selections)
+                .filter(|(_, is_selected)| **is_selected)
+                .map(|((name, prop), _)| (name.to_owned(), prop.to_owned()))
+                .collect::<IndexMap<_, _>>();
+            let chunk = ElementChunk {
+                meta: Element {
+                    count: row_count,
+                    name: elem.name.to_owned(),
+                    properties: properties.into(),
+                },
+                data,
+                offsets,
+                start: self.row_index,
+            };
+
+            self.row_index += row_count;
+            return Ok(Some(chunk));
+        }
+
+        Ok(None)
+    }
+}
+
+impl<R: Read + Seek> PlyReader<R> {
+    /// Decoding the header from the seekable `reader`.
+    ///
+    /// The skipped data are discarded by seeking.
+    /// Seeking beyond the end is not an error until the next read.
+    pub fn new_seekable(reader: R) -> Result<Self, Error> {
+        Self::new_with_skip(reader, |reader, n| {
+            Ok(reader.seek_relative(i64::try_from(n)?)?)
+        })
+    }
+}
+
+impl ElementChunk {
+    /// Get the element entry of the chunk.
+    ///
+    /// It can be accessed in the same way as [`Object::elem`].
+    #[inline]
+    pub fn as_entry(&self) -> ElementEntry<'_> {
+        ElementEntry {
+            meta: &self.meta,
+            data: &self.data,
+            offsets: &self.offsets,
+        }
+    }
+}
+
+impl<R> std::fmt::Debug for PlyReader<R> {
+    #[inline]
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        f.debug_struct("PlyReader")
+            .field("header", &self.header)
+            .field("elem_index", &self.elem_index)
+            .field("row_index", &self.row_index)
+            .field("selections", &self.selections)
+            .field("should_select_all", &self.should_select_all)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    #[test]
+    fn read_chunk_on_example_another_cube() {
+        use super::*;
+
+        let sources = [
+            &include_bytes!(
+                "../../../../examples/data/polygon/another-cube.greg-turk.ascii.ply"
+            )[..],
+            &include_bytes!(
+                "../../../../examples/data/polygon/another-cube.greg-turk.binary-be.ply"
+            )[..],
+            &include_bytes!(
+                "../../../../examples/data/polygon/another-cube.greg-turk.binary-le.ply"
+            )[..],
+        ];
+
+        sources.into_iter().for_each(|source| {
+            let object = Object::decode(&mut Cursor::new(source)).unwrap();
+            let mut reader = PlyReader::new(Cursor::new(source)).unwrap();
+            reader
+                .select_properties("vertex", ["z", "x"])
+                .unwrap()
+                .select_properties("edge", ["vertex2"])
+                .unwrap();
+
+            let chunks = reader.chunks(3).collect::<Result<Vec<_>, _>>().unwrap();
+
+            let target = vec![
+                ("vertex", 0, 3),
+                ("vertex", 3, 3),
+                ("vertex", 6, 2),
+                ("edge", 0, 3),
+                ("edge", 3, 2),
+            ];
+            let output = chunks
+                .iter()
+                .map(|c| (c.meta.name.as_str(), c.start, c.meta.count))
+                .collect::<Vec<_>>();
+            assert_eq!(output, target);
+
+            let target = vec!["x", "z"];
+            let output = chunks[0].meta.keys().collect::<Vec<_>>();
+            assert_eq!(output, target);
+
+            let target = object.elem_prop("vertex", "z").unwrap().data.to_owned();
+            let output = chunks[..3]
+                .iter()
+                .flat_map(|c| c.data[1].to_owned())
+                .collect::<Vec<_>>();
+            assert_eq!(output, target);
+
+            let target = object.elem_prop("edge", "vertex2").unwrap().data.to_owned();
+            let output = chunks[3..]
+                .iter()
+                .flat_map(|c| c.data[0].to_owned())
+                .collect::<Vec<_>>();
+            assert_eq!(output, target);
+        });
+    }
+
+    #[test]
+    fn read_chunk_on_example_list_payload() {
+        use super::*;
+
+        let source = &include_bytes!(
+            "../../../../examples/data/polygon/another-cube.greg-turk.binary-le.ply"
+        )[..];
+        let object = Object::decode(&mut Cursor::new(source)).unwrap();
+        let mut reader = PlyReader::new_seekable(Cursor::new(source)).unwrap();
+        reader.select_element("face").unwrap();
+
+        let chunk = reader.read_chunk(4).unwrap().unwrap();
+
+        let target = object.elem_prop("face", "vertex_index").unwrap();
+        let target = target.lists::<i32>().unwrap().take(4).collect::<Vec<_>>();
+        let output = chunk.as_entry();
+        let output = output.prop("vertex_index").unwrap();
+        let output = output.lists::<i32>().unwrap().collect::<Vec<_>>();
+        assert_eq!(output, target);
+
+        let target = (4, 3);
+        let output = reader.read_chunk(4).unwrap().unwrap();
+        let output = (output.start, output.meta.count);
+        assert_eq!(output, target);
+
+        let target = None;
+        let output = reader.read_chunk(4).unwrap();
+        assert_eq!(output, target);
+    }
+
+    #[test]
+    fn read_chunk_on_all_examples() {
+        use super::*;
+
+        let sources = [
+            &include_bytes!("../../../../examples/data/polygon/empty-element.ascii.ply")
+                [..],
+            &include_bytes!(
+                "../../../../examples/data/polygon/empty-element.binary-le.ply"
+            )[..],
+            &include_bytes!(
+                "../../../../examples/data/polygon/supported-data-types-common.ply"
+            )[..],
+            &include_bytes!(
+                "../../../../examples/data/polygon/supported-data-types-special.ply"
+            )[..],
+            &include_bytes!("../../../../examples/data/polygon/triangle.binary-be.ply")[..],
+            &include_bytes!("../../../../examples/data/polygon/triangle.binary-le.ply")[..],
+        ];
+
+        sources.into_iter().for_each(|source| {
+            let object = Object::decode(&mut Cursor::new(source)).unwrap();
+            let mut reader = PlyReader::new(Cursor::new(source)).unwrap();
+
+            let target = object
+                .elems()
+                .filter(|e| e.meta.count > 0 && !e.meta.is_empty())
+                .map(|e| e.data.to_owned())
+                .collect::<Vec<_>>();
+            let output = reader
+                .chunks(usize::MAX)
+                .map(|c| c.map(|c| c.data))
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap();
+            assert_eq!(output, target);
+        });
+    }
+
+    #[test]
+    fn read_chunk_on_truncated_payload() {
+        use super::*;
+
+        let source = &include_bytes!(
+            "../../../../examples/data/polygon/another-cube.greg-turk.binary-le.ply"
+        )[..];
+        let source = &source[..source.len() - 1];
+        let mut reader = PlyReader::new(Cursor::new(source)).unwrap();
+        reader.select_element("vertex").unwrap();
+
+        let target = true;
+        let output = reader.read_chunk(8).is_ok_and(|c| c.is_some());
+        assert_eq!(output, target);
+
+        let target = true;
+        let output = matches!(reader.read_chunk(8), Err(Error::Io(_)));
+        assert_eq!(output, target);
+
+        let target = None;
+        let output = reader.read_chunk(8).unwrap();
+        assert_eq!(output, target);
+    }
+
+    #[test]
+    fn read_chunk_on_huge_skipped_element() {
+        use super::*;
+
+        let source = &b"ply\nformat binary_little_endian 1.0\n\
+            element a 18446744073709551615\nproperty double x\n\
+            element b 1\nproperty uchar y\nend_header\n\x01"[..];
+        let mut reader = PlyReader::new(Cursor::new(source)).unwrap();
+        reader.select_element("b").unwrap();
+
+        let target = true;
+        let output = matches!(
+            reader.read_chunk(1).unwrap_err(),
+            Error::OutOfBounds(_, _, elem) if elem == "element a",
+        );
+        assert_eq!(output, target);
+
+        let target = None;
+        let output = reader.read_chunk(1).unwrap();
+        assert_eq!(output, target);
+    }
+
+    #[test]
+    fn select_on_missing_symbols() {
+        use super::*;
+
+        let source = &include_bytes!(
+            "../../../../examples/data/polygon/triangle.binary-le.ply"
+        )[..];
+        let mut reader = PlyReader::new(Cursor::new(source)).unwrap();
+
+        let target = true;
+        let output = matches!(
+            reader.select_element("vretex").unwrap_err(),
+            Error::MissingSymbol(symbol) if symbol == "element vretex",
+        );
+        assert_eq!(output, target);
+
+        let target = true;
+        let output = matches!(
+            reader.select_properties("vertex", ["x", "w"]).unwrap_err(),
+            Error::MissingSymbol(symbol) if symbol == "property w",
+        );
+        assert_eq!(output, target);
+    }
+}
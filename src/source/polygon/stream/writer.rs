@@ -0,0 +1,372 @@
+//! Polygon streaming writer implementation.
+
+pub use super::*;
+
+use object::encode::encode_element_rows;
+use std::io::{BufWriter, Write};
+use Error::*;
+
+/// Polygon streaming writer.
+///
+/// It encodes the header up front,
+/// and then writes the payload in chunks of element rows.
+///
+/// The elements are written in the order of the header,
+/// and [`PlyWriter::finish`] should be called after the last chunk.
+/// The elements without properties need no chunks.
+pub struct PlyWriter<W: Write> {
+    /// Header.
+    pub header: Header,
+    elem_index: usize,
+    row_index: usize,
+    writer: BufWriter<W>,
+}
+
+impl<W: Write> PlyWriter<W> {
+    /// Encoding the `header` to the `writer`.
+    ///
+    /// # Errors
+    ///
+    /// It returns [`Error::InvalidKind`] if any property kind has no size.
+    pub fn new(
+        writer: W,
+        header: Header,
+    ) -> Result<Self, Error> {
+        header.values().try_for_each(|elem| {
+            elem.property_sizes().try_for_each(|size| size.map(drop))
+        })?;
+
+        let mut writer = BufWriter::new(writer);
+        header.encode(&mut writer)?;
+
+        #[cfg(all(debug_assertions, not(test)))]
+        log::debug!(target: "gausplat-loader::polygon::stream", "PlyWriter::new");
+
+        Ok(Self {
+            header,
+            elem_index: 0,
+            row_index: 0,
+            writer,
+        })
+    }
+
+    /// Writing the chunk of element rows.
+    ///
+    /// The data of the chunk are in native endianness,
+    /// and they are converted to the format of the header.
+    /// [`ElementChunk::start`] is ignored.
+    ///
+    /// The chunk is validated before being written:
+    ///
+    /// - It returns [`Error::MissingSymbol`] if the element or any property
+    ///   is not declared in the header.
+    /// - It returns [`Error::InvalidKind`] if any property kind
+    ///   differs from the declared one.
+    /// - It returns [`Error::MismatchedElementCount`] if the rows exceed the declared count,
+    ///   or any previous element is incomplete.
+    /// - It returns [`Error::OutOfBounds`] if the data or the offsets
+    ///   mismatch the row count.
+    pub fn write_chunk(
+        &mut self,
+        chunk: &ElementChunk,
+    ) -> Result<&mut Self, Error> {
+        let name = &chunk.meta.name;
+        let index = self
+            .header
+            .get_index_of(name)
+            .ok_or_else(|| MissingSymbol(format!("element {name}")))?;
+        if index < self.elem_index {
+            // NOTE: The index is guaranteed to be valid.
+            let elem = &self.header[index];
+            return Err(MismatchedElementCount(
+                elem.count + chunk.meta.count,
+                elem.count,
+                format!("element {name}"),
+            ));
+        }
+        while self.elem_index < index {
+            self.finish_element()?;
+        }
+
+        // NOTE: The index is guaranteed to be valid.
+        let elem = &self.header[index];
+        let row_count = self.row_index + chunk.meta.count;
+        if row_count > elem.count {
+            return Err(MismatchedElementCount(
+                row_count,
+                elem.count,
+                format!("element {name}"),
+            ));
+        }
+        validate_chunk(elem, chunk)?;
+
+        // NOTE: The chunk is encoded before being written,
+        // so an invalid chunk writes nothing.
+        let mut data = vec![];
+        encode_element_rows(&mut data, &chunk.as_entry(), &self.header.format)?;
+        self.writer.write_all(&data)?;
+
+        self.row_index = row_count;
+        Ok(self)
+    }
+
+    /// Finishing the writing and returning the inner writer.
+    ///
+    /// # Errors
+    ///
+    /// It returns [`Error::MismatchedElementCount`] if any element is incomplete.
+    pub fn finish(mut self) -> Result<W, Error> {
+        while self.elem_index < self.header.len() {
+            self.finish_element()?;
+        }
+
+        #[cfg(all(debug_assertions, not(test)))]
+        log::debug!(target: "gausplat-loader::polygon::stream", "PlyWriter::finish");
+
+        Ok(self.writer.into_inner().map_err(|err| err.into_error())?)
+    }
+
+    /// Moving to the next element after checking the row count of the current one.
+    ///
+    /// The remaining rows of an element without properties are written here.
+    fn finish_element(&mut self) -> Result<(), Error> {
+        // NOTE: The index is guaranteed to be valid.
+        let elem = &self.header[self.elem_index];
+        if elem.is_empty() && self.row_index < elem.count {
+            let meta = Element {
+                count: elem.count - self.row_index,
+                ..elem.to_owned()
+            };
+            let entry = ElementEntry {
+                meta: &meta,
+                data: &vec![],
+                offsets: &[],
+            };
+            encode_element_rows(&mut self.writer, &entry, &self.header.format)?;
+            self.row_index = elem.count;
+        }
+        if self.row_index != elem.count {
+            return Err(MismatchedElementCount(
+                self.row_index,
+                elem.count,
+                format!("element {}", elem.name),
+            ));
+        }
+        self.elem_index += 1;
+        self.row_index = 0;
+        Ok(())
+    }
+}
+
+impl<W: Write> std::fmt::Debug for PlyWriter<W> {
+    #[inline]
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        f.debug_struct("PlyWriter")
+            .field("header", &self.header)
+            .field("elem_index", &self.elem_index)
+            .field("row_index", &self.row_index)
+            .finish()
+    }
+}
+
+/// Validating the properties and data of the chunk against the element.
+fn validate_chunk(
+    elem: &Element,
+    chunk: &ElementChunk,
+) -> Result<(), Error> {
+    if let Some(prop) = elem.values().nth(chunk.meta.len()) {
+        return Err(MissingSymbol(format!("property {}", prop.name)));
+    }
+
+    let row_count = chunk.meta.count;
+    let prop_sizes = elem.property_sizes().collect::<Result<Vec<_>, _>>()?;
+    chunk
+        .meta
+        .values()
+        .enumerate()
+        .try_for_each(|(prop_index, prop)| {
+            let (prop_expected, size) = elem
+                .get_index(prop_index)
+                .map(|(_, prop)| prop)
+                .zip(prop_sizes.get(prop_index))
+                .filter(|(prop_expected, _)| prop_expected.name == prop.name)
+                .ok_or_else(|| MissingSymbol(format!("property {}", prop.name)))?;
+            if prop.kind != prop_expected.kind {
+                return Err(InvalidKind(prop.kind.to_string()));
+            }
+
+            let data_size = chunk.data.get(prop_index).map(Vec::len).unwrap_or_default();
+            let data_size_expected = if prop.is_list() {
+                let offsets = chunk
+                    .offsets
+                    .get(prop_index)
+                    .map(Vec::as_slice)
+                    .unwrap_or_default();
+                if offsets.len() != row_count + 1 {
+                    return Err(OutOfBounds(
+                        offsets.len(),
+                        row_count + 1,
+                        format!("offsets of property {}", prop.name),
+                    ));
+                }
+                // NOTE: The offsets are not empty.
+                offsets[row_count] * size
+            } else {
+                row_count * size
+            };
+            if data_size != data_size_expected {
+                return Err(OutOfBounds(
+                    data_size,
+                    data_size_expected,
+                    format!("data of property {}", prop.name),
+                ));
+            }
+
+            Ok(())
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    #[test]
+    fn write_chunk_on_examples() {
+        use super::*;
+
+        let sources = [
+            &include_bytes!(
+                "../../../../examples/data/polygon/another-cube.greg-turk.ascii.ply"
+            )[..],
+            &include_bytes!("../../../../examples/data/polygon/empty-element.ascii.ply")
+                [..],
+        ];
+
+        sources.into_iter().for_each(|source| {
+            let mut object = Object::decode(&mut Cursor::new(source)).unwrap();
+
+            [
+                Format::Ascii,
+                Format::BinaryBigEndian,
+                Format::BinaryLittleEndian,
+            ]
+            .into_iter()
+            .for_each(|format| {
+                object.header.format = format;
+                let mut reader = PlyReader::new(Cursor::new(source)).unwrap();
+                let mut writer =
+                    PlyWriter::new(vec![], object.header.to_owned()).unwrap();
+                reader
+                    .chunks(3)
+                    .try_for_each(|chunk| {
+                        writer.write_chunk(&chunk?)?;
+                        Ok::<_, Error>(())
+                    })
+                    .unwrap();
+                let output = writer.finish().unwrap();
+
+                let target = &mut vec![];
+                object.encode(target).unwrap();
+                assert_eq!(output, *target);
+            });
+        });
+    }
+
+    #[test]
+    fn write_chunk_on_invalid_chunks() {
+        use super::*;
+
+        let source = &include_bytes!(
+            "../../../../examples/data/polygon/triangle.binary-le.ply"
+        )[..];
+        let object = Object::decode(&mut Cursor::new(source)).unwrap();
+        let chunk = PlyReader::new(Cursor::new(source))
+            .unwrap()
+            .read_chunk(usize::MAX)
+            .unwrap()
+            .unwrap();
+        let mut writer = PlyWriter::new(vec![], object.header.to_owned()).unwrap();
+
+        let mut chunk_invalid = chunk.to_owned();
+        chunk_invalid.meta.name = "vretex".into();
+        let target = true;
+        let output = matches!(
+            writer.write_chunk(&chunk_invalid).unwrap_err(),
+            Error::MissingSymbol(symbol) if symbol == "element vretex",
+        );
+        assert_eq!(output, target);
+
+        let mut chunk_invalid = chunk.to_owned();
+        chunk_invalid.meta.values_mut().for_each(|prop| {
+            prop.kind = PropertyKind::Scalar("double".into());
+        });
+        let target = true;
+        let output = matches!(
+            writer.write_chunk(&chunk_invalid).unwrap_err(),
+            Error::InvalidKind(kind) if kind == "double",
+        );
+        assert_eq!(output, target);
+
+        let mut chunk_invalid = chunk.to_owned();
+        chunk_invalid.data[0].pop();
+        let target = true;
+        let output = matches!(
+            writer.write_chunk(&chunk_invalid).unwrap_err(),
+            Error::OutOfBounds(11, 12, _),
+        );
+        assert_eq!(output, target);
+
+        let mut chunk_invalid = chunk.to_owned();
+        chunk_invalid.meta.pop();
+        let target = true;
+        let output = matches!(
+            writer.write_chunk(&chunk_invalid).unwrap_err(),
+            Error::MissingSymbol(symbol) if symbol == "property y",
+        );
+        assert_eq!(output, target);
+
+        writer.write_chunk(&chunk).unwrap();
+        let target = true;
+        let output = matches!(
+            writer.write_chunk(&chunk).unwrap_err(),
+            Error::MismatchedElementCount(6, 3, _),
+        );
+        assert_eq!(output, target);
+
+        let target = object.header.to_string().len() + 24;
+        let output = writer.finish().unwrap().len();
+        assert_eq!(output, target);
+    }
+
+    #[test]
+    fn finish_on_incomplete_elements() {
+        use super::*;
+
+        let source = &include_bytes!(
+            "../../../../examples/data/polygon/another-cube.greg-turk.binary-le.ply"
+        )[..];
+        let mut reader = PlyReader::new(Cursor::new(source)).unwrap();
+        let mut writer = PlyWriter::new(vec![], reader.header.to_owned()).unwrap();
+        let chunks = reader.chunks(4).collect::<Result<Vec<_>, _>>().unwrap();
+
+        writer.write_chunk(&chunks[0]).unwrap();
+        let target = true;
+        let output = matches!(
+            writer.write_chunk(&chunks[2]).unwrap_err(),
+            Error::MismatchedElementCount(4, 8, elem) if elem == "element vertex",
+        );
+        assert_eq!(output, target);
+
+        writer.write_chunk(&chunks[1]).unwrap();
+        writer.write_chunk(&chunks[2]).unwrap();
+        let target = true;
+        let output = matches!(
+            writer.finish().unwrap_err(),
+            Error::MismatchedElementCount(4, 7, elem) if elem == "element face",
+        );
+        assert_eq!(output, target);
+    }
+}